@@ -0,0 +1,91 @@
+//! A counter component, embedded twice by the application.
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Paragraph, Widget},
+};
+use std::{thread, time::Duration};
+use teatui::{
+    component::{Component, Effects},
+    update::Update,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct Counter {
+    value: u64,
+    doubling: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Increment,
+    Decrement,
+    Double,
+    Doubled(u64),
+}
+
+#[derive(Debug)]
+pub enum Effect {
+    Double(u64),
+}
+
+impl Component for Counter {
+    type Message = Message;
+    type Effect = Effect;
+
+    fn update(self, message: Message) -> Update<Self, Effect> {
+        match message {
+            Message::Increment => Update::Next(
+                Self {
+                    value: self.value + 1,
+                    ..self
+                },
+                None,
+            ),
+            Message::Decrement => Update::Next(
+                Self {
+                    value: self.value.saturating_sub(1),
+                    ..self
+                },
+                None,
+            ),
+            Message::Double => {
+                let effect = Effect::Double(self.value);
+                Update::Next(
+                    Self {
+                        doubling: true,
+                        ..self
+                    },
+                    Some(effect),
+                )
+            }
+            Message::Doubled(value) => Update::Next(
+                Self {
+                    value,
+                    doubling: false,
+                },
+                None,
+            ),
+        }
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let status = if self.doubling { " (doubling…)" } else { "" };
+
+        Paragraph::new(format!("{}{status}", self.value))
+            .centered()
+            .render(area, buf);
+    }
+}
+
+/// Runs the effects of a counter, pretending the computer needs some time to double a number.
+impl Effects for Counter {
+    fn run(effect: Effect) -> Option<Message> {
+        match effect {
+            Effect::Double(value) => {
+                thread::sleep(Duration::from_millis(500));
+                Some(Message::Doubled(value * 2))
+            }
+        }
+    }
+}
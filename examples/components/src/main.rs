@@ -0,0 +1,191 @@
+//! # [TeaTui] Components example
+//!
+//! The same counter component is embedded twice, each one with its own state and effects.
+//! Keys go to the focused counter, `tab` moves the focus and clicking a counter focuses it.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use teatui::{
+    ProgramError,
+    component::{self, Component, Effects},
+    focus::Focus,
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    regions::Clickable,
+    update::Update,
+};
+
+mod counter;
+
+use counter::Counter;
+
+fn main() -> Result<(), Box<ProgramError<App, Message, Effect>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|app: &App| &app.keymap)
+        .with_focus_keymap(|app: &App| app.focus.current().map(|pane| app.pane_keymap(*pane)))
+        .with_focus(|app: &App| &app.focus, Message::Focus)
+        .with_mouse_capture();
+
+    teatui::start_with(
+        options,
+        init,
+        App::update,
+        component::render,
+        component::effects,
+    )
+    .map_err(Box::new)
+}
+
+fn init() -> (App, Option<Effect>) {
+    (App::default(), None)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Apples,
+    Pears,
+}
+
+#[derive(Debug, Clone)]
+pub struct App {
+    apples: Counter,
+    pears: Counter,
+    focus: Focus<Pane>,
+    keymap: Keymap<Message>,
+    apples_keymap: Keymap<Message>,
+    pears_keymap: Keymap<Message>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        let keymap = Keymap::new()
+            .bind("1", Message::FocusPane(Pane::Apples), "focus apples")
+            .bind("2", Message::FocusPane(Pane::Pears), "focus pears")
+            .bind("q", Message::Exit, "quit")
+            .bind("esc", Message::Exit, "quit");
+
+        Self {
+            apples: Counter::default(),
+            pears: Counter::default(),
+            focus: Focus::new([Pane::Apples, Pane::Pears]),
+            keymap,
+            apples_keymap: counter_keymap(Message::Apples),
+            pears_keymap: counter_keymap(Message::Pears),
+        }
+    }
+}
+
+/// The same keys drive whichever counter is focused
+fn counter_keymap(lift: fn(counter::Message) -> Message) -> Keymap<Message> {
+    Keymap::new()
+        .bind("right", lift(counter::Message::Increment), "increment")
+        .bind("left", lift(counter::Message::Decrement), "decrement")
+        .bind("d", lift(counter::Message::Double), "double")
+}
+
+impl App {
+    fn pane_keymap(&self, pane: Pane) -> &Keymap<Message> {
+        match pane {
+            Pane::Apples => &self.apples_keymap,
+            Pane::Pears => &self.pears_keymap,
+        }
+    }
+
+    fn render_pane(&self, pane: Pane, area: Rect, buf: &mut Buffer) {
+        let (title, counter) = match pane {
+            Pane::Apples => (" Apples ", &self.apples),
+            Pane::Pears => (" Pears ", &self.pears),
+        };
+
+        let block = Block::bordered()
+            .title(Line::from(title).centered())
+            .border_style(self.focus.style(&pane));
+
+        counter.widget().render(block.inner(area), buf);
+        block.render(area, buf);
+        area.on_click(Message::FocusPane(pane));
+    }
+}
+
+/// Messages of the application, wrapping the messages of each counter
+#[derive(Debug, Clone)]
+pub enum Message {
+    Apples(counter::Message),
+    Pears(counter::Message),
+    Focus(Focus<Pane>),
+    FocusPane(Pane),
+    Exit,
+    NoOp,
+}
+
+impl From<Event> for Message {
+    fn from(_value: Event) -> Self {
+        Self::NoOp
+    }
+}
+
+/// Effects of the application, wrapping the effects of each counter
+#[derive(Debug)]
+pub enum Effect {
+    Apples(counter::Effect),
+    Pears(counter::Effect),
+}
+
+impl Component for App {
+    type Message = Message;
+    type Effect = Effect;
+
+    fn update(self, message: Message) -> Update<Self, Effect> {
+        match message {
+            Message::Apples(message) => self
+                .apples
+                .update(message)
+                .lift(|apples| App { apples, ..self }, Effect::Apples),
+            Message::Pears(message) => self
+                .pears
+                .update(message)
+                .lift(|pears| App { pears, ..self }, Effect::Pears),
+            Message::Focus(focus) => Update::Next(App { focus, ..self }, None),
+            Message::FocusPane(pane) => {
+                let focus = self.focus.focus(&pane);
+                Update::Next(App { focus, ..self }, None)
+            }
+            Message::Exit => Update::Exit,
+            Message::NoOp => Update::Next(self, None),
+        }
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let [counters_area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let [apples_area, pears_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(counters_area);
+
+        self.render_pane(Pane::Apples, apples_area, buf);
+        self.render_pane(Pane::Pears, pears_area, buf);
+
+        // The help follows the focus, showing the keys of the focused counter first
+        let help = match self.focus.current() {
+            Some(pane) => Help::short(self.pane_keymap(*pane)).with_keymap(&self.keymap),
+            None => Help::short(&self.keymap),
+        };
+
+        help.render(help_area, buf);
+    }
+}
+
+/// The effects of each counter are run by the counter,
+/// and the resulting messages are routed back to the counter that asked for them
+impl Effects for App {
+    fn run(effect: Effect) -> Option<Message> {
+        match effect {
+            Effect::Apples(effect) => Counter::run(effect).map(Message::Apples),
+            Effect::Pears(effect) => Counter::run(effect).map(Message::Pears),
+        }
+    }
+}
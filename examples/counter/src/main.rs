@@ -1,25 +1,102 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::Event;
 use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
     style::Stylize,
     text::Line,
-    widgets::{Block, Paragraph},
+    widgets::{Block, Paragraph, Widget},
+};
+use teatui::{
+    ProgramError,
+    help::Help,
+    keymap::{Keymap, config::Actions},
+    options::ProgramOptions,
+    update::Update,
 };
-use teatui::{ProgramError, update::Update};
 
+/// Run with `cargo run -p counter -- examples/counter/keymap.toml` to load the bindings
+/// from a file, which is reloaded every time it changes.
 fn main() -> Result<(), ProgramError<Model, Message, Effect>> {
-    teatui::start(|| (Model::default(), None), update, view, run_effects)
+    let options = ProgramOptions::default().with_keymap(|model: &Model| &model.keymap);
+
+    let Some(path) = std::env::args().nth(1) else {
+        return teatui::start_with(
+            options,
+            || (Model::default(), None),
+            update,
+            view,
+            run_effects,
+        );
+    };
+
+    // Invalid bindings are reported before the terminal is taken over
+    let keymap = match Keymap::load(&path, &actions()) {
+        Ok(keymap) => keymap,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    };
+
+    let options = options.with_keymap_reload(path, actions(), |keymap| {
+        Message::KeymapReloaded(keymap.map_err(|error| error.to_string()))
+    });
+
+    teatui::start_with(
+        options,
+        move || {
+            let model = Model {
+                keymap: keymap.clone(),
+                ..Model::default()
+            };
+            (model, None)
+        },
+        update,
+        view,
+        run_effects,
+    )
+}
+
+/// Actions the keymap file can bind keys to
+fn actions() -> Actions<Message> {
+    Actions::new()
+        .action("increment", Message::IncCounter, "increment")
+        .action("decrement", Message::DecCounter, "decrement")
+        .action("reset", Message::Reset, "reset")
+        .action("quit", Message::Exit, "quit")
 }
 
 /// Defines the state of the application
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Model {
     pub counter: u64,
+    pub keymap: Keymap<Message>,
+    pub keymap_error: Option<String>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        let keymap = Keymap::new()
+            .bind("right", Message::IncCounter, "increment")
+            .bind("left", Message::DecCounter, "decrement")
+            .bind("g g", Message::Reset, "reset")
+            .bind("q", Message::Exit, "quit")
+            .bind("esc", Message::Exit, "quit")
+            .bind("ctrl+c", Message::Exit, "quit");
+
+        Self {
+            counter: 0,
+            keymap,
+            keymap_error: None,
+        }
+    }
 }
 
 impl Model {
     pub fn increment_counter(model: Model) -> Model {
         Model {
             counter: model.counter + 1,
+            ..model
         }
     }
 
@@ -30,7 +107,14 @@ impl Model {
             model.counter - 1
         };
 
-        Model { counter }
+        Model { counter, ..model }
+    }
+
+    pub fn reset_counter(model: Model) -> Model {
+        Model {
+            counter: 0,
+            ..model
+        }
     }
 }
 
@@ -39,52 +123,21 @@ impl Model {
 pub enum Effect {}
 
 /// Messages that represent a change of state in the application
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Message {
     IncCounter,
     DecCounter,
+    Reset,
+    KeymapReloaded(Result<Keymap<Message>, String>),
     Exit,
     NoOp,
 }
 
+/// Key presses are resolved through the keymap of the model,
+/// every other event is ignored
 impl From<crossterm::event::Event> for Message {
-    fn from(value: Event) -> Self {
-        match value {
-            Event::Key(KeyEvent {
-                code: KeyCode::Esc | KeyCode::Char('q'),
-                kind: KeyEventKind::Press,
-                state: _,
-                modifiers: _,
-            }) => Self::Exit,
-
-            Event::Key(KeyEvent {
-                code: KeyCode::Right,
-                kind: KeyEventKind::Press,
-                state: _,
-                modifiers: _,
-            }) => Self::IncCounter,
-
-            Event::Key(KeyEvent {
-                code: KeyCode::Left,
-                kind: KeyEventKind::Press,
-                state: _,
-                modifiers: _,
-            }) => Self::DecCounter,
-
-            Event::Key(KeyEvent {
-                code: KeyCode::Char('c') | KeyCode::Char('C'),
-                modifiers: KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                state: _,
-            }) => Self::Exit,
-
-            Event::FocusGained
-            | Event::FocusLost
-            | Event::Key(_)
-            | Event::Mouse(_)
-            | Event::Paste(_)
-            | Event::Resize(_, _) => Self::NoOp,
-        }
+    fn from(_value: Event) -> Self {
+        Self::NoOp
     }
 }
 
@@ -98,6 +151,23 @@ pub fn update(model: Model, msg: Message) -> Update<Model, Effect> {
         Message::NoOp => Update::Next(model, None),
         Message::IncCounter => Update::Next(Model::increment_counter(model), None),
         Message::DecCounter => Update::Next(Model::decrement_counter(model), None),
+        Message::Reset => Update::Next(Model::reset_counter(model), None),
+        Message::KeymapReloaded(Ok(keymap)) => Update::Next(
+            Model {
+                keymap,
+                keymap_error: None,
+                ..model
+            },
+            None,
+        ),
+        // The previous keymap is kept until the file is fixed
+        Message::KeymapReloaded(Err(error)) => Update::Next(
+            Model {
+                keymap_error: Some(error),
+                ..model
+            },
+            None,
+        ),
     }
 }
 
@@ -108,23 +178,45 @@ pub fn run_effects(_model: Model, _effect: Effect) -> Option<Message> {
 /// Elm-like View function.
 ///
 /// Given the current state (read-only), return a drawable widget.
-pub fn view(model: Model) -> Paragraph<'static> {
-    let counter = model.counter;
-
-    let title = Line::from("Ratatui Actor-based Counter")
-        .bold()
-        .blue()
-        .centered();
-
-    let text = format!(
-        r#"Counter TUI!
-        
-Counter: {counter}
-        
-Press `Esc`, `Ctrl-C` or `q` to stop running."#
-    );
-
-    Paragraph::new(text)
-        .block(Block::bordered().title(title))
-        .centered()
+pub fn view(model: Model) -> CounterView {
+    CounterView { model }
+}
+
+pub struct CounterView {
+    model: Model,
+}
+
+impl Widget for CounterView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let counter = self.model.counter;
+
+        let title = Line::from("Ratatui Actor-based Counter")
+            .bold()
+            .blue()
+            .centered();
+
+        let block = Block::bordered().title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let text = format!("Counter TUI!\n\nCounter: {counter}\n");
+
+        let text = match &self.model.keymap_error {
+            Some(error) => format!("{text}\n{error}\n"),
+            None => text,
+        };
+
+        let height = text.lines().count() as u16 + 1;
+        let [text_area, help_area] =
+            Layout::vertical([Constraint::Length(height), Constraint::Fill(1)]).areas(inner);
+
+        Paragraph::new(text).centered().render(text_area, buf);
+
+        // The help is generated from the keymap, so it follows the keymap file too
+        let [help_area] = Layout::horizontal([Constraint::Length(40)])
+            .flex(Flex::Center)
+            .areas(help_area);
+
+        Help::full(&self.model.keymap).render(help_area, buf);
+    }
 }
@@ -0,0 +1,289 @@
+//! # [TeaTui] Dialogs example
+//!
+//! A list of notes edited through dialogs drawn above it, which take every key while open.
+//! `a` adds a note, `r` renames the selected one, `d` deletes it after confirming,
+//! `i` toggles a popup about the selected note and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, ListItem, Paragraph, Widget},
+};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::{
+        dialog::{Dialog, ModalMessage, Modals},
+        list::{self, SelectList},
+    },
+    help::Help,
+    keymap::Keymap,
+    layers::{Corner, Layered, Placement},
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_modals(|model: &Model| &model.modals, Message::Modals)
+        .with_bracketed_paste();
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    let notes = [
+        "Groceries",
+        "Ideas for the garden",
+        "Books to read",
+        "Trip to Lisbon",
+        "Meeting notes",
+    ];
+
+    let keymap = Keymap::new()
+        .bind("a", Message::AskAdd, "add")
+        .bind("r", Message::AskRename, "rename")
+        .bind("d", Message::AskDelete, "delete")
+        .bind("i", Message::ToggleInfo, "info")
+        .bind("q", Message::Exit, "quit");
+
+    let model = Model {
+        notes: SelectList::new(notes.map(Note::new))
+            .with_highlight_style(Style::new().bg(Color::Blue)),
+        modals: Modals::new(),
+        show_info: false,
+        status: String::new(),
+        keymap,
+    };
+
+    (model, None)
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    notes: SelectList<Note>,
+    modals: Modals<Message>,
+    show_info: bool,
+    /// What happened last.
+    status: String,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    List(list::Message),
+    Modals(ModalMessage<Message>),
+    AskAdd,
+    AskRename,
+    AskDelete,
+    Add(String),
+    Rename(String),
+    Delete,
+    Cancelled,
+    ToggleInfo,
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Note {
+    name: String,
+}
+
+impl Note {
+    fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl From<&Note> for ListItem<'_> {
+    fn from(note: &Note) -> Self {
+        ListItem::new(format!(" {}", note.name))
+    }
+}
+
+fn not_empty(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        Err("The name can't be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn open(model: Model, dialog: Dialog<Message>) -> Update<Model, ()> {
+    update(model, Message::Modals(ModalMessage::open(dialog)))
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        Message::Event(event) => match list::Message::from_event(&event) {
+            Some(msg) => update(model, Message::List(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::List(msg) => model
+            .notes
+            .update(msg)
+            .lift_model(|notes| Model { notes, ..model }),
+        Message::Modals(msg) => model
+            .modals
+            .update(msg)
+            .lift_model(|modals| Model { modals, ..model }),
+        Message::AskAdd => {
+            let dialog = Dialog::prompt("New note", "Name of the note:", Message::Add)
+                .with_placeholder("Untitled")
+                .with_validator(not_empty)
+                .with_cancel(Message::Cancelled);
+
+            open(model, dialog)
+        }
+        Message::AskRename => match model.notes.selected() {
+            Some(note) => {
+                let text = format!("New name of {:?}:", note.name);
+                let dialog = Dialog::prompt("Rename", text, Message::Rename)
+                    .with_value(note.name.clone())
+                    .with_validator(not_empty)
+                    .with_cancel(Message::Cancelled);
+
+                open(model, dialog)
+            }
+            None => Update::Next(model, None),
+        },
+        Message::AskDelete => match model.notes.selected() {
+            Some(note) => {
+                let text = format!("Delete {:?}? It can't be brought back.", note.name);
+                let dialog = Dialog::confirm("Delete", text, Message::Delete)
+                    .with_labels("Delete", "Keep")
+                    .with_cancel(Message::Cancelled)
+                    .with_style(Style::new().fg(Color::Red));
+
+                open(model, dialog)
+            }
+            None => Update::Next(model, None),
+        },
+        Message::Add(name) => {
+            let mut notes = model.notes.items().to_vec();
+            notes.push(Note::new(name.clone()));
+
+            let model = Model {
+                notes: model.notes.with_items(notes),
+                status: format!("Added {name:?}"),
+                ..model
+            };
+
+            update(model, Message::List(list::Message::Last))
+        }
+        Message::Rename(name) => {
+            let model = Model {
+                notes: model.notes.map_selected(|note| note.name = name.clone()),
+                status: format!("Renamed to {name:?}"),
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Delete => {
+            let Some(index) = model.notes.selected_index() else {
+                return Update::Next(model, None);
+            };
+
+            let mut notes = model.notes.items().to_vec();
+            let name = notes.remove(index).name;
+
+            let model = Model {
+                notes: model.notes.with_items(notes),
+                status: format!("Deleted {name:?}"),
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Cancelled => {
+            let model = Model {
+                status: "Cancelled".to_string(),
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::ToggleInfo => {
+            let model = Model {
+                show_info: !model.show_info,
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> DialogsView {
+    DialogsView { model }
+}
+
+struct DialogsView {
+    model: Model,
+}
+
+impl Widget for DialogsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let model = &self.model;
+        let mut layered = Layered::new(NotesView { model });
+
+        if model.show_info
+            && let Some(note) = model.notes.selected()
+        {
+            let info = Paragraph::new(vec![
+                Line::from(format!("{} characters", note.name.chars().count())),
+                Line::from(format!("{} words", note.name.split_whitespace().count())),
+            ])
+            .block(Block::bordered().title(" Info ").yellow());
+
+            layered = layered.layer(Placement::corner(Corner::TopRight, 24, 4), info);
+        }
+
+        layered
+            .layer(Placement::Full, model.modals.widget())
+            .render(area, buf);
+    }
+}
+
+/// The notes below the layers.
+struct NotesView<'a> {
+    model: &'a Model,
+}
+
+impl Widget for NotesView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [list_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let notes = &self.model.notes;
+        let block = Block::bordered().title(format!(" Notes ({}) ", notes.items().len()));
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+
+        notes.view(inner, buf);
+
+        Line::from(format!(" {}", self.model.status))
+            .dark_gray()
+            .render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
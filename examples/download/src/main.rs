@@ -0,0 +1,230 @@
+//! # [TeaTui] Spinner and progress bar example
+//!
+//! Pretends to download a file: a spinner and an indeterminate bar move while the mirror
+//! is being resolved, then the bar follows the chunks downloaded by the effects.
+//! The components ask for their own ticks, the model never counts frames.
+//! `r` downloads the file again and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use std::convert::Infallible;
+use std::thread;
+use std::time::{Duration, Instant};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::{
+        progress::{self, ProgressBar},
+        spinner::{self, Animation, Spinner},
+    },
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+const FILE: &str = "teatui-0.5.0.tar.gz";
+const CHUNKS: usize = 40;
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, Effect>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_ticks(Model::deadline, Message::Tick);
+
+    teatui::start_with(options, init, update, view, effects).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<Effect>) {
+    let keymap = Keymap::new()
+        .bind("r", Message::Restart, "download again")
+        .bind("q", Message::Exit, "quit");
+
+    let model = Model {
+        spinner: Spinner::new(Animation::Dots).with_style(Style::new().fg(Color::Cyan)),
+        progress: ProgressBar::new(),
+        status: Status::Done,
+        keymap,
+    };
+
+    match update(model, Message::Restart) {
+        Update::Next(model, effect) => (model, effect),
+        Update::Exit => unreachable!("restarting never exits"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Resolving,
+    Downloading,
+    Done,
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    spinner: Spinner,
+    progress: ProgressBar,
+    status: Status,
+    keymap: Keymap<Message>,
+}
+
+impl Model {
+    /// Earliest tick wanted by the components.
+    fn deadline(&self) -> Option<Instant> {
+        [self.spinner.deadline(), self.progress.deadline()]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    NoOp,
+    Tick(Instant),
+    Restart,
+    Resolved,
+    Downloaded(usize),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(_value: Event) -> Self {
+        Self::NoOp
+    }
+}
+
+#[derive(Debug)]
+enum Effect {
+    Resolve,
+    Download(usize),
+}
+
+/// Takes some time to resolve the mirror, then to download every chunk.
+fn effects(_model: Model, effect: Effect) -> Option<Message> {
+    match effect {
+        Effect::Resolve => {
+            thread::sleep(Duration::from_millis(1500));
+            Some(Message::Resolved)
+        }
+        Effect::Download(chunk) => {
+            thread::sleep(Duration::from_millis(40 + (chunk * 37 % 11) as u64 * 10));
+            Some(Message::Downloaded(chunk))
+        }
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+    match msg {
+        Message::NoOp => Update::Next(model, None),
+        // Components ignore the ticks they did not ask for
+        Message::Tick(now) => {
+            let model = Model {
+                spinner: step(model.spinner, spinner::Message::Tick(now)),
+                progress: step(model.progress, progress::Message::Tick(now)),
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Restart if model.status != Status::Done => Update::Next(model, None),
+        Message::Restart => {
+            let spinner = model.spinner.with_label("Resolving mirror");
+            let progress = step(model.progress, progress::Message::Reset);
+
+            let model = Model {
+                spinner: step(spinner, spinner::Message::Start),
+                progress: step(progress, progress::Message::Indeterminate),
+                status: Status::Resolving,
+                ..model
+            };
+
+            Update::Next(model, Some(Effect::Resolve))
+        }
+        Message::Resolved => {
+            let model = Model {
+                spinner: model.spinner.with_label(format!("Downloading {FILE}")),
+                progress: step(model.progress, progress::Message::Set(0.0)),
+                status: Status::Downloading,
+                ..model
+            };
+
+            Update::Next(model, Some(Effect::Download(0)))
+        }
+        Message::Downloaded(chunk) if chunk + 1 == CHUNKS => {
+            let model = Model {
+                spinner: step(model.spinner, spinner::Message::Stop),
+                progress: step(model.progress, progress::Message::Finish),
+                status: Status::Done,
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Downloaded(chunk) => {
+            let ratio = (chunk + 1) as f64 / CHUNKS as f64;
+            let model = Model {
+                progress: step(model.progress, progress::Message::Set(ratio)),
+                ..model
+            };
+
+            Update::Next(model, Some(Effect::Download(chunk + 1)))
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+/// Updates a component which never exits nor asks for an effect.
+fn step<C>(component: C, msg: C::Message) -> C
+where
+    C: Component<Effect = Infallible>,
+{
+    match component.update(msg) {
+        Update::Next(component, _) => component,
+        Update::Exit => unreachable!("spinners and progress bars never exit"),
+    }
+}
+
+fn view(model: Model) -> DownloadView {
+    DownloadView { model }
+}
+
+struct DownloadView {
+    model: Model,
+}
+
+impl Widget for DownloadView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [area] = Layout::horizontal([Constraint::Length(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [box_area, help_area] =
+            Layout::vertical([Constraint::Length(4), Constraint::Length(1)])
+                .flex(Flex::Center)
+                .areas(area);
+
+        let block = Block::bordered().title(" Download ");
+        let inner = block.inner(box_area);
+        block.render(box_area, buf);
+
+        let [status_area, progress_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+
+        match self.model.status {
+            Status::Done => Line::from(format!("✓ Downloaded {FILE}"))
+                .green()
+                .render(status_area, buf),
+            _ => self.model.spinner.view(status_area, buf),
+        }
+
+        self.model.progress.view(progress_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
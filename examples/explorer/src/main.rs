@@ -0,0 +1,211 @@
+//! # [TeaTui] Tree example
+//!
+//! Explores the settings of a pretend configuration service, each section being fetched
+//! by an effect the first time it is expanded. The `secrets` section always fails to load.
+//! `↑`/`↓` move, `→`/`←` expand and collapse, `enter` toggles and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use std::thread;
+use std::time::Duration;
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::tree::{self, Children, Node, Tree},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, Effect>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_mouse_capture();
+
+    teatui::start_with(options, init, update, view, effects).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<Effect>) {
+    let keymap = Keymap::new().bind("q", Message::Exit, "quit");
+
+    let roots = ["server", "database", "cache", "features", "secrets"].map(|section| {
+        Node::lazy(Setting {
+            key: section.to_string(),
+            value: None,
+        })
+    });
+
+    let model = Model {
+        settings: Tree::new(roots, Setting::label)
+            .with_page_size(20)
+            .with_highlight_style(Style::new().bg(Color::Blue)),
+        keymap,
+    };
+
+    (model, None)
+}
+
+/// A section of the configuration, or a value.
+#[derive(Debug, Clone)]
+struct Setting {
+    /// Full key, the sections being separated by dots.
+    key: String,
+    value: Option<String>,
+}
+
+impl Setting {
+    fn name(&self) -> &str {
+        self.key.rsplit('.').next().unwrap_or_default()
+    }
+
+    fn label(&self) -> String {
+        match &self.value {
+            Some(value) => format!("{} = {value}", self.name()),
+            None => self.name().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    settings: Tree<Setting>,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Settings(tree::Message<Setting>),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+#[derive(Debug)]
+enum Effect {
+    Settings(tree::Effect<Setting>),
+}
+
+fn effects(_model: Model, effect: Effect) -> Option<Message> {
+    match effect {
+        Effect::Settings(tree::Effect::Load(path, section)) => {
+            let children = fetch(&section.key).map(|settings| {
+                settings
+                    .into_iter()
+                    .map(|setting| match setting.value {
+                        Some(_) => Node::leaf(setting),
+                        None => Node::lazy(setting),
+                    })
+                    .collect()
+            });
+
+            Some(Message::Settings(tree::Message::Loaded(path, children)))
+        }
+    }
+}
+
+/// Asks the configuration service for the settings of a section, which takes a while.
+fn fetch(section: &str) -> Result<Vec<Setting>, String> {
+    thread::sleep(Duration::from_millis(600));
+
+    let entries: &[(&str, Option<&str>)] = match section {
+        "server" => &[
+            ("host", Some("0.0.0.0")),
+            ("port", Some("8080")),
+            ("tls", None),
+            ("workers", Some("8")),
+        ],
+        "server.tls" => &[
+            ("enabled", Some("true")),
+            ("certificate", Some("/etc/ssl/server.pem")),
+        ],
+        "database" => &[("url", Some("postgres://db.internal/app")), ("pool", None)],
+        "database.pool" => &[
+            ("min", Some("2")),
+            ("max", Some("32")),
+            ("idle_timeout", Some("10m")),
+        ],
+        "cache" => &[("backend", Some("redis")), ("ttl", Some("300s"))],
+        "features" => &[
+            ("search", Some("true")),
+            ("billing", Some("false")),
+            ("beta", None),
+        ],
+        "features.beta" => &[],
+        _ => return Err("access denied".to_string()),
+    };
+
+    let settings = entries
+        .iter()
+        .map(|(key, value)| Setting {
+            key: format!("{section}.{key}"),
+            value: value.map(str::to_string),
+        })
+        .collect();
+
+    Ok(settings)
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+    match msg {
+        Message::Event(event) => match tree::Message::from_event(&event) {
+            Some(msg) => update(model, Message::Settings(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::Settings(msg) => model
+            .settings
+            .update(msg)
+            .lift(|settings| Model { settings, ..model }, Effect::Settings),
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> ExplorerView {
+    ExplorerView { model }
+}
+
+struct ExplorerView {
+    model: Model,
+}
+
+impl Widget for ExplorerView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [tree_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Settings ");
+        let inner = block.inner(tree_area);
+        block.render(tree_area, buf);
+        self.model.settings.view(inner, buf);
+
+        let status = match self.model.settings.selected_node() {
+            Some(node) => match (&node.item().value, node.children()) {
+                (Some(value), _) => format!(" {} = {value}", node.item().key),
+                (None, Children::Loaded(children)) => {
+                    format!(" {} ({} settings)", node.item().key, children.len())
+                }
+                (None, _) => format!(" {}", node.item().key),
+            },
+            None => String::new(),
+        };
+        Line::from(status).dark_gray().render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
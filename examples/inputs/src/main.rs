@@ -0,0 +1,202 @@
+//! # [TeaTui] Text inputs example
+//!
+//! A login prompt with a validated username and a masked password.
+//! `tab` moves between the inputs, `enter` submits and `esc` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::text_input::{self, TextInput},
+    focus::Focus,
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_focus(|model: &Model| &model.focus, Message::Focus)
+        .with_bracketed_paste();
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    (Model::default(), None)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Username,
+    Password,
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    username: TextInput,
+    password: TextInput,
+    focus: Focus<Field>,
+    keymap: Keymap<Message>,
+    status: Line<'static>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        let username = TextInput::new()
+            .with_placeholder("alice")
+            .with_max_chars(16)
+            .with_validator(|value| {
+                if value.is_empty() {
+                    Err("the username is required".to_string())
+                } else if !value.chars().all(char::is_alphanumeric) {
+                    Err("the username can only have letters and numbers".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+
+        // Inside the borders of the form, 40 columns wide
+        let password = TextInput::new()
+            .with_width(38)
+            .with_placeholder("at least 8 characters")
+            .with_mask('•')
+            .with_focused(false)
+            .with_validator(|value| match value.chars().count() {
+                0..8 => Err("the password is too short".to_string()),
+                _ => Ok(()),
+            });
+
+        let keymap = Keymap::new().bind("enter", Message::Submit, "log in").bind(
+            "esc",
+            Message::Exit,
+            "quit",
+        );
+
+        Self {
+            username,
+            password,
+            focus: Focus::new([Field::Username, Field::Password]),
+            keymap,
+            status: Line::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Focus(Focus<Field>),
+    Submit,
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        // Events not handled by the keymap are typed into the focused input
+        Message::Event(event) => {
+            let Some(msg) = text_input::Message::from_event(&event) else {
+                return Update::Next(model, None);
+            };
+
+            match model.focus.current() {
+                Some(Field::Username) => model
+                    .username
+                    .update(msg)
+                    .lift_model(|username| Model { username, ..model }),
+                Some(Field::Password) => model
+                    .password
+                    .update(msg)
+                    .lift_model(|password| Model { password, ..model }),
+                None => Update::Next(model, None),
+            }
+        }
+        Message::Focus(focus) => {
+            let model = Model {
+                username: model
+                    .username
+                    .with_focused(focus.is_focused(&Field::Username)),
+                password: model
+                    .password
+                    .with_focused(focus.is_focused(&Field::Password)),
+                focus,
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Submit => {
+            let status = match model.username.error().or(model.password.error()) {
+                Some(error) => Line::from(format!("Can't log in: {error}")).red(),
+                None => Line::from(format!("Welcome, {}!", model.username.value())).green(),
+            };
+
+            Update::Next(Model { status, ..model }, None)
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> LoginView {
+    LoginView { model }
+}
+
+struct LoginView {
+    model: Model,
+}
+
+impl LoginView {
+    fn render_field(&self, field: Field, area: Rect, buf: &mut Buffer) {
+        let (title, input) = match field {
+            Field::Username => (" Username ", &self.model.username),
+            Field::Password => (" Password ", &self.model.password),
+        };
+
+        let block = Block::bordered()
+            .title(title)
+            .border_style(self.model.focus.style(&field));
+
+        input.widget().render(block.inner(area), buf);
+        block.render(area, buf);
+    }
+}
+
+impl Widget for LoginView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [form_area] = Layout::horizontal([Constraint::Length(40)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [username_area, password_area, status_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::Center)
+        .areas(form_area);
+
+        self.render_field(Field::Username, username_area, buf);
+        self.render_field(Field::Password, password_area, buf);
+        Paragraph::new(self.model.status.clone())
+            .wrap(Wrap { trim: true })
+            .render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
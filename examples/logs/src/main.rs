@@ -0,0 +1,228 @@
+//! # [TeaTui] Virtual list example
+//!
+//! A million log lines, of which only the ones on screen are drawn every frame.
+//! `↑`/`↓`, `page up`/`page down` and `home`/`end` move, `e` only shows the errors
+//! and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Row, StatefulWidget, Table, TableState, Widget},
+};
+use std::sync::Arc;
+use std::time::Instant;
+use teatui::{
+    ProgramError,
+    components::{list, virtual_list::VirtualList},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+const LINES: usize = 1_000_000;
+
+const SERVICES: [&str; 5] = ["api", "auth", "billing", "search", "worker"];
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_mouse_capture();
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    let logs: Vec<Log> = (0..LINES).map(Log::generate).collect();
+    let shown: Vec<usize> = (0..logs.len()).collect();
+
+    let keymap = Keymap::new()
+        .bind("e", Message::ToggleErrors, "errors only")
+        .bind("q", Message::Exit, "quit");
+
+    let model = Model {
+        list: VirtualList::new(shown.len())
+            .with_page_size(20)
+            .with_highlight_style(Style::new().bg(Color::Blue)),
+        logs: Arc::new(logs),
+        shown: Arc::new(shown),
+        errors_only: false,
+        keymap,
+    };
+
+    (model, None)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug)]
+struct Log {
+    time: String,
+    level: Level,
+    service: &'static str,
+    message: String,
+}
+
+impl Log {
+    /// Makes up the log line at `index`, always the same one.
+    fn generate(index: usize) -> Self {
+        let hash = index.wrapping_mul(2_654_435_761) % 1_000;
+        let level = match hash {
+            0..20 => Level::Error,
+            20..100 => Level::Warn,
+            _ => Level::Info,
+        };
+        let seconds = index / 10;
+
+        Self {
+            time: format!(
+                "{:02}:{:02}:{:02}.{:03}",
+                seconds / 3600 % 24,
+                seconds / 60 % 60,
+                seconds % 60,
+                index % 10 * 100 + hash % 100
+            ),
+            level,
+            service: SERVICES[hash % SERVICES.len()],
+            message: match level {
+                Level::Error => format!("request {index} failed after {} ms", hash * 3),
+                Level::Warn => format!("request {index} took {} ms", hash * 2),
+                Level::Info => format!("request {index} served in {} ms", hash / 10),
+            },
+        }
+    }
+}
+
+/// The logs are shared with every copy of the model, only the indices of the lines
+/// shown change.
+#[derive(Debug, Clone)]
+struct Model {
+    logs: Arc<Vec<Log>>,
+    /// Indices of the logs matching the filter.
+    shown: Arc<Vec<usize>>,
+    list: VirtualList,
+    errors_only: bool,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    List(list::Message),
+    ToggleErrors,
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        Message::Event(event) => match list::Message::from_event(&event) {
+            Some(msg) => update(model, Message::List(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::List(msg) => model
+            .list
+            .update(msg)
+            .lift_model(|list| Model { list, ..model }),
+        Message::ToggleErrors => {
+            let errors_only = !model.errors_only;
+            let shown: Vec<usize> = (0..model.logs.len())
+                .filter(|&index| !errors_only || model.logs[index].level == Level::Error)
+                .collect();
+
+            let model = Model {
+                list: model.list.with_len(shown.len()),
+                shown: Arc::new(shown),
+                errors_only,
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> LogsView {
+    LogsView { model }
+}
+
+struct LogsView {
+    model: Model,
+}
+
+impl Widget for LogsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let start = Instant::now();
+
+        let [table_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Logs ");
+        let inner = block.inner(table_area);
+        block.render(table_area, buf);
+
+        // Only the rows fitting below the header are built
+        let height = inner.height.saturating_sub(1);
+        let list = &self.model.list;
+        let rows = list.window(height).map(|position| {
+            let log = &self.model.logs[self.model.shown[position]];
+            let level = match log.level {
+                Level::Info => Line::from("INFO").green(),
+                Level::Warn => Line::from("WARN").yellow(),
+                Level::Error => Line::from("ERROR").red(),
+            };
+
+            Row::new([
+                Line::from(log.time.as_str()),
+                level,
+                Line::from(log.service),
+                Line::from(log.message.as_str()),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["Time", "Level", "Service", "Message"]).bold())
+        .row_highlight_style(Style::new().bg(Color::Blue));
+
+        let mut state = TableState::default().with_selected(list.state(height).selected());
+        StatefulWidget::render(table, inner, buf, &mut state);
+
+        let status = match list.selected() {
+            Some(position) => format!(" line {} of {}", position + 1, list.len()),
+            None => " no line".to_string(),
+        };
+        let elapsed = format!("drawn in {} µs ", start.elapsed().as_micros());
+
+        Line::from(status).render(status_area, buf);
+        Line::from(elapsed).right_aligned().render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
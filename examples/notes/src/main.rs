@@ -0,0 +1,164 @@
+//! # [TeaTui] Text area example
+//!
+//! A commit message editor that grows with its content, up to ten lines.
+//! `ctrl+s` commits the message, `ctrl+z` and `ctrl+y` undo and redo, `esc` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::textarea::{self, TextArea},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+const WIDTH: u16 = 60;
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_bracketed_paste();
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    (Model::default(), None)
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    message: TextArea,
+    keymap: Keymap<Message>,
+    status: Line<'static>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        // Inside the borders of the editor
+        let message = TextArea::new()
+            .with_size(WIDTH - 2, 10)
+            .with_placeholder("Summary of the change")
+            .with_line_numbers(true)
+            .with_max_height(10);
+
+        let keymap = Keymap::new()
+            .bind("ctrl+s", Message::Commit, "commit")
+            .bind("ctrl+t", Message::ToggleWrap, "toggle wrapping")
+            .bind("esc", Message::Exit, "quit");
+
+        Self {
+            message,
+            keymap,
+            status: Line::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Commit,
+    ToggleWrap,
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        // Events not handled by the keymap are typed into the editor
+        Message::Event(event) => match textarea::Message::from_event(&event) {
+            Some(msg) => model
+                .message
+                .update(msg)
+                .lift_model(|message| Model { message, ..model }),
+            None => Update::Next(model, None),
+        },
+        Message::Commit => {
+            let summary = model.message.lines()[0].trim().to_string();
+
+            if summary.is_empty() {
+                let status = Line::from("The summary can't be empty").red();
+                return Update::Next(Model { status, ..model }, None);
+            }
+
+            // Clearing the editor can be undone, in case the commit was a mistake
+            let status = Line::from(format!("Committed \"{summary}\"")).green();
+            model
+                .message
+                .update(textarea::Message::SetValue(String::new()))
+                .lift_model(|message| Model {
+                    message,
+                    status,
+                    ..model
+                })
+        }
+        Message::ToggleWrap => {
+            let wrap = !model.message.wraps();
+            let message = model.message.with_wrap(wrap);
+            Update::Next(Model { message, ..model }, None)
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> NotesView {
+    NotesView { model }
+}
+
+struct NotesView {
+    model: Model,
+}
+
+impl Widget for NotesView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let message = &self.model.message;
+        let block = Block::bordered()
+            .title(" Commit message ")
+            .border_style(Style::new().fg(Color::Cyan));
+
+        // The editor grows with the message, the borders taking two rows
+        let height = message.height(WIDTH - 2).max(1) + 2;
+
+        let [form_area] = Layout::horizontal([Constraint::Length(WIDTH)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [editor_area, position_area, status_area, help_area] = Layout::vertical([
+            Constraint::Length(height),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::Center)
+        .areas(form_area);
+
+        message.widget().render(block.inner(editor_area), buf);
+        block.render(editor_area, buf);
+
+        let (line, offset) = message.cursor();
+        let column = message.lines()[line][..offset].chars().count();
+        Line::from(format!("Ln {}, Col {}", line + 1, column + 1))
+            .dark_gray()
+            .right_aligned()
+            .render(position_area, buf);
+
+        self.model.status.clone().render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
@@ -0,0 +1,183 @@
+//! # [TeaTui] Pager example
+//!
+//! A small `less`: shows the file given as argument, or its own source.
+//! `j`/`k`, `space`/`b`, `g`/`G` and the mouse wheel scroll, `/` searches,
+//! `n`/`N` move between matches and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::Widget,
+};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::viewport::{self, Viewport},
+    help::Help,
+    keymap::{DEFAULT_MODE, Keymap},
+    options::ProgramOptions,
+    update::Update,
+};
+
+/// Mode of the keymap while the search prompt is open, so `q` can be searched for.
+const SEARCH_MODE: &str = "search";
+
+/// Lines below the content: the status bar and the help.
+const STATUS_HEIGHT: u16 = 2;
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_mouse_capture();
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    let (name, content) = match std::env::args().nth(1) {
+        Some(path) => match std::fs::read_to_string(&path) {
+            Ok(content) => (path, content),
+            Err(error) => (path, format!("Can't read the file: {error}")),
+        },
+        None => ("main.rs".to_string(), include_str!("main.rs").to_string()),
+    };
+
+    // Comments are dimmed, to show styled text is kept
+    let text: Text<'static> = content
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("//") {
+                Line::from(line.to_string()).dark_gray()
+            } else {
+                Line::from(line.to_string())
+            }
+        })
+        .collect();
+
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+
+    let keymap = Keymap::new()
+        .bind_in(DEFAULT_MODE, "q", Message::Exit, "quit")
+        .bind("ctrl+c", Message::Exit, "quit");
+
+    let model = Model {
+        name,
+        content: Viewport::new(text).with_size(width, height.saturating_sub(STATUS_HEIGHT)),
+        keymap,
+    };
+
+    (model, None)
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    name: String,
+    content: Viewport,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Viewport(viewport::Message),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        Message::Event(Event::Resize(width, height)) => Update::Next(
+            Model {
+                content: model
+                    .content
+                    .with_size(width, height.saturating_sub(STATUS_HEIGHT)),
+                ..model
+            },
+            None,
+        ),
+        Message::Event(event) => {
+            let msg = if model.content.is_searching() {
+                viewport::Message::from_search_event(&event)
+            } else {
+                viewport::Message::from_event(&event)
+            };
+
+            match msg {
+                Some(msg) => update(model, Message::Viewport(msg)),
+                None => Update::Next(model, None),
+            }
+        }
+        // The keymap follows the search prompt, for `q` to be typed in it
+        Message::Viewport(msg) => model.content.update(msg).lift_model(|content| {
+            let mode = if content.is_searching() {
+                SEARCH_MODE
+            } else {
+                DEFAULT_MODE
+            };
+
+            Model {
+                content,
+                keymap: model.keymap.with_mode(mode),
+                ..model
+            }
+        }),
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> PagerView {
+    PagerView { model }
+}
+
+struct PagerView {
+    model: Model,
+}
+
+impl Widget for PagerView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [content_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let content = &self.model.content;
+        content.view(content_area, buf);
+
+        let lines = content.lines().len();
+        let (top, _) = content.offset();
+        let last = (top + content_area.height as usize).min(lines);
+        let position = if content.is_at_bottom() {
+            "END".to_string()
+        } else {
+            format!("{}%", last * 100 / lines.max(1))
+        };
+
+        let matches = match (content.query(), content.current_match()) {
+            ("", _) => String::new(),
+            (_, _) if content.match_count() == 0 => "no match".to_string(),
+            (_, Some(current)) => format!("match {}/{}", current + 1, content.match_count()),
+            (_, None) => format!("{} matches", content.match_count()),
+        };
+
+        let status = format!(
+            " {}  lines {}-{}/{lines}  {position}  {matches}",
+            self.model.name,
+            (top + 1).min(lines),
+            last
+        );
+        Line::from(status).reversed().render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
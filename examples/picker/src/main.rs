@@ -0,0 +1,184 @@
+//! # [TeaTui] File picker example
+//!
+//! Browses the directory given as argument, or the current one, every listing being read
+//! by the effects. `enter` opens a directory or chooses a file, `backspace` goes up,
+//! `.` shows the hidden files, `e` only shows Rust files and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use std::path::PathBuf;
+use teatui::{
+    ProgramError,
+    component::{Component, Effects},
+    components::file_picker::{self, FilePicker},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+/// Extensions shown when only Rust files are.
+const RUST: [&str; 2] = ["rs", "toml"];
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, Effect>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_mouse_capture();
+
+    teatui::start_with(options, init, update, view, effects).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<Effect>) {
+    let dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let keymap = Keymap::new()
+        .bind(
+            ".",
+            Message::Picker(file_picker::Message::ToggleHidden),
+            "hidden files",
+        )
+        .bind("e", Message::ToggleRust, "Rust files only")
+        .bind("q", Message::Exit, "quit");
+
+    let model = Model {
+        picker: FilePicker::new(dir).with_highlight_style(Style::new().bg(Color::Blue)),
+        rust_only: false,
+        chosen: None,
+        keymap,
+    };
+
+    match update(model, Message::Picker(file_picker::Message::Refresh)) {
+        Update::Next(model, effect) => (model, effect),
+        Update::Exit => unreachable!("listing a directory never exits"),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    picker: FilePicker,
+    rust_only: bool,
+    chosen: Option<PathBuf>,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Picker(file_picker::Message),
+    ToggleRust,
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+#[derive(Debug)]
+enum Effect {
+    Picker(file_picker::Effect),
+}
+
+fn effects(_model: Model, effect: Effect) -> Option<Message> {
+    match effect {
+        Effect::Picker(effect) => FilePicker::run(effect).map(Message::Picker),
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+    match msg {
+        Message::Event(event) => match file_picker::Message::from_event(&event) {
+            Some(msg) => update(model, Message::Picker(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::Picker(file_picker::Message::Chosen(path)) => {
+            let model = Model {
+                chosen: Some(path),
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Picker(msg) => model
+            .picker
+            .update(msg)
+            .lift(|picker| Model { picker, ..model }, Effect::Picker),
+        Message::ToggleRust => {
+            let rust_only = !model.rust_only;
+            let extensions = if rust_only { &RUST[..] } else { &[] };
+
+            let model = Model {
+                picker: model.picker.with_extensions(extensions.iter().copied()),
+                rust_only,
+                ..model
+            };
+
+            Update::Next(model, None)
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> PickerView {
+    PickerView { model }
+}
+
+struct PickerView {
+    model: Model,
+}
+
+impl Widget for PickerView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [picker_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let picker = &self.model.picker;
+        let mut filters = vec![];
+
+        if picker.shows_hidden() {
+            filters.push("hidden files");
+        }
+
+        if self.model.rust_only {
+            filters.push("Rust files only");
+        }
+
+        let mut block = Block::bordered().title(format!(" {} ", picker.dir().display()));
+
+        if !filters.is_empty() {
+            block =
+                block.title_bottom(Line::from(format!(" {} ", filters.join(", "))).right_aligned());
+        }
+
+        let inner = block.inner(picker_area);
+        block.render(picker_area, buf);
+        picker.view(inner, buf);
+
+        let status = match (&self.model.chosen, picker.selected()) {
+            (Some(chosen), _) => Line::from(format!(" Chosen: {}", chosen.display())).green(),
+            (None, Some(entry)) if !entry.is_dir() => {
+                Line::from(format!(" {} bytes", entry.size())).dark_gray()
+            }
+            (None, _) => Line::default(),
+        };
+        status.render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
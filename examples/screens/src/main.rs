@@ -0,0 +1,450 @@
+//! # [TeaTui] Screen router example
+//!
+//! A bookshelf going from the list of books to a book, then to a note about it.
+//! Every screen loads its own data through the effects when it is pushed, and the screens
+//! below keep their state, so going back finds the selection where it was left.
+//! `enter` opens a book, `n` writes a note about it, `esc` goes back and `ctrl+c` quits.
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, ListItem, Widget},
+};
+use std::thread;
+use std::time::Duration;
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::{
+        list::{self, SelectList},
+        router::{self, Router, Screen},
+        text_input::{self, TextInput},
+    },
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Book {
+    title: &'static str,
+    author: &'static str,
+    year: u16,
+}
+
+const BOOKS: [Book; 5] = [
+    Book {
+        title: "Dune",
+        author: "Frank Herbert",
+        year: 1965,
+    },
+    Book {
+        title: "The Left Hand of Darkness",
+        author: "Ursula K. Le Guin",
+        year: 1969,
+    },
+    Book {
+        title: "Neuromancer",
+        author: "William Gibson",
+        year: 1984,
+    },
+    Book {
+        title: "Hyperion",
+        author: "Dan Simmons",
+        year: 1989,
+    },
+    Book {
+        title: "Piranesi",
+        author: "Susanna Clarke",
+        year: 2020,
+    },
+];
+
+impl From<&Book> for ListItem<'_> {
+    fn from(book: &Book) -> Self {
+        ListItem::new(Line::from(vec![
+            Span::raw(format!(" {}", book.title)),
+            Span::raw(format!(" — {}", book.author)).dark_gray(),
+        ]))
+    }
+}
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, Effect>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_router(|model: &Model| &model.router, Message::Router)
+        .with_bracketed_paste();
+
+    teatui::start_with(options, init, update, view, effects).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<Effect>) {
+    let router = Router::new(Page::Shelf(Shelf { books: None }));
+    let effect = router.init().map(Effect::Screen);
+
+    let model = Model {
+        router,
+        keymap: Keymap::new().bind("ctrl+c", Message::Exit, "quit"),
+    };
+
+    (model, effect)
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    router: Router<Page>,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Router(router::Message<Page>),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+/// Message for the screen shown.
+fn page(msg: PageMessage) -> Message {
+    Message::Router(router::Message::Screen(msg))
+}
+
+#[derive(Debug)]
+enum Effect {
+    /// Effect of a screen, whose result goes back to that screen.
+    Screen(router::ScreenEffect<PageEffect>),
+}
+
+#[derive(Debug)]
+enum PageEffect {
+    LoadBooks,
+    LoadReviews(&'static str),
+}
+
+fn effects(_model: Model, effect: Effect) -> Option<Message> {
+    match effect {
+        Effect::Screen(effect) => effect.run(page_effects).map(Message::Router),
+    }
+}
+
+fn page_effects(effect: PageEffect) -> Option<PageMessage> {
+    match effect {
+        PageEffect::LoadBooks => {
+            thread::sleep(Duration::from_millis(500));
+            Some(PageMessage::Shelf(ShelfMessage::Loaded(BOOKS.to_vec())))
+        }
+        PageEffect::LoadReviews(title) => {
+            thread::sleep(Duration::from_millis(500));
+
+            let reviews = vec![
+                format!("★★★★★ {title} holds up after all these years."),
+                "★★★☆☆ Slow to start, hard to put down after that.".to_string(),
+            ];
+
+            Some(PageMessage::Book(BookMessage::Reviews(reviews)))
+        }
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+    match msg {
+        Message::Event(event) => match model.router.current().message(&event) {
+            Some(msg) => update(model, page(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::Router(router::Message::Screen(PageMessage::Shelf(ShelfMessage::Open))) => {
+            let book = match model.router.current() {
+                Page::Shelf(shelf) => shelf.selected(),
+                _ => None,
+            };
+
+            match book {
+                Some(book) => {
+                    let screen = Page::Book(BookPage {
+                        book,
+                        reviews: None,
+                        notes: vec![],
+                    });
+                    update(model, Message::Router(router::Message::push(screen)))
+                }
+                None => Update::Next(model, None),
+            }
+        }
+        Message::Router(router::Message::Screen(PageMessage::Book(BookMessage::Write))) => {
+            let Page::Book(book) = model.router.current() else {
+                return Update::Next(model, None);
+            };
+
+            let screen = Page::Note(NotePage {
+                book: book.book,
+                input: TextInput::new().with_placeholder("What did you think of it?"),
+            });
+            update(model, Message::Router(router::Message::push(screen)))
+        }
+        Message::Router(router::Message::Screen(PageMessage::Note(NoteMessage::Save))) => {
+            let note = match model.router.current() {
+                Page::Note(note) => note.input.value().trim().to_string(),
+                _ => String::new(),
+            };
+
+            // Back to the book, which gets the note
+            let Update::Next(model, _) = update(model, Message::Router(router::Message::Pop))
+            else {
+                return Update::Exit;
+            };
+
+            if note.is_empty() {
+                Update::Next(model, None)
+            } else {
+                update(model, page(PageMessage::Book(BookMessage::Add(note))))
+            }
+        }
+        Message::Router(msg) => model
+            .router
+            .update(msg)
+            .lift(|router| Model { router, ..model }, Effect::Screen),
+        Message::Exit => Update::Exit,
+    }
+}
+
+/// Screens of the bookshelf.
+#[derive(Debug, Clone)]
+enum Page {
+    Shelf(Shelf),
+    Book(BookPage),
+    Note(NotePage),
+}
+
+#[derive(Debug, Clone)]
+enum PageMessage {
+    Shelf(ShelfMessage),
+    Book(BookMessage),
+    Note(NoteMessage),
+}
+
+#[derive(Debug, Clone)]
+struct Shelf {
+    /// `None` while the books are loading.
+    books: Option<SelectList<Book>>,
+}
+
+#[derive(Debug, Clone)]
+enum ShelfMessage {
+    Loaded(Vec<Book>),
+    List(list::Message),
+    Open,
+}
+
+impl Shelf {
+    fn selected(&self) -> Option<Book> {
+        self.books.as_ref()?.selected().copied()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BookPage {
+    book: Book,
+    /// `None` while the reviews are loading.
+    reviews: Option<Vec<String>>,
+    notes: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum BookMessage {
+    Reviews(Vec<String>),
+    Write,
+    Add(String),
+}
+
+#[derive(Debug, Clone)]
+struct NotePage {
+    book: Book,
+    input: TextInput,
+}
+
+#[derive(Debug, Clone)]
+enum NoteMessage {
+    Input(text_input::Message),
+    Save,
+}
+
+impl Page {
+    /// Converts an event for this screen.
+    fn message(&self, event: &Event) -> Option<PageMessage> {
+        let key = match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => Some(key.code),
+            _ => None,
+        };
+
+        match (self, key) {
+            (Self::Shelf(_), Some(KeyCode::Enter)) => Some(PageMessage::Shelf(ShelfMessage::Open)),
+            (Self::Shelf(_), _) => list::Message::from_event(event)
+                .map(|msg| PageMessage::Shelf(ShelfMessage::List(msg))),
+            (Self::Book(_), Some(KeyCode::Char('n'))) => {
+                Some(PageMessage::Book(BookMessage::Write))
+            }
+            (Self::Book(_), _) => None,
+            (Self::Note(_), Some(KeyCode::Enter)) => Some(PageMessage::Note(NoteMessage::Save)),
+            (Self::Note(_), _) => text_input::Message::from_event(event)
+                .map(|msg| PageMessage::Note(NoteMessage::Input(msg))),
+        }
+    }
+
+    /// Keys of the screen, for the help line.
+    fn hints(&self) -> &'static str {
+        match self {
+            Self::Shelf(_) => "↑↓ select • enter open",
+            Self::Book(_) => "n write a note",
+            Self::Note(_) => "enter save",
+        }
+    }
+}
+
+impl Component for Page {
+    type Message = PageMessage;
+    type Effect = PageEffect;
+
+    fn init(&self) -> Option<PageEffect> {
+        match self {
+            Self::Shelf(_) => Some(PageEffect::LoadBooks),
+            Self::Book(book) => Some(PageEffect::LoadReviews(book.book.title)),
+            Self::Note(_) => None,
+        }
+    }
+
+    fn update(self, message: PageMessage) -> Update<Self, PageEffect> {
+        match (self, message) {
+            (Self::Shelf(_), PageMessage::Shelf(ShelfMessage::Loaded(books))) => {
+                let books =
+                    SelectList::new(books).with_highlight_style(Style::new().bg(Color::Blue));
+                Update::Next(Self::Shelf(Shelf { books: Some(books) }), None)
+            }
+            (
+                Self::Shelf(Shelf { books: Some(books) }),
+                PageMessage::Shelf(ShelfMessage::List(msg)),
+            ) => books
+                .update(msg)
+                .lift_model(|books| Self::Shelf(Shelf { books: Some(books) })),
+            (Self::Book(book), PageMessage::Book(BookMessage::Reviews(reviews))) => {
+                let book = BookPage {
+                    reviews: Some(reviews),
+                    ..book
+                };
+                Update::Next(Self::Book(book), None)
+            }
+            (Self::Book(mut book), PageMessage::Book(BookMessage::Add(note))) => {
+                book.notes.push(note);
+                Update::Next(Self::Book(book), None)
+            }
+            (Self::Note(note), PageMessage::Note(NoteMessage::Input(msg))) => note
+                .input
+                .update(msg)
+                .lift_model(|input| Self::Note(NotePage { input, ..note })),
+            // Messages for another screen, such as a late result
+            (screen, _) => Update::Next(screen, None),
+        }
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        match self {
+            Self::Shelf(Shelf { books: None }) => {
+                Line::from(" Loading books…").dark_gray().render(area, buf)
+            }
+            Self::Shelf(Shelf { books: Some(books) }) => books.view(area, buf),
+            Self::Book(book) => {
+                let mut lines = vec![
+                    Line::from(format!(" {}", book.book.title)).bold(),
+                    Line::from(format!(" {}, {}", book.book.author, book.book.year)),
+                    Line::default(),
+                    Line::from(" Reviews").cyan(),
+                ];
+
+                match &book.reviews {
+                    Some(reviews) => lines.extend(
+                        reviews
+                            .iter()
+                            .map(|review| Line::from(format!(" {review}"))),
+                    ),
+                    None => lines.push(Line::from(" Loading…").dark_gray()),
+                }
+
+                if !book.notes.is_empty() {
+                    lines.push(Line::default());
+                    lines.push(Line::from(" Notes").cyan());
+                    lines.extend(
+                        book.notes
+                            .iter()
+                            .map(|note| Line::from(format!(" • {note}"))),
+                    );
+                }
+
+                for (line, row) in lines.into_iter().zip(area.rows()) {
+                    line.render(row, buf);
+                }
+            }
+            Self::Note(note) => {
+                let [label_area, input_area] =
+                    Layout::vertical([Constraint::Length(2), Constraint::Length(3)]).areas(area);
+
+                Line::from(format!(" Note about {}", note.book.title)).render(label_area, buf);
+
+                let block = Block::bordered();
+                let inner = block.inner(input_area);
+                block.render(input_area, buf);
+                note.input.view(inner, buf);
+            }
+        }
+    }
+}
+
+impl Screen for Page {
+    fn title(&self) -> String {
+        match self {
+            Self::Shelf(_) => "Shelf".to_string(),
+            Self::Book(book) => book.book.title.to_string(),
+            Self::Note(_) => "Note".to_string(),
+        }
+    }
+}
+
+fn view(model: Model) -> ScreensView {
+    ScreensView { model }
+}
+
+struct ScreensView {
+    model: Model,
+}
+
+impl Widget for ScreensView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [screen_area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        let router = &self.model.router;
+        let breadcrumbs = router.titles().collect::<Vec<_>>().join(" › ");
+
+        let block = Block::bordered().title(format!(" {breadcrumbs} "));
+        let inner = block.inner(screen_area);
+        block.render(screen_area, buf);
+        router.view(inner, buf);
+
+        let mut hints = vec![router.current().hints()];
+
+        if router.can_go_back() {
+            hints.push("esc back");
+        }
+
+        hints.push("ctrl+c quit");
+
+        Line::from(format!(" {}", hints.join(" • ")))
+            .dark_gray()
+            .render(help_area, buf);
+    }
+}
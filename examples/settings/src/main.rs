@@ -0,0 +1,177 @@
+//! # [TeaTui] Form example
+//!
+//! An account settings form, each field being checked as it is left and the passwords
+//! being checked against each other. `tab` and the arrows move between the fields,
+//! `space` toggles a checkbox or changes the theme, `enter` saves and `esc` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::form::{self, Field, Form, Value, Values},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_bracketed_paste();
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    let form = Form::new([
+        Field::text("name", "Name")
+            .with_placeholder("Alice Liddell")
+            .with_required(true),
+        Field::text("email", "Email")
+            .with_placeholder("alice@example.com")
+            .with_required(true)
+            .with_validator(|email| match email.split_once('@') {
+                Some((user, domain)) if !user.is_empty() && domain.contains('.') => Ok(()),
+                _ => Err("Not an email address".to_string()),
+            }),
+        Field::number("port", "SSH port")
+            .with_value("22")
+            .with_range(Some(1.0), Some(65535.0)),
+        Field::password("password", "Password").with_validator(|password| {
+            if password.chars().count() < 8 {
+                Err("At least 8 characters".to_string())
+            } else {
+                Ok(())
+            }
+        }),
+        Field::password("confirm", "Confirm password"),
+        Field::select("theme", "Theme", ["System", "Light", "Dark"]),
+        Field::checkbox("newsletter", "Newsletter").with_checked(true),
+    ])
+    .with_check(|values| {
+        if values.text("password") == values.text("confirm") {
+            Ok(())
+        } else {
+            Err(("confirm", "The passwords differ".to_string()))
+        }
+    })
+    .with_submit_label("Save");
+
+    let keymap =
+        Keymap::new()
+            .bind("esc", Message::Exit, "quit")
+            .bind("ctrl+c", Message::Exit, "quit");
+
+    let model = Model {
+        form,
+        saved: None,
+        keymap,
+    };
+
+    (model, None)
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    form: Form,
+    /// Values of the last save.
+    saved: Option<Values>,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Form(form::Message),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        Message::Event(event) => match form::Message::from_event(&event) {
+            Some(msg) => update(model, Message::Form(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::Form(msg) => match model.form.apply(msg) {
+            (form, Some(values)) => {
+                let model = Model {
+                    form,
+                    saved: Some(values),
+                    ..model
+                };
+
+                Update::Next(model, None)
+            }
+            (form, None) => Update::Next(Model { form, ..model }, None),
+        },
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> SettingsView {
+    SettingsView { model }
+}
+
+struct SettingsView {
+    model: Model,
+}
+
+impl Widget for SettingsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [area] = Layout::horizontal([Constraint::Length(64)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [form_area, saved_area, help_area] = Layout::vertical([
+            Constraint::Length(self.model.form.height() + 2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Account ");
+        let inner = block.inner(form_area);
+        block.render(form_area, buf);
+        self.model.form.view(inner, buf);
+
+        if let Some(values) = &self.model.saved {
+            let lines: Vec<Line> = values
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        _ if key == "password" || key == "confirm" => "••••••••".to_string(),
+                        Value::Text(text) => format!("{text:?}"),
+                        Value::Number(number) => number.to_string(),
+                        Value::Bool(checked) => checked.to_string(),
+                    };
+
+                    Line::from(format!(" {key} = {value}"))
+                })
+                .collect();
+
+            let block = Block::bordered().title(" Saved ").green();
+            let inner = block.inner(saved_area);
+            block.render(saved_area, buf);
+
+            for (line, row) in lines.into_iter().zip(inner.rows()) {
+                line.render(row, buf);
+            }
+        }
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
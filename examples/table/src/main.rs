@@ -0,0 +1,226 @@
+//! # [TeaTui] Table example
+//!
+//! The planets of the solar system, wider than most terminals.
+//! `↑`/`↓` select a planet, `←`/`→` move between columns, `s` sorts by the current column,
+//! `+`, `-` and `=` resize it and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::table::{self, Column, Table},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default().with_keymap(|model: &Model| &model.keymap);
+
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<()>) {
+    (Model::default(), None)
+}
+
+#[derive(Debug, Clone)]
+struct Planet {
+    name: &'static str,
+    kind: &'static str,
+    /// Mean distance to the sun, in millions of kilometers.
+    distance: f64,
+    /// Equatorial diameter, in kilometers.
+    diameter: u32,
+    /// Length of a year, in earth days.
+    year: f64,
+    moons: u16,
+    discovered: &'static str,
+}
+
+const PLANETS: [Planet; 8] = [
+    Planet {
+        name: "Mercury",
+        kind: "terrestrial",
+        distance: 57.9,
+        diameter: 4_879,
+        year: 88.0,
+        moons: 0,
+        discovered: "antiquity",
+    },
+    Planet {
+        name: "Venus",
+        kind: "terrestrial",
+        distance: 108.2,
+        diameter: 12_104,
+        year: 224.7,
+        moons: 0,
+        discovered: "antiquity",
+    },
+    Planet {
+        name: "Earth",
+        kind: "terrestrial",
+        distance: 149.6,
+        diameter: 12_756,
+        year: 365.2,
+        moons: 1,
+        discovered: "antiquity",
+    },
+    Planet {
+        name: "Mars",
+        kind: "terrestrial",
+        distance: 228.0,
+        diameter: 6_792,
+        year: 687.0,
+        moons: 2,
+        discovered: "antiquity",
+    },
+    Planet {
+        name: "Jupiter",
+        kind: "gas giant",
+        distance: 778.5,
+        diameter: 142_984,
+        year: 4_331.0,
+        moons: 95,
+        discovered: "antiquity",
+    },
+    Planet {
+        name: "Saturn",
+        kind: "gas giant",
+        distance: 1_432.0,
+        diameter: 120_536,
+        year: 10_747.0,
+        moons: 146,
+        discovered: "antiquity",
+    },
+    Planet {
+        name: "Uranus",
+        kind: "ice giant",
+        distance: 2_867.0,
+        diameter: 51_118,
+        year: 30_589.0,
+        moons: 28,
+        discovered: "1781, William Herschel",
+    },
+    Planet {
+        name: "Neptune",
+        kind: "ice giant",
+        distance: 4_515.0,
+        diameter: 49_528,
+        year: 59_800.0,
+        moons: 16,
+        discovered: "1846, Le Verrier and Galle",
+    },
+];
+
+#[derive(Debug, Clone)]
+struct Model {
+    planets: Table<Planet>,
+    keymap: Keymap<Message>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        let columns = [
+            Column::new("Planet", |planet: &Planet| planet.name.to_string()),
+            Column::new("Type", |planet: &Planet| planet.kind.to_string()),
+            Column::new("Distance (Mkm)", |planet: &Planet| {
+                format!("{:.1}", planet.distance)
+            })
+            .with_compare(|a, b| a.distance.total_cmp(&b.distance)),
+            Column::new("Diameter (km)", |planet: &Planet| {
+                planet.diameter.to_string()
+            })
+            .with_compare(|a, b| a.diameter.cmp(&b.diameter)),
+            Column::new("Year (days)", |planet: &Planet| {
+                format!("{:.1}", planet.year)
+            })
+            .with_compare(|a, b| a.year.total_cmp(&b.year)),
+            Column::new("Moons", |planet: &Planet| planet.moons.to_string())
+                .with_compare(|a, b| a.moons.cmp(&b.moons)),
+            Column::new("Discovered", |planet: &Planet| {
+                planet.discovered.to_string()
+            }),
+        ];
+
+        let keymap = Keymap::new()
+            .bind("s", Message::Table(table::Message::Sort), "sort")
+            .bind("q", Message::Exit, "quit")
+            .bind("esc", Message::Exit, "quit");
+
+        Self {
+            planets: Table::new(PLANETS, columns)
+                .with_highlight_style(Style::new().bg(Color::Blue)),
+            keymap,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Event(Event),
+    Table(table::Message),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(event: Event) -> Self {
+        Self::Event(event)
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, ()> {
+    match msg {
+        Message::Event(event) => match table::Message::from_event(&event) {
+            Some(msg) => update(model, Message::Table(msg)),
+            None => Update::Next(model, None),
+        },
+        Message::Table(msg) => model
+            .planets
+            .update(msg)
+            .lift_model(|planets| Model { planets, ..model }),
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> PlanetsView {
+    PlanetsView { model }
+}
+
+struct PlanetsView {
+    model: Model,
+}
+
+impl Widget for PlanetsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [table_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Solar system ");
+        self.model.planets.view(block.inner(table_area), buf);
+        block.render(table_area, buf);
+
+        let planets = &self.model.planets;
+        let column = planets.columns()[planets.column()].title();
+        let status = match planets.selected() {
+            Some(planet) => format!(" {} · {column} column", planet.name),
+            None => format!(" {column} column"),
+        };
+        Line::from(status).render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
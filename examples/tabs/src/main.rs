@@ -1,4 +1,4 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::Event;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
@@ -8,10 +8,22 @@ use ratatui::{
     widgets::{Block, Padding, Paragraph, Tabs, Widget},
 };
 use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
-use teatui::{ProgramError, update::Update};
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::palette::{self, Command, CommandPalette},
+    help::Help,
+    keymap::Keymap,
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_command_palette(|model: &Model| &model.palette, Message::Palette);
 
-fn main() -> Result<(), ProgramError<Model, Message, ()>> {
-    teatui::start(init, update, view, |_, _| None)
+    teatui::start_with(options, init, update, view, |_, _| None).map_err(Box::new)
 }
 
 fn init() -> (Model, Option<()>) {
@@ -19,21 +31,68 @@ fn init() -> (Model, Option<()>) {
 }
 
 /// Defines the state of the application
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 struct Model {
     selected_tab: SelectedTab,
+    keymap: Keymap<Message>,
+    palette: CommandPalette<Message>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        let keymap = Keymap::new()
+            .bind("l", Message::NextTab, "next tab")
+            .bind("right", Message::NextTab, "next tab")
+            .bind("h", Message::PreviousTab, "previous tab")
+            .bind("left", Message::PreviousTab, "previous tab")
+            .bind("g g", Message::FirstTab, "first tab")
+            .bind("G", Message::LastTab, "last tab")
+            .bind("q", Message::Exit, "quit")
+            .bind("esc", Message::Exit, "quit");
+
+        // Every binding is a command, and so is every tab
+        let palette =
+            SelectedTab::iter().fold(CommandPalette::from_keymap(&keymap), |palette, tab| {
+                palette.with_command(Command::new(
+                    format!("go to {tab}"),
+                    Message::SelectTab(tab),
+                ))
+            });
+
+        // The runtime opens the palette on its own, this binding only shows up in the help
+        let keymap = keymap.bind(
+            "ctrl+p",
+            Message::Palette(palette::Message::Open),
+            "commands",
+        );
+
+        Self {
+            selected_tab: SelectedTab::default(),
+            keymap,
+            palette,
+        }
+    }
 }
 
 impl Model {
     pub fn next_tab(self) -> Self {
         Model {
             selected_tab: self.selected_tab.next(),
+            ..self
         }
     }
 
     pub fn previous_tab(self) -> Self {
         Model {
             selected_tab: self.selected_tab.previous(),
+            ..self
+        }
+    }
+
+    pub fn select_tab(self, selected_tab: SelectedTab) -> Self {
+        Model {
+            selected_tab,
+            ..self
         }
     }
 }
@@ -68,45 +127,23 @@ impl SelectedTab {
 }
 
 /// Messages that represent a change of state in the application
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Message {
     NextTab,
     PreviousTab,
+    FirstTab,
+    LastTab,
+    SelectTab(SelectedTab),
+    Palette(palette::Message),
     Exit,
     NoOp,
 }
 
+/// Key presses are resolved through the keymap of the model,
+/// every other event is ignored
 impl From<crossterm::event::Event> for Message {
-    fn from(value: Event) -> Self {
-        match value {
-            Event::Key(KeyEvent {
-                code: KeyCode::Esc | KeyCode::Char('q'),
-                kind: KeyEventKind::Press,
-                state: _,
-                modifiers: _,
-            }) => Self::Exit,
-
-            Event::Key(KeyEvent {
-                code: KeyCode::Char('l') | KeyCode::Right,
-                kind: KeyEventKind::Press,
-                state: _,
-                modifiers: _,
-            }) => Self::NextTab,
-
-            Event::Key(KeyEvent {
-                code: KeyCode::Char('h') | KeyCode::Left,
-                kind: KeyEventKind::Press,
-                state: _,
-                modifiers: _,
-            }) => Self::PreviousTab,
-
-            Event::FocusGained
-            | Event::FocusLost
-            | Event::Key(_)
-            | Event::Mouse(_)
-            | Event::Paste(_)
-            | Event::Resize(_, _) => Self::NoOp,
-        }
+    fn from(_value: Event) -> Self {
+        Self::NoOp
     }
 }
 
@@ -120,6 +157,13 @@ fn update(model: Model, msg: Message) -> Update<Model, ()> {
         Message::NoOp => Update::Next(model, None),
         Message::NextTab => Update::Next(Model::next_tab(model), None),
         Message::PreviousTab => Update::Next(Model::previous_tab(model), None),
+        Message::FirstTab => Update::Next(model.select_tab(SelectedTab::Tab1), None),
+        Message::LastTab => Update::Next(model.select_tab(SelectedTab::Tab4), None),
+        Message::SelectTab(tab) => Update::Next(model.select_tab(tab), None),
+        Message::Palette(msg) => model
+            .palette
+            .update(msg)
+            .lift_model(|palette| Model { palette, ..model }),
     }
 }
 
@@ -149,9 +193,10 @@ impl Widget for AppWidget {
 
         self.model.selected_tab.render(inner_area, buf);
 
-        Line::raw("◄ ► to change tab | Press q to quit")
-            .centered()
-            .render(footer_area, buf);
+        Help::short(&self.model.keymap).render(footer_area, buf);
+
+        // Drawn last, on top of everything else
+        self.model.palette.view(area, buf);
     }
 }
 
@@ -0,0 +1,196 @@
+//! # [TeaTui] Toasts example
+//!
+//! Pretends to back up a folder, the effects reporting how it went with toasts
+//! stacked in the top right corner. The runtime removes every toast once it expires,
+//! the model never counts time. `b` starts a backup, `x` dismisses the newest toast,
+//! `c` dismisses them all and `q` quits.
+use crossterm::event::Event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use std::thread;
+use std::time::Duration;
+use teatui::{
+    ProgramError,
+    component::Component,
+    components::toast::{self, Level, Toast, Toasts},
+    help::Help,
+    keymap::Keymap,
+    layers::{Layered, Placement},
+    options::ProgramOptions,
+    update::Update,
+};
+
+fn main() -> Result<(), Box<ProgramError<Model, Message, Effect>>> {
+    let options = ProgramOptions::default()
+        .with_keymap(|model: &Model| &model.keymap)
+        .with_toasts(|model: &Model| &model.toasts, Message::Toasts);
+
+    teatui::start_with(options, init, update, view, effects).map_err(Box::new)
+}
+
+fn init() -> (Model, Option<Effect>) {
+    let keymap = Keymap::new()
+        .bind("b", Message::Backup, "back up")
+        .bind("x", Message::Toasts(toast::Message::Dismiss), "dismiss")
+        .bind(
+            "c",
+            Message::Toasts(toast::Message::DismissAll),
+            "dismiss all",
+        )
+        .bind("q", Message::Exit, "quit");
+
+    let model = Model {
+        toasts: Toasts::new(),
+        backups: vec![],
+        running: 0,
+        keymap,
+    };
+
+    (model, None)
+}
+
+#[derive(Debug, Clone)]
+struct Model {
+    toasts: Toasts,
+    /// Reports of the backups done.
+    backups: Vec<Toast>,
+    /// Backups being run by the effects.
+    running: usize,
+    keymap: Keymap<Message>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    NoOp,
+    Toasts(toast::Message),
+    Backup,
+    /// A backup is over, with the toast reporting it.
+    BackedUp(Toast),
+    Exit,
+}
+
+impl From<Event> for Message {
+    fn from(_value: Event) -> Self {
+        Self::NoOp
+    }
+}
+
+#[derive(Debug)]
+enum Effect {
+    /// Runs the backup with the given number.
+    Backup(usize),
+}
+
+fn effects(_model: Model, effect: Effect) -> Option<Message> {
+    match effect {
+        Effect::Backup(number) => {
+            thread::sleep(Duration::from_millis(800));
+
+            let toast = match number % 3 {
+                0 => Toast::error(format!("Backup #{number} failed: the disk is full")),
+                2 => Toast::warn(format!(
+                    "Backup #{number} done, 3 files were skipped because they were in use"
+                )),
+                _ => Toast::info(format!("Backup #{number} done")),
+            };
+
+            Some(Message::BackedUp(toast))
+        }
+    }
+}
+
+fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+    match msg {
+        Message::NoOp => Update::Next(model, None),
+        Message::Toasts(msg) => model
+            .toasts
+            .update(msg)
+            .lift_model(|toasts| Model { toasts, ..model }),
+        Message::Backup => {
+            let number = model.backups.len() + model.running + 1;
+
+            let model = Model {
+                running: model.running + 1,
+                ..model
+            };
+
+            Update::Next(model, Some(Effect::Backup(number)))
+        }
+        Message::BackedUp(toast) => {
+            let mut backups = model.backups.clone();
+            backups.push(toast.clone());
+
+            let model = Model {
+                backups,
+                running: model.running - 1,
+                ..model
+            };
+
+            update(model, Message::Toasts(toast::Message::Push(toast)))
+        }
+        Message::Exit => Update::Exit,
+    }
+}
+
+fn view(model: Model) -> ToastsView {
+    ToastsView { model }
+}
+
+struct ToastsView {
+    model: Model,
+}
+
+impl Widget for ToastsView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let model = &self.model;
+
+        Layered::new(BackupsView { model })
+            .layer(Placement::Full, model.toasts.widget())
+            .render(area, buf);
+    }
+}
+
+/// The backups below the toasts.
+struct BackupsView<'a> {
+    model: &'a Model,
+}
+
+impl Widget for BackupsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [backups_area, status_area, help_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Backups ");
+        let inner = block.inner(backups_area);
+        block.render(backups_area, buf);
+
+        for (toast, row) in self.model.backups.iter().zip(inner.rows()) {
+            let line = Line::from(format!(" {}", toast.text()));
+
+            match toast.level() {
+                Level::Info => line.render(row, buf),
+                Level::Warn => line.yellow().render(row, buf),
+                Level::Error => line.red().render(row, buf),
+            }
+        }
+
+        let status = match self.model.running {
+            0 => Line::default(),
+            running => Line::from(format!(" {running} backup(s) running…")).dark_gray(),
+        };
+        status.render(status_area, buf);
+
+        Help::short(&self.model.keymap)
+            .with_key_style(Style::new().fg(Color::Cyan))
+            .render(help_area, buf);
+    }
+}
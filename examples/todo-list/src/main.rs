@@ -2,15 +2,17 @@
 
 use crate::model::Model;
 use message::Message;
-use teatui::ProgramError;
+use teatui::{ProgramError, options::ProgramOptions};
 
 mod message;
 mod model;
 mod update;
 mod view;
 
-fn main() -> Result<(), ProgramError<Model, Message, ()>> {
-    teatui::start(init, update::update, view::view, |_, _| None)
+fn main() -> Result<(), Box<ProgramError<Model, Message, ()>>> {
+    let options = ProgramOptions::default().with_mouse_gestures(Message::from_gesture);
+
+    teatui::start_with(options, init, update::update, view::view, |_, _| None).map_err(Box::new)
 }
 
 fn init() -> (Model, Option<()>) {
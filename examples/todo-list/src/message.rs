@@ -1,20 +1,40 @@
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use teatui::components::{list, text_input};
+use teatui::mouse::{MouseGesture, ScrollDirection};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Message {
     NoOp,
     Exit,
-    SelectNext,
-    SelectNone,
-    SelectPrevious,
-    SelectFirst,
-    SelectLast,
+    Event(Event),
+    List(list::Message),
     ToggleStatus,
+    StartSearch,
+    Search(text_input::Message),
+    /// Leaves the search, keeping the list filtered.
+    EndSearch,
+    /// Leaves the search, showing every item again.
+    CancelSearch,
 }
 
-impl From<crossterm::event::Event> for Message {
-    fn from(value: crossterm::event::Event) -> Self {
-        match value {
+impl Message {
+    pub fn from_gesture(gesture: MouseGesture) -> Option<Self> {
+        match gesture {
+            MouseGesture::Scroll {
+                direction: ScrollDirection::Down,
+                ..
+            } => Some(Self::List(list::Message::Next)),
+            MouseGesture::Scroll {
+                direction: ScrollDirection::Up,
+                ..
+            } => Some(Self::List(list::Message::Previous)),
+            _ => None,
+        }
+    }
+
+    /// Keys used while browsing the list.
+    pub fn browsing(event: Event) -> Self {
+        match event {
             Event::Key(KeyEvent {
                 code: KeyCode::Esc | KeyCode::Char('q'),
                 kind: KeyEventKind::Press,
@@ -34,42 +54,65 @@ impl From<crossterm::event::Event> for Message {
                 kind: KeyEventKind::Press,
                 state: _,
                 modifiers: _,
-            }) => Self::SelectNone,
+            }) => Self::List(list::Message::SelectNone),
 
             Event::Key(KeyEvent {
-                code: KeyCode::Char('j') | KeyCode::Down,
+                code: KeyCode::Char('g'),
                 kind: KeyEventKind::Press,
                 state: _,
                 modifiers: _,
-            }) => Self::SelectNext,
+            }) => Self::List(list::Message::First),
 
             Event::Key(KeyEvent {
-                code: KeyCode::Char('k') | KeyCode::Up,
+                code: KeyCode::Char('G'),
                 kind: KeyEventKind::Press,
                 state: _,
                 modifiers: _,
-            }) => Self::SelectPrevious,
+            }) => Self::List(list::Message::Last),
 
             Event::Key(KeyEvent {
-                code: KeyCode::Char('g') | KeyCode::Home,
+                code: KeyCode::Char('/'),
                 kind: KeyEventKind::Press,
                 state: _,
                 modifiers: _,
-            }) => Self::SelectFirst,
+            }) => Self::StartSearch,
+
+            // Arrows, `j`, `k`, home, end and the page keys
+            event => list::Message::from_event(&event).map_or(Self::NoOp, Self::List),
+        }
+    }
 
+    /// Keys used while typing a search.
+    pub fn searching(event: Event) -> Self {
+        match event {
             Event::Key(KeyEvent {
-                code: KeyCode::Char('G') | KeyCode::End,
+                code: KeyCode::Enter,
                 kind: KeyEventKind::Press,
                 state: _,
                 modifiers: _,
-            }) => Self::SelectLast,
+            }) => Self::EndSearch,
 
-            Event::FocusGained
-            | Event::FocusLost
-            | Event::Key(_)
-            | Event::Mouse(_)
-            | Event::Paste(_)
-            | Event::Resize(_, _) => Self::NoOp,
+            Event::Key(KeyEvent {
+                code: KeyCode::Esc,
+                kind: KeyEventKind::Press,
+                state: _,
+                modifiers: _,
+            }) => Self::CancelSearch,
+
+            Event::Key(KeyEvent {
+                code: KeyCode::Up | KeyCode::Down,
+                kind: KeyEventKind::Press,
+                state: _,
+                modifiers: _,
+            }) => list::Message::from_event(&event).map_or(Self::NoOp, Self::List),
+
+            event => text_input::Message::from_event(&event).map_or(Self::NoOp, Self::Search),
         }
     }
 }
+
+impl From<crossterm::event::Event> for Message {
+    fn from(value: crossterm::event::Event) -> Self {
+        Self::Event(value)
+    }
+}
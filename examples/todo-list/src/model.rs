@@ -1,9 +1,10 @@
-use ratatui::widgets::ListState;
+use teatui::components::{list::SelectList, text_input::TextInput};
 
 #[derive(Clone, Debug)]
 pub struct Model {
-    pub items: Vec<TodoItem>,
-    pub state: ListState,
+    pub todos: SelectList<TodoItem>,
+    pub search: TextInput,
+    pub searching: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -273,9 +274,18 @@ impl FromIterator<(Status, &'static str, &'static str)> for Model {
             .map(|(status, todo, info)| TodoItem::new(status, todo, info))
             .collect();
 
-        let state = ListState::default().with_selected(Some(0));
+        let todos =
+            SelectList::new(items)
+                .with_wrap_around(true)
+                .with_filter(|item: &TodoItem, query| {
+                    item.todo.to_lowercase().contains(&query.to_lowercase())
+                });
 
-        Self { items, state }
+        Self {
+            todos,
+            search: TextInput::new().with_placeholder("search"),
+            searching: false,
+        }
     }
 }
 
@@ -290,75 +300,14 @@ impl TodoItem {
 }
 
 impl Model {
-    pub fn selected(&self) -> Option<&TodoItem> {
-        self.state
-            .selected()
-            .and_then(|index| self.items.get(index))
-    }
-
-    pub fn select_none(self) -> Self {
-        let state = ListState::default().with_selected(None);
-
-        Self {
-            items: self.items,
-            state,
-        }
-    }
-
-    pub fn select_first(self) -> Self {
-        let state = ListState::default().with_selected(Some(0));
-
-        Model {
-            items: self.items,
-            state,
-        }
-    }
-
-    pub fn select_last(self) -> Self {
-        let state = ListState::default().with_selected(Some(self.items.len() - 1));
+    pub fn toggle_status(self) -> Self {
+        let todos = self.todos.map_selected(|item| {
+            item.status = match item.status {
+                Status::Todo => Status::Completed,
+                Status::Completed => Status::Todo,
+            };
+        });
 
-        Model {
-            items: self.items,
-            state,
-        }
-    }
-
-    pub fn select_next(self) -> Self {
-        match self.state.selected() {
-            Some(index) => {
-                let state =
-                    ListState::default().with_selected(Some((index + 1) % self.items.len()));
-
-                Model { state, ..self }
-            }
-            None => self.select_first(),
-        }
-    }
-
-    pub fn select_previous(self) -> Self {
-        match self.state.selected() {
-            Some(0) => self.select_last(),
-            Some(index) => {
-                let state = ListState::default().with_selected(Some(index.saturating_sub(1)));
-                Model { state, ..self }
-            }
-            None => self.select_last(),
-        }
-    }
-
-    pub fn toggle_status(mut self) -> Self {
-        let selected_index = self.state.selected();
-
-        match selected_index {
-            Some(index) => {
-                self.items[index].status = match self.items[index].status {
-                    Status::Todo => Status::Completed,
-                    Status::Completed => Status::Todo,
-                };
-
-                Model { ..self }
-            }
-            None => Model { ..self },
-        }
+        Self { todos, ..self }
     }
 }
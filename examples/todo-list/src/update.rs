@@ -1,4 +1,4 @@
-use teatui::update::Update;
+use teatui::{component::Component, components::list, update::Update};
 
 use crate::{message::Message, model::Model};
 
@@ -6,11 +6,48 @@ pub fn update(model: Model, msg: Message) -> Update<Model, ()> {
     match msg {
         Message::NoOp => Update::Next(model, None),
         Message::Exit => Update::Exit,
-        Message::SelectNext => Update::Next(model.select_next(), None),
-        Message::SelectNone => Update::Next(model.select_none(), None),
-        Message::SelectPrevious => Update::Next(model.select_previous(), None),
-        Message::SelectFirst => Update::Next(model.select_first(), None),
-        Message::SelectLast => Update::Next(model.select_last(), None),
+        // Keys type into the search while it is open
+        Message::Event(event) if model.searching => update(model, Message::searching(event)),
+        Message::Event(event) => update(model, Message::browsing(event)),
+        Message::List(msg) => model
+            .todos
+            .update(msg)
+            .lift_model(|todos| Model { todos, ..model }),
         Message::ToggleStatus => Update::Next(model.toggle_status(), None),
+        Message::StartSearch => Update::Next(
+            Model {
+                searching: true,
+                ..model
+            },
+            None,
+        ),
+        // The list is filtered every time the search changes
+        Message::Search(msg) => match model
+            .search
+            .update(msg)
+            .lift_model(|search| Model { search, ..model })
+        {
+            Update::Next(model, _) => {
+                let query = model.search.value().to_string();
+                update(model, Message::List(list::Message::Filter(query)))
+            }
+            update => update,
+        },
+        Message::EndSearch => Update::Next(
+            Model {
+                searching: false,
+                ..model
+            },
+            None,
+        ),
+        Message::CancelSearch => {
+            let model = Model {
+                searching: false,
+                search: model.search.with_value(""),
+                ..model
+            };
+
+            update(model, Message::List(list::Message::Filter(String::new())))
+        }
     }
 }
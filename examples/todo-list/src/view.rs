@@ -8,12 +8,17 @@ use ratatui::{
     symbols,
     text::Line,
     widgets::{
-        Block, Borders, HighlightSpacing, List, ListItem, Padding, Paragraph, StatefulWidget,
-        Widget, Wrap,
+        Block, Borders, HighlightSpacing, ListItem, Padding, Paragraph, StatefulWidget, Widget,
+        Wrap,
     },
 };
 
-use crate::model::{Model, Status, TodoItem};
+use teatui::{component::Component, components::list, regions::Clickable};
+
+use crate::{
+    message::Message,
+    model::{Model, Status, TodoItem},
+};
 
 const TODO_HEADER_STYLE: Style = Style::new().fg(SLATE.c100).bg(BLUE.c800);
 const NORMAL_ROW_BG: Color = SLATE.c950;
@@ -31,7 +36,7 @@ pub struct AppWidget {
 }
 
 impl Widget for AppWidget {
-    fn render(mut self, area: Rect, buf: &mut Buffer) {
+    fn render(self, area: Rect, buf: &mut Buffer) {
         let [header_area, main_area, footer_area] = Layout::vertical([
             Constraint::Length(2),
             Constraint::Fill(1),
@@ -47,9 +52,7 @@ impl Widget for AppWidget {
             .centered()
             .render(header_area, buf);
 
-        Paragraph::new("Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom.")
-            .centered()
-            .render(footer_area, buf);
+        self.render_footer(footer_area, buf);
 
         self.render_list(list_area, buf);
         self.render_selected_item(item_area, buf);
@@ -58,41 +61,68 @@ impl Widget for AppWidget {
 
 /// Rendering logic for the app
 impl AppWidget {
-    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+    fn render_list(&self, area: Rect, buf: &mut Buffer) {
+        let title = match self.model.todos.query() {
+            "" => "TODO List".to_string(),
+            query => format!("TODO List matching \"{query}\""),
+        };
+
         let block = Block::new()
-            .title(Line::raw("TODO List").centered())
+            .title(Line::raw(title).centered())
             .borders(Borders::TOP)
             .border_set(symbols::border::EMPTY)
             .border_style(TODO_HEADER_STYLE)
             .bg(NORMAL_ROW_BG);
 
-        // Iterate through all elements in the `items` and stylize them.
-        let items: Vec<ListItem> = self
+        let list_area = block.inner(area);
+
+        // Stylize every item shown, alternating the background of the rows
+        let list = self
             .model
-            .items
-            .iter()
-            .enumerate()
-            .map(|(i, todo_item)| {
-                let color = alternate_colors(i);
-                ListItem::from(todo_item).bg(color)
+            .todos
+            .list(list_area.height, |position, todo_item| {
+                ListItem::from(todo_item).bg(alternate_colors(position))
             })
-            .collect();
-
-        // Create a List from all list items and highlight the currently selected one
-        let list = List::new(items)
             .block(block)
             .highlight_style(SELECTED_STYLE)
             .highlight_symbol(">")
             .highlight_spacing(HighlightSpacing::Always);
 
+        // The state is built from the model every frame, so ratatui can't change the model.
         // We need to disambiguate this trait method as both `Widget` and `StatefulWidget` share the
         // same method name `render`.
-        StatefulWidget::render(list, area, buf, &mut self.model.state);
+        let mut state = self.model.todos.state(list_area.height);
+        StatefulWidget::render(list, area, buf, &mut state);
+
+        // Every visible row selects its item when clicked
+        let shown = self.model.todos.shown(list_area.height);
+
+        for (row, (index, _)) in list_area.rows().zip(shown) {
+            row.on_click(Message::List(list::Message::Select(index)));
+        }
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        if !self.model.searching {
+            Paragraph::new(
+                "Use ↓↑ to move, ← to unselect, → to change status, g/G to go top/bottom, / to search.",
+            )
+            .centered()
+            .render(area, buf);
+
+            return;
+        }
+
+        let [prompt_area, search_area] =
+            Layout::horizontal([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+
+        Line::raw("/ ").render(prompt_area, buf);
+        self.model.search.widget().render(search_area, buf);
     }
 
     fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
         // We get the info depending on the item's state.
-        let info = if let Some(item) = self.model.selected() {
+        let info = if let Some(item) = self.model.todos.selected() {
             match item.status {
                 Status::Completed => format!("✓ DONE: {}", item.info),
                 Status::Todo => format!("☐ TODO: {}", item.info),
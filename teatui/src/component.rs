@@ -0,0 +1,303 @@
+//! Reusable pieces of an application, each one with its own model, messages and effects.
+//!
+//! A parent embeds a [`Component`] in its model, wraps the child messages in one of its own
+//! messages and the child effects in one of its own effects.
+//! Components running effects implement [`Effects`], so the parent runs the child effects
+//! and lifts the message they produce, routing it back to the child through the parent update:
+//!
+//! ```no_run
+//! # use ratatui::{buffer::Buffer, layout::Rect};
+//! # use teatui::component::{self, Component, Effects};
+//! # use teatui::update::Update;
+//! # mod todo {
+//! #     use ratatui::{buffer::Buffer, layout::Rect};
+//! #     use teatui::component::{Component, Effects};
+//! #     use teatui::update::Update;
+//! #     pub struct Todo;
+//! #     pub enum Message {}
+//! #     pub enum Effect {}
+//! #     impl Component for Todo {
+//! #         type Message = Message;
+//! #         type Effect = Effect;
+//! #         fn update(self, message: Message) -> Update<Self, Effect> { match message {} }
+//! #         fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+//! #     }
+//! #     impl Effects for Todo {
+//! #         fn run(effect: Effect) -> Option<Message> { match effect {} }
+//! #     }
+//! # }
+//! # use todo::Todo;
+//! struct Model {
+//!     todo: Todo,
+//! }
+//!
+//! enum Message {
+//!     Todo(todo::Message),
+//!     Exit,
+//! }
+//!
+//! enum Effect {
+//!     Todo(todo::Effect),
+//! }
+//!
+//! impl Component for Model {
+//!     type Message = Message;
+//!     type Effect = Effect;
+//!
+//!     fn update(self, msg: Message) -> Update<Self, Effect> {
+//!         match msg {
+//!             Message::Todo(msg) => self
+//!                 .todo
+//!                 .update(msg)
+//!                 .lift(|todo| Model { todo, ..self }, Effect::Todo),
+//!             Message::Exit => Update::Exit,
+//!         }
+//!     }
+//!
+//!     fn view(&self, area: Rect, buf: &mut Buffer) {
+//!         self.todo.view(area, buf);
+//!     }
+//! }
+//!
+//! impl Effects for Model {
+//!     fn run(effect: Effect) -> Option<Message> {
+//!         match effect {
+//!             Effect::Todo(effect) => Todo::run(effect).map(Message::Todo),
+//!         }
+//!     }
+//! }
+//! # impl From<crossterm::event::Event> for Message {
+//! #     fn from(_event: crossterm::event::Event) -> Self { Message::Exit }
+//! # }
+//! # impl Clone for Model { fn clone(&self) -> Self { Model { todo: Todo } } }
+//! # impl std::fmt::Debug for Model {
+//! #     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { f.write_str("Model") }
+//! # }
+//! # impl std::fmt::Debug for Effect {
+//! #     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { f.write_str("Effect") }
+//! # }
+//! # fn init() -> (Model, Option<Effect>) { (Model { todo: Todo }, None) }
+//!
+//! # #[cfg(not(feature = "tokio"))]
+//! teatui::start(init, Model::update, component::render, component::effects);
+//! ```
+use crate::update::Update;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+
+/// A model together with the messages it handles, the effects it asks for and the way it is drawn.
+pub trait Component: Sized {
+    type Message;
+    type Effect;
+
+    /// Effect run when the component is created or shown, such as loading what it displays.
+    ///
+    /// `None` by default. The parent lifts it like the effects of [`Component::update`].
+    fn init(&self) -> Option<Self::Effect> {
+        None
+    }
+
+    fn update(self, message: Self::Message) -> Update<Self, Self::Effect>;
+
+    fn view(&self, area: Rect, buf: &mut Buffer);
+
+    /// Borrows the component as a widget, to render it inside the view of its parent.
+    fn widget(&self) -> ComponentWidget<'_, Self> {
+        ComponentWidget { component: self }
+    }
+}
+
+/// A component whose effects are run outside of its update, usually by the effects function
+/// of its parent.
+pub trait Effects: Component {
+    /// Runs an effect of the component, returning the message giving back its result.
+    fn run(effect: Self::Effect) -> Option<Self::Message>;
+}
+
+/// Widget drawing a borrowed component, see [`Component::widget`].
+pub struct ComponentWidget<'a, C> {
+    component: &'a C,
+}
+
+impl<C: Component> Widget for ComponentWidget<'_, C> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.component.view(area, buf);
+    }
+}
+
+/// Widget drawing an owned component, see [`render`].
+pub struct Rendered<C> {
+    component: C,
+}
+
+impl<C: Component> Widget for Rendered<C> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.component.view(area, buf);
+    }
+}
+
+/// View function of a program whose model is a component.
+///
+/// Together with [`Component::update`], a component can be started as a whole program:
+/// `teatui::start(init, App::update, component::render, component::effects)`.
+pub fn render<C: Component>(component: C) -> Rendered<C> {
+    Rendered { component }
+}
+
+/// Effects function of a program whose model is a component, see [`Effects`].
+pub fn effects<C: Effects>(_component: C, effect: C::Effect) -> Option<C::Message> {
+    C::run(effect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counter(i32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum CounterMessage {
+        Add(i32),
+        Saved(i32),
+        Quit,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum CounterEffect {
+        Save(i32),
+    }
+
+    impl Component for Counter {
+        type Message = CounterMessage;
+        type Effect = CounterEffect;
+
+        fn update(self, message: CounterMessage) -> Update<Self, CounterEffect> {
+            match message {
+                CounterMessage::Add(n) => {
+                    let count = self.0 + n;
+                    Update::Next(Counter(count), Some(CounterEffect::Save(count)))
+                }
+                CounterMessage::Saved(_) => Update::Next(self, None),
+                CounterMessage::Quit => Update::Exit,
+            }
+        }
+
+        fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+    }
+
+    impl Effects for Counter {
+        fn run(effect: CounterEffect) -> Option<CounterMessage> {
+            match effect {
+                CounterEffect::Save(count) => Some(CounterMessage::Saved(count)),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Toggle(bool);
+
+    impl Component for Toggle {
+        type Message = ();
+        type Effect = Infallible;
+
+        fn update(self, _message: ()) -> Update<Self, Infallible> {
+            Update::Next(Toggle(!self.0), None)
+        }
+
+        fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Model {
+        counter: Counter,
+        toggle: Toggle,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Counter(CounterMessage),
+        Toggle,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Effect {
+        Counter(CounterEffect),
+    }
+
+    impl Component for Model {
+        type Message = Message;
+        type Effect = Effect;
+
+        fn update(self, message: Message) -> Update<Self, Effect> {
+            match message {
+                Message::Counter(msg) => self
+                    .counter
+                    .update(msg)
+                    .lift(|counter| Model { counter, ..self }, Effect::Counter),
+                Message::Toggle => self
+                    .toggle
+                    .update(())
+                    .lift_model(|toggle| Model { toggle, ..self }),
+            }
+        }
+
+        fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+    }
+
+    impl Effects for Model {
+        fn run(effect: Effect) -> Option<Message> {
+            match effect {
+                Effect::Counter(effect) => Counter::run(effect).map(Message::Counter),
+            }
+        }
+    }
+
+    fn model() -> Model {
+        Model {
+            counter: Counter(1),
+            toggle: Toggle(false),
+        }
+    }
+
+    fn next(update: Update<Model, Effect>) -> (Model, Option<Effect>) {
+        match update {
+            Update::Next(model, effect) => (model, effect),
+            Update::Exit => panic!("the model exited"),
+        }
+    }
+
+    #[test]
+    fn lifting_maps_the_model_and_the_effect_of_the_child() {
+        let (model, effect) = next(model().update(Message::Counter(CounterMessage::Add(2))));
+
+        assert_eq!(model.counter, Counter(3));
+        assert_eq!(model.toggle, Toggle(false));
+        assert_eq!(effect, Some(Effect::Counter(CounterEffect::Save(3))));
+    }
+
+    #[test]
+    fn child_effects_give_back_a_message_for_the_child() {
+        let message = effects(model(), Effect::Counter(CounterEffect::Save(3)));
+
+        assert_eq!(message, Some(Message::Counter(CounterMessage::Saved(3))));
+    }
+
+    #[test]
+    fn lifting_the_model_of_a_child_without_effects() {
+        let (model, effect) = next(model().update(Message::Toggle));
+
+        assert_eq!(model.toggle, Toggle(true));
+        assert_eq!(model.counter, Counter(1));
+        assert_eq!(effect, None);
+    }
+
+    #[test]
+    fn a_child_exiting_exits_the_parent() {
+        let update = model().update(Message::Counter(CounterMessage::Quit));
+
+        assert!(matches!(update, Update::Exit));
+    }
+}
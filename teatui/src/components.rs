@@ -0,0 +1,17 @@
+//! Ready-made components, see [`crate::component::Component`].
+pub mod dialog;
+mod edit;
+pub mod file_picker;
+pub mod form;
+pub mod list;
+pub mod palette;
+pub mod progress;
+pub mod router;
+pub mod spinner;
+pub mod table;
+pub mod text_input;
+pub mod textarea;
+pub mod toast;
+pub mod tree;
+pub mod viewport;
+pub mod virtual_list;
@@ -0,0 +1,630 @@
+//! Confirm and prompt dialogs, stacked above the view and resolving to a message.
+//!
+//! The model keeps the open dialogs in [`Modals`], drawn as the top layer of the view.
+//! Once registered with [`ProgramOptions::with_modals`](crate::options::ProgramOptions::with_modals),
+//! the runtime gives every key to the topmost dialog: `enter` closes it and sends
+//! the message it resolves to, `esc` closes it and sends its cancel message, if any.
+//!
+//! ```no_run
+//! # use teatui::components::dialog::{Dialog, ModalMessage};
+//! # use teatui::update::Update;
+//! # struct Model;
+//! # #[derive(Clone)]
+//! # enum Message { AskDelete(usize), Delete(usize), Modals(ModalMessage<Message>) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::AskDelete(index) => {
+//!     let dialog = Dialog::confirm("Delete", "Delete this note?", Message::Delete(index));
+//!     update(model, Message::Modals(ModalMessage::open(dialog)))
+//! }
+//! # _ => Update::Next(model, None),
+//! # }
+//! # }
+//! ```
+use super::text_input::{self, TextInput, Validator};
+use crate::component::Component;
+use crate::keymap;
+use crate::layers::{self, Placement};
+use crate::update::Update;
+use crate::view;
+use crossterm::event::{Event, KeyCode};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Padding, Paragraph, Widget, Wrap};
+use std::convert::Infallible;
+
+#[derive(Debug, Clone)]
+enum Kind<Msg> {
+    /// Buttons with the message each one sends, `None` sending the cancel message.
+    Confirm {
+        buttons: Vec<(String, Option<Msg>)>,
+        selected: usize,
+    },
+    Prompt {
+        input: TextInput,
+        on_submit: fn(String) -> Msg,
+    },
+}
+
+/// Model of a dialog.
+#[derive(Debug, Clone)]
+pub struct Dialog<Msg> {
+    title: String,
+    text: String,
+    kind: Kind<Msg>,
+    /// Message sent when the dialog is dismissed.
+    on_cancel: Option<Msg>,
+    width: u16,
+    style: Style,
+    button_style: Style,
+    error_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Selects the next button.
+    Next,
+    /// Selects the previous button.
+    Previous,
+    /// Edits the value of a prompt.
+    Input(text_input::Message),
+}
+
+impl<Msg> Dialog<Msg> {
+    fn new(title: impl Into<String>, text: impl Into<String>, kind: Kind<Msg>) -> Self {
+        Self {
+            title: title.into(),
+            text: text.into(),
+            kind,
+            on_cancel: None,
+            width: 50,
+            style: Style::new(),
+            button_style: Style::new().add_modifier(Modifier::REVERSED),
+            error_style: Style::new().fg(Color::Red),
+        }
+    }
+
+    /// Asks whether to go on: `Yes` sends `on_confirm` and `No` dismisses the dialog.
+    pub fn confirm(title: impl Into<String>, text: impl Into<String>, on_confirm: Msg) -> Self {
+        let kind = Kind::Confirm {
+            buttons: vec![
+                ("Yes".to_string(), Some(on_confirm)),
+                ("No".to_string(), None),
+            ],
+            selected: 0,
+        };
+
+        Self::new(title, text, kind)
+    }
+
+    /// Asks for a line of text, sending `on_submit` with it.
+    pub fn prompt(
+        title: impl Into<String>,
+        text: impl Into<String>,
+        on_submit: fn(String) -> Msg,
+    ) -> Self {
+        let kind = Kind::Prompt {
+            input: TextInput::new(),
+            on_submit,
+        };
+
+        Self::new(title, text, kind)
+    }
+
+    /// Labels of the buttons of a confirm dialog, `Yes` and `No` by default.
+    pub fn with_labels(self, confirm: impl Into<String>, cancel: impl Into<String>) -> Self {
+        let kind = match self.kind {
+            Kind::Confirm {
+                mut buttons,
+                selected,
+            } => {
+                buttons[0].0 = confirm.into();
+                buttons[1].0 = cancel.into();
+                Kind::Confirm { buttons, selected }
+            }
+            kind => kind,
+        };
+
+        Self { kind, ..self }
+    }
+
+    /// Message sent when the dialog is dismissed with `esc` or its cancel button.
+    pub fn with_cancel(self, on_cancel: Msg) -> Self {
+        Self {
+            on_cancel: Some(on_cancel),
+            ..self
+        }
+    }
+
+    /// Initial value of a prompt.
+    pub fn with_value(self, value: impl Into<String>) -> Self {
+        self.map_input(|input| input.with_value(value))
+    }
+
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        self.map_input(|input| input.with_placeholder(placeholder))
+    }
+
+    /// Checks the value of a prompt, which can't be submitted while it is not valid.
+    pub fn with_validator(self, validator: Validator) -> Self {
+        self.map_input(|input| input.with_validator(validator))
+    }
+
+    /// Width of the dialog, 50 by default.
+    pub fn with_width(self, width: u16) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Style of the border and the text.
+    pub fn with_style(self, style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the selected button, reversed by default.
+    pub fn with_button_style(self, button_style: impl Into<Style>) -> Self {
+        Self {
+            button_style: button_style.into(),
+            ..self
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Value typed in a prompt.
+    pub fn value(&self) -> Option<&str> {
+        match &self.kind {
+            Kind::Prompt { input, .. } => Some(input.value()),
+            Kind::Confirm { .. } => None,
+        }
+    }
+
+    fn map_input(self, f: impl FnOnce(TextInput) -> TextInput) -> Self {
+        let kind = match self.kind {
+            Kind::Prompt { input, on_submit } => Kind::Prompt {
+                input: f(input),
+                on_submit,
+            },
+            kind => kind,
+        };
+
+        Self { kind, ..self }
+    }
+
+    /// Converts a key or a paste according to the kind of dialog.
+    fn message(&self, event: &Event) -> Option<Message> {
+        match (&self.kind, event) {
+            (Kind::Prompt { .. }, _) => text_input::Message::from_event(event).map(Message::Input),
+            (Kind::Confirm { .. }, Event::Key(key)) => match key.code {
+                KeyCode::Right | KeyCode::Tab | KeyCode::Char('l') => Some(Message::Next),
+                KeyCode::Left | KeyCode::BackTab | KeyCode::Char('h') => Some(Message::Previous),
+                _ => None,
+            },
+            (Kind::Confirm { .. }, _) => None,
+        }
+    }
+
+    /// Messages sent when `enter` is pressed, `None` while the prompt is not valid.
+    fn resolve(&self) -> Option<Vec<Msg>>
+    where
+        Msg: Clone,
+    {
+        match &self.kind {
+            Kind::Confirm { buttons, selected } => {
+                let message = buttons[*selected]
+                    .1
+                    .clone()
+                    .or_else(|| self.on_cancel.clone());
+                Some(message.into_iter().collect())
+            }
+            Kind::Prompt { input, on_submit } => input
+                .is_valid()
+                .then(|| vec![on_submit(input.value().to_string())]),
+        }
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        let kind = match (self.kind, message) {
+            (Kind::Confirm { buttons, selected }, Message::Next) => Kind::Confirm {
+                selected: (selected + 1) % buttons.len(),
+                buttons,
+            },
+            (Kind::Confirm { buttons, selected }, Message::Previous) => Kind::Confirm {
+                selected: (selected + buttons.len() - 1) % buttons.len(),
+                buttons,
+            },
+            (Kind::Prompt { input, on_submit }, Message::Input(msg)) => Kind::Prompt {
+                input: input.apply(msg),
+                on_submit,
+            },
+            (kind, _) => kind,
+        };
+
+        Self { kind, ..self }
+    }
+
+    /// Rows taken by the dialog drawn `width` columns wide.
+    pub fn height(&self, width: u16) -> u16 {
+        let error = match &self.kind {
+            Kind::Prompt { input, .. } => u16::from(input.error().is_some()),
+            Kind::Confirm { .. } => 0,
+        };
+
+        // Borders, text, blank row, input or buttons
+        2 + layers::wrapped_height(&self.text, width.saturating_sub(4)) + 2 + error
+    }
+}
+
+impl<Msg: Clone> Component for Dialog<Msg> {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(format!(" {} ", self.title))
+            .padding(Padding::horizontal(1))
+            .style(self.style);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let text_height = layers::wrapped_height(&self.text, inner.width).min(inner.height);
+        Paragraph::new(self.text.as_str())
+            .wrap(Wrap { trim: true })
+            .render(
+                Rect {
+                    height: text_height,
+                    ..inner
+                },
+                buf,
+            );
+
+        let row = Rect {
+            y: inner.y + text_height + 1,
+            height: 1,
+            ..inner
+        }
+        .intersection(inner);
+
+        match &self.kind {
+            Kind::Confirm { buttons, selected } => {
+                let spans = buttons.iter().enumerate().flat_map(|(index, (label, _))| {
+                    let style = if index == *selected {
+                        self.button_style
+                    } else {
+                        Style::new()
+                    };
+
+                    [Span::raw("  "), Span::styled(format!(" {label} "), style)]
+                });
+
+                Line::from_iter(spans)
+                    .alignment(Alignment::Right)
+                    .render(row, buf);
+            }
+            Kind::Prompt { input, .. } => {
+                input.view(row, buf);
+
+                if let Some(error) = input.error() {
+                    let error_row = Rect {
+                        y: row.y + 1,
+                        ..row
+                    }
+                    .intersection(inner);
+                    Line::styled(error, self.error_style).render(error_row, buf);
+                }
+            }
+        }
+    }
+}
+
+/// Stack of the dialogs open, the last one being on top.
+#[derive(Debug, Clone)]
+pub struct Modals<Msg> {
+    dialogs: Vec<Dialog<Msg>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModalMessage<Msg> {
+    /// Opens a dialog above the others, see [`ModalMessage::open`].
+    Open(Box<Dialog<Msg>>),
+    /// Closes the topmost dialog.
+    Close,
+    /// Gives a message to the topmost dialog.
+    Dialog(Message),
+}
+
+impl<Msg> ModalMessage<Msg> {
+    pub fn open(dialog: Dialog<Msg>) -> Self {
+        Self::Open(Box::new(dialog))
+    }
+}
+
+impl<Msg> Default for Modals<Msg> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Msg> Modals<Msg> {
+    pub fn new() -> Self {
+        Self { dialogs: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dialogs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.dialogs.len()
+    }
+
+    /// Dialog receiving the keys.
+    pub fn top(&self) -> Option<&Dialog<Msg>> {
+        self.dialogs.last()
+    }
+
+    /// Messages the runtime turns an event into while a dialog is open,
+    /// `None` letting the event go through the rest of the pipeline.
+    ///
+    /// Keys and pastes belong to the topmost dialog and mouse events are dropped,
+    /// so nothing below the dialogs is clicked. Other events go through.
+    pub(crate) fn route(
+        &self,
+        event: &Event,
+        wrap: &dyn Fn(ModalMessage<Msg>) -> Msg,
+    ) -> Option<Vec<Msg>>
+    where
+        Msg: Clone,
+    {
+        let dialog = self.top()?;
+
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Paste(_) => {
+                let message = dialog.message(event).map(ModalMessage::Dialog);
+                return Some(message.map(wrap).into_iter().collect());
+            }
+            Event::Mouse(_) => return Some(vec![]),
+            _ => return None,
+        };
+
+        if !keymap::is_press(key) {
+            return Some(vec![]);
+        }
+
+        let close = || wrap(ModalMessage::Close);
+
+        match key.code {
+            // An invalid prompt stays open, showing why
+            KeyCode::Enter => match dialog.resolve() {
+                Some(resolved) => Some([close()].into_iter().chain(resolved).collect()),
+                None => Some(vec![]),
+            },
+            KeyCode::Esc => Some(
+                [close()]
+                    .into_iter()
+                    .chain(dialog.on_cancel.clone())
+                    .collect(),
+            ),
+            _ => {
+                let message = dialog.message(event).map(ModalMessage::Dialog);
+                Some(message.map(wrap).into_iter().collect())
+            }
+        }
+    }
+}
+
+impl<Msg: Clone> Component for Modals<Msg> {
+    type Message = ModalMessage<Msg>;
+    type Effect = Infallible;
+
+    fn update(mut self, message: ModalMessage<Msg>) -> Update<Self, Infallible> {
+        match message {
+            ModalMessage::Open(dialog) => self.dialogs.push(*dialog),
+            ModalMessage::Close => {
+                self.dialogs.pop();
+            }
+            ModalMessage::Dialog(msg) => {
+                if let Some(dialog) = self.dialogs.pop() {
+                    self.dialogs.push(dialog.apply(msg));
+                }
+            }
+        }
+
+        Update::Next(self, None)
+    }
+
+    /// Draws every dialog centered in `area`, only the topmost one showing its cursor.
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        for dialog in &self.dialogs {
+            let width = dialog.width.min(area.width);
+            let dialog_area = Placement::center(width, dialog.height(width)).area(area);
+
+            Clear.render(dialog_area, buf);
+            view::hide_cursor();
+            dialog.view(dialog_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{
+        KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Answer {
+        Delete,
+        Cancel,
+        Rename(String),
+    }
+
+    #[derive(Debug, Clone)]
+    enum Msg {
+        Answer(Answer),
+        Modals(ModalMessage<Msg>),
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn apply(modals: Modals<Msg>, message: ModalMessage<Msg>) -> Modals<Msg> {
+        match modals.update(message) {
+            Update::Next(modals, _) => modals,
+            Update::Exit => unreachable!("dialogs never exit"),
+        }
+    }
+
+    fn open(dialogs: impl IntoIterator<Item = Dialog<Msg>>) -> Modals<Msg> {
+        dialogs.into_iter().fold(Modals::new(), |modals, dialog| {
+            apply(modals, ModalMessage::open(dialog))
+        })
+    }
+
+    fn confirm() -> Dialog<Msg> {
+        Dialog::confirm("Delete", "Delete this note?", Msg::Answer(Answer::Delete))
+    }
+
+    fn prompt() -> Dialog<Msg> {
+        Dialog::prompt("Rename", "New name", |name| {
+            Msg::Answer(Answer::Rename(name))
+        })
+    }
+
+    /// Routes the events like the runtime, applying the messages for the dialogs
+    /// and returning the other ones.
+    fn send(modals: Modals<Msg>, events: &[Event]) -> (Modals<Msg>, Vec<Answer>) {
+        let mut modals = modals;
+        let mut answers = vec![];
+
+        for event in events {
+            let messages = modals.route(event, &Msg::Modals).unwrap_or_default();
+
+            for message in messages {
+                match message {
+                    Msg::Modals(msg) => modals = apply(modals, msg),
+                    Msg::Answer(answer) => answers.push(answer),
+                }
+            }
+        }
+
+        (modals, answers)
+    }
+
+    #[test]
+    fn confirming_sends_the_message_of_the_dialog() {
+        let (modals, answers) = send(open([confirm()]), &[key(KeyCode::Enter)]);
+
+        assert!(modals.is_empty());
+        assert_eq!(answers, [Answer::Delete]);
+    }
+
+    #[test]
+    fn declining_sends_the_cancel_message_if_any() {
+        let dialog = confirm().with_cancel(Msg::Answer(Answer::Cancel));
+        let (modals, answers) = send(open([dialog]), &[key(KeyCode::Right), key(KeyCode::Enter)]);
+        assert!(modals.is_empty());
+        assert_eq!(answers, [Answer::Cancel]);
+
+        let (modals, answers) = send(open([confirm()]), &[key(KeyCode::Tab), key(KeyCode::Enter)]);
+        assert!(modals.is_empty());
+        assert_eq!(answers, []);
+    }
+
+    #[test]
+    fn escape_dismisses_the_dialog() {
+        let dialog = confirm().with_cancel(Msg::Answer(Answer::Cancel));
+        let (modals, answers) = send(open([dialog]), &[key(KeyCode::Esc)]);
+
+        assert!(modals.is_empty());
+        assert_eq!(answers, [Answer::Cancel]);
+    }
+
+    #[test]
+    fn prompts_answer_with_the_value_typed() {
+        let dialog = prompt().with_value("note");
+        let events = [
+            key(KeyCode::Char('s')),
+            Event::Paste(" 2".to_string()),
+            key(KeyCode::Enter),
+        ];
+        let (modals, answers) = send(open([dialog]), &events);
+
+        assert!(modals.is_empty());
+        assert_eq!(answers, [Answer::Rename("notes 2".to_string())]);
+    }
+
+    #[test]
+    fn invalid_prompts_stay_open() {
+        let dialog = prompt().with_validator(|name| {
+            if name.is_empty() {
+                Err("The name can't be empty".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        let (modals, answers) = send(open([dialog]), &[key(KeyCode::Enter)]);
+        assert_eq!(modals.len(), 1);
+        assert_eq!(answers, []);
+
+        let (modals, answers) = send(modals, &[key(KeyCode::Char('a')), key(KeyCode::Enter)]);
+        assert!(modals.is_empty());
+        assert_eq!(answers, [Answer::Rename("a".to_string())]);
+    }
+
+    #[test]
+    fn only_the_topmost_dialog_gets_the_keys() {
+        let (modals, answers) = send(open([confirm(), prompt()]), &[key(KeyCode::Enter)]);
+
+        assert_eq!(modals.len(), 1);
+        assert_eq!(modals.top().map(Dialog::title), Some("Delete"));
+        assert_eq!(answers, [Answer::Rename(String::new())]);
+    }
+
+    #[test]
+    fn open_dialogs_swallow_keys_and_mouse_events() {
+        let modals = open([confirm()]);
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        });
+        let release = Event::Key(KeyEvent::new_with_kind(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        ));
+
+        for event in [key(KeyCode::Char('q')), click, release] {
+            assert!(
+                modals
+                    .route(&event, &Msg::Modals)
+                    .is_some_and(|messages| messages.is_empty())
+            );
+        }
+
+        // Other events, such as resizing, go through
+        assert!(modals.route(&Event::Resize(80, 24), &Msg::Modals).is_none());
+    }
+
+    #[test]
+    fn events_go_through_without_dialogs() {
+        let modals = Modals::<Msg>::new();
+
+        assert!(modals.route(&key(KeyCode::Enter), &Msg::Modals).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Grapheme and word boundaries shared by the text editing components.
+//!
+//! Positions are byte offsets into the text, always on grapheme boundaries.
+use unicode_segmentation::UnicodeSegmentation;
+
+pub(crate) fn previous_boundary(text: &str, position: usize) -> usize {
+    text[..position]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+pub(crate) fn next_boundary(text: &str, position: usize) -> usize {
+    text[position..]
+        .graphemes(true)
+        .next()
+        .map_or(position, |grapheme| position + grapheme.len())
+}
+
+/// Start of the word before `position`, skipping the separators right before it.
+pub(crate) fn word_start(text: &str, position: usize) -> usize {
+    let mut graphemes = text[..position].grapheme_indices(true).rev().peekable();
+    let mut start = position;
+
+    while let Some((index, _)) = graphemes.next_if(|(_, grapheme)| !is_word(grapheme)) {
+        start = index;
+    }
+
+    while let Some((index, _)) = graphemes.next_if(|(_, grapheme)| is_word(grapheme)) {
+        start = index;
+    }
+
+    start
+}
+
+/// End of the word after `position`, skipping the separators right after it.
+pub(crate) fn word_end(text: &str, position: usize) -> usize {
+    let mut graphemes = text[position..].graphemes(true).peekable();
+    let mut end = position;
+
+    while let Some(grapheme) = graphemes.next_if(|grapheme| !is_word(grapheme)) {
+        end += grapheme.len();
+    }
+
+    while let Some(grapheme) = graphemes.next_if(|grapheme| is_word(grapheme)) {
+        end += grapheme.len();
+    }
+
+    end
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_over_whole_graphemes() {
+        // "e" followed by a combining acute accent, then a flag made of two code points
+        let text = "ae\u{301}🇫🇷";
+
+        assert_eq!(next_boundary(text, 0), 1);
+        assert_eq!(next_boundary(text, 1), 4);
+        assert_eq!(next_boundary(text, 4), text.len());
+        assert_eq!(next_boundary(text, text.len()), text.len());
+
+        assert_eq!(previous_boundary(text, text.len()), 4);
+        assert_eq!(previous_boundary(text, 4), 1);
+        assert_eq!(previous_boundary(text, 0), 0);
+    }
+
+    #[test]
+    fn word_start_skips_separators_then_the_word() {
+        let text = "foo bar_baz  qux";
+
+        assert_eq!(word_start(text, text.len()), 13);
+        assert_eq!(word_start(text, 13), 4);
+        assert_eq!(word_start(text, 6), 4);
+        assert_eq!(word_start(text, 4), 0);
+        assert_eq!(word_start(text, 0), 0);
+    }
+
+    #[test]
+    fn word_end_skips_separators_then_the_word() {
+        let text = "foo bar_baz  qux";
+
+        assert_eq!(word_end(text, 0), 3);
+        assert_eq!(word_end(text, 3), 11);
+        assert_eq!(word_end(text, 5), 11);
+        assert_eq!(word_end(text, 11), text.len());
+        assert_eq!(word_end(text, text.len()), text.len());
+    }
+
+    #[test]
+    fn punctuation_separates_words() {
+        assert_eq!(word_start("path/to.file", 12), 8);
+        assert_eq!(word_end("path/to.file", 4), 7);
+    }
+}
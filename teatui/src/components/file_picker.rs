@@ -0,0 +1,629 @@
+//! File picker, listing directories through effects so update never waits on the filesystem.
+//!
+//! The picker asks for the listing of a directory with an [`Effect`], which the parent
+//! runs with [`FilePicker::run`] in its effects function. Choosing a file ends with
+//! [`Message::Chosen`], which the parent handles before giving it back to the picker:
+//!
+//! ```no_run
+//! # use std::path::PathBuf;
+//! # use teatui::component::{Component, Effects};
+//! # use teatui::components::file_picker::{self, FilePicker};
+//! # use teatui::update::Update;
+//! # struct Model { picker: FilePicker }
+//! # enum Message { Picker(file_picker::Message) }
+//! # enum Effect { Picker(file_picker::Effect) }
+//! # fn open(model: Model, _path: PathBuf) -> Update<Model, Effect> { Update::Next(model, None) }
+//! fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+//!     match msg {
+//!         Message::Picker(file_picker::Message::Chosen(path)) => open(model, path),
+//!         Message::Picker(msg) => model
+//!             .picker
+//!             .update(msg)
+//!             .lift(|picker| Model { picker, ..model }, Effect::Picker),
+//!     }
+//! }
+//!
+//! fn effects(_model: Model, effect: Effect) -> Option<Message> {
+//!     match effect {
+//!         Effect::Picker(effect) => FilePicker::run(effect).map(Message::Picker),
+//!     }
+//! }
+//! ```
+//!
+//! A new picker lists its directory once it gets [`Message::Refresh`].
+use super::list::{self, SelectList};
+use crate::component::{Component, Effects};
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{ListItem, StatefulWidget, Widget};
+use std::path::{self, Path, PathBuf};
+use std::{fs, io};
+
+/// File or directory listed by the picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+}
+
+impl Entry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the entry is a directory, or a link to one.
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Size of a file in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Whether the name of the entry starts with a dot.
+    pub fn is_hidden(&self) -> bool {
+        self.name.starts_with('.')
+    }
+}
+
+/// Model of a file picker.
+#[derive(Debug, Clone)]
+pub struct FilePicker {
+    dir: PathBuf,
+    /// Every entry of the directory, directories first.
+    entries: Vec<Entry>,
+    /// Entries shown, according to the hidden files and the extensions.
+    list: SelectList<Entry>,
+    loading: bool,
+    error: Option<String>,
+    /// Entry to select once the directory is listed, the one left when going up.
+    reselect: Option<PathBuf>,
+    show_hidden: bool,
+    /// Extensions of the files shown, every file being shown if empty.
+    extensions: Vec<String>,
+    dir_style: Style,
+    file_style: Style,
+    error_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    List(list::Message),
+    /// Enters the selected directory, or chooses the selected file.
+    Open,
+    /// Goes up to the parent directory.
+    Parent,
+    ToggleHidden,
+    /// Lists the directory again.
+    Refresh,
+    /// Gives the entries of a directory, or the error listing it.
+    Listed {
+        /// Directory as asked for by [`Effect::List`].
+        dir: PathBuf,
+        /// Absolute path of the directory, shown from then on.
+        resolved: PathBuf,
+        entries: Result<Vec<Entry>, String>,
+    },
+    /// A file was chosen, with its absolute path.
+    Chosen(PathBuf),
+}
+
+impl Message {
+    /// Converts the list keys, `enter`, `→` and `l` to open, `backspace`, `←` and `h`
+    /// to go up, `.` to toggle the hidden files and `ctrl+r` to refresh.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Key(key) = event else {
+            return list::Message::from_event(event).map(Self::List);
+        };
+
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => Some(Self::Open),
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => Some(Self::Parent),
+            KeyCode::Char('.') => Some(Self::ToggleHidden),
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Self::Refresh)
+            }
+            _ => list::Message::from_event(event).map(Self::List),
+        }
+    }
+}
+
+/// Filesystem work asked for by a file picker, see [`FilePicker::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Effect {
+    /// Resolves the absolute path of a directory and lists its entries.
+    List(PathBuf),
+    /// Resolves the absolute path of the chosen file.
+    Choose(PathBuf),
+}
+
+impl Effects for FilePicker {
+    /// Lists a directory or resolves the chosen file, returning the message giving back the result.
+    fn run(effect: Effect) -> Option<Message> {
+        match effect {
+            Effect::List(dir) => {
+                let resolved = fs::canonicalize(&dir)
+                    .or_else(|_| path::absolute(&dir))
+                    .unwrap_or_else(|_| dir.clone());
+                let entries = read_dir(&resolved).map_err(|error| error.to_string());
+
+                Some(Message::Listed {
+                    dir,
+                    resolved,
+                    entries,
+                })
+            }
+            Effect::Choose(path) => {
+                let path = fs::canonicalize(&path).unwrap_or(path);
+                Some(Message::Chosen(path))
+            }
+        }
+    }
+}
+
+/// Entries of a directory, directories first, then by name ignoring case.
+fn read_dir(dir: &Path) -> io::Result<Vec<Entry>> {
+    let mut entries = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let path = entry.path();
+            // Links are followed, a broken one is shown as an empty file
+            let metadata = fs::metadata(&path).ok();
+
+            Entry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                is_dir: metadata.as_ref().is_some_and(fs::Metadata::is_dir),
+                size: metadata.map_or(0, |metadata| metadata.len()),
+                path,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    entries.sort_by_cached_key(|entry| (!entry.is_dir, entry.name.to_lowercase()));
+    Ok(entries)
+}
+
+impl FilePicker {
+    /// Creates a picker of the given directory, listed on [`Message::Refresh`].
+    ///
+    /// The directory is resolved to an absolute path once listed, so going up ends at the root.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            entries: vec![],
+            list: SelectList::new([]),
+            loading: false,
+            error: None,
+            reselect: None,
+            show_hidden: false,
+            extensions: vec![],
+            dir_style: Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD),
+            file_style: Style::new(),
+            error_style: Style::new().fg(Color::Red),
+        }
+    }
+
+    /// Only shows the files with one of the given extensions, ignoring case.
+    /// Directories are always shown.
+    pub fn with_extensions<S: Into<String>>(self, extensions: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            extensions: extensions
+                .into_iter()
+                .map(|extension| extension.into().trim_start_matches('.').to_lowercase())
+                .collect(),
+            ..self
+        }
+        .refilter()
+    }
+
+    /// Whether the files and directories starting with a dot are shown, `false` by default.
+    pub fn with_show_hidden(self, show_hidden: bool) -> Self {
+        Self {
+            show_hidden,
+            ..self
+        }
+        .refilter()
+    }
+
+    /// Style of the directories, bold blue by default.
+    pub fn with_dir_style(self, dir_style: impl Into<Style>) -> Self {
+        Self {
+            dir_style: dir_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_file_style(self, file_style: impl Into<Style>) -> Self {
+        Self {
+            file_style: file_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_highlight_style(self, highlight_style: impl Into<Style>) -> Self {
+        Self {
+            list: self.list.with_highlight_style(highlight_style),
+            ..self
+        }
+    }
+
+    /// Style of the error shown when a directory can't be listed, red by default.
+    pub fn with_error_style(self, error_style: impl Into<Style>) -> Self {
+        Self {
+            error_style: error_style.into(),
+            ..self
+        }
+    }
+
+    /// Directory shown.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Entries shown, according to the hidden files and the extensions.
+    pub fn entries(&self) -> &[Entry] {
+        self.list.items()
+    }
+
+    pub fn selected(&self) -> Option<&Entry> {
+        self.list.selected()
+    }
+
+    /// Whether the directory is being listed.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Error listing the directory.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn shows_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    fn is_shown(&self, entry: &Entry) -> bool {
+        if entry.is_hidden() && !self.show_hidden {
+            return false;
+        }
+
+        entry.is_dir
+            || self.extensions.is_empty()
+            || entry.path.extension().is_some_and(|extension| {
+                let extension = extension.to_string_lossy().to_lowercase();
+                self.extensions.contains(&extension)
+            })
+    }
+
+    /// Shows the entries matching the settings, keeping the selected entry selected
+    /// when it is still shown, or the first one when nothing was selected.
+    fn refilter(self) -> Self {
+        let selected = self
+            .reselect
+            .clone()
+            .or_else(|| self.selected().map(|entry| entry.path.clone()));
+        let shown: Vec<Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| self.is_shown(entry))
+            .cloned()
+            .collect();
+
+        let index = selected.and_then(|path| shown.iter().position(|entry| entry.path == path));
+        let list = self.list.with_items(shown);
+        let list = match (index, list.selected_index()) {
+            (Some(index), _) => list.apply(list::Message::Select(index)),
+            (None, None) => list.apply(list::Message::First),
+            (None, Some(_)) => list,
+        };
+
+        Self { list, ..self }
+    }
+
+    /// Lists another directory, selecting `reselect` once it is listed.
+    fn change_dir(self, dir: PathBuf, reselect: Option<PathBuf>) -> (Self, Option<Effect>) {
+        let picker = Self {
+            dir: dir.clone(),
+            entries: vec![],
+            list: self.list.with_items([]),
+            loading: true,
+            error: None,
+            reselect,
+            ..self
+        };
+
+        (picker, Some(Effect::List(dir)))
+    }
+
+    pub(crate) fn apply(self, message: Message) -> (Self, Option<Effect>) {
+        match message {
+            Message::List(msg) => {
+                let picker = Self {
+                    list: self.list.apply(msg),
+                    ..self
+                };
+                (picker, None)
+            }
+            Message::Open => match self.selected() {
+                Some(entry) if entry.is_dir => {
+                    let dir = entry.path.clone();
+                    self.change_dir(dir, None)
+                }
+                Some(entry) => {
+                    let path = entry.path.clone();
+                    (self, Some(Effect::Choose(path)))
+                }
+                None => (self, None),
+            },
+            // A relative directory not listed yet has no parent to go to
+            Message::Parent => match self
+                .dir
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map(Path::to_path_buf)
+            {
+                Some(parent) => {
+                    let dir = self.dir.clone();
+                    self.change_dir(parent, Some(dir))
+                }
+                None => (self, None),
+            },
+            Message::ToggleHidden => {
+                let show_hidden = !self.show_hidden;
+                (self.with_show_hidden(show_hidden), None)
+            }
+            Message::Refresh => {
+                let dir = self.dir.clone();
+                let reselect = self.selected().map(|entry| entry.path.clone());
+                self.change_dir(dir, reselect)
+            }
+            // Listings of a directory left since then are stale
+            Message::Listed { dir, .. } if dir != self.dir => (self, None),
+            Message::Listed {
+                resolved, entries, ..
+            } => {
+                let (entries, error) = match entries {
+                    Ok(entries) => (entries, None),
+                    Err(error) => (vec![], Some(error)),
+                };
+
+                let picker = Self {
+                    dir: resolved,
+                    entries,
+                    error,
+                    loading: false,
+                    ..self
+                }
+                .refilter();
+
+                let picker = Self {
+                    reselect: None,
+                    ..picker
+                };
+                (picker, None)
+            }
+            Message::Chosen(_) => (self, None),
+        }
+    }
+
+    fn item<'a>(&self, entry: &'a Entry) -> ListItem<'a> {
+        let line = if entry.is_dir {
+            Line::from(vec![
+                Span::styled(entry.name.as_str(), self.dir_style),
+                Span::styled("/", self.dir_style),
+            ])
+        } else {
+            Line::from(Span::styled(entry.name.as_str(), self.file_style))
+        };
+
+        ListItem::new(line)
+    }
+}
+
+impl Component for FilePicker {
+    type Message = Message;
+    type Effect = Effect;
+
+    fn update(self, message: Message) -> Update<Self, Effect> {
+        let (picker, effect) = self.apply(message);
+        Update::Next(picker, effect)
+    }
+
+    /// Draws the entries, or whether the directory is being listed or could not be.
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(error) = &self.error {
+            Line::styled(
+                format!("Can't list the directory: {error}"),
+                self.error_style,
+            )
+            .render(area, buf);
+            return;
+        }
+
+        if self.loading {
+            Line::styled("Loading…", Style::new().fg(Color::DarkGray)).render(area, buf);
+            return;
+        }
+
+        if self.list.items().is_empty() {
+            Line::styled("No files", Style::new().fg(Color::DarkGray)).render(area, buf);
+            return;
+        }
+
+        let list = self.list.list(area.height, |_, entry| self.item(entry));
+        StatefulWidget::render(list, area, buf, &mut self.list.state(area.height));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIR: &str = "/nonexistent/picker";
+
+    fn dir() -> PathBuf {
+        PathBuf::from(DIR)
+    }
+
+    fn entry(name: &str, is_dir: bool) -> Entry {
+        Entry {
+            name: name.to_string(),
+            path: dir().join(name),
+            is_dir,
+            size: 0,
+        }
+    }
+
+    fn listed(picker: FilePicker) -> FilePicker {
+        let entries = vec![
+            entry(".config", true),
+            entry("src", true),
+            entry(".env", false),
+            entry("Cargo.toml", false),
+            entry("README.md", false),
+            entry("notes.MD", false),
+        ];
+
+        let listed = Message::Listed {
+            dir: dir(),
+            resolved: dir(),
+            entries: Ok(entries),
+        };
+
+        picker.apply(listed).0
+    }
+
+    fn names(picker: &FilePicker) -> Vec<&str> {
+        picker.entries().iter().map(Entry::name).collect()
+    }
+
+    #[test]
+    fn hides_the_hidden_entries_unless_asked() {
+        let picker = listed(FilePicker::new(DIR));
+        assert_eq!(
+            names(&picker),
+            ["src", "Cargo.toml", "README.md", "notes.MD"]
+        );
+
+        let picker = picker.apply(Message::ToggleHidden).0;
+        assert_eq!(names(&picker).len(), 6);
+    }
+
+    #[test]
+    fn shows_the_directories_and_the_files_with_the_extensions() {
+        let picker = listed(FilePicker::new(DIR).with_extensions([".md"]));
+
+        assert_eq!(names(&picker), ["src", "README.md", "notes.MD"]);
+    }
+
+    #[test]
+    fn keeps_the_selected_entry_when_refiltered() {
+        let picker = listed(FilePicker::new(DIR));
+        let picker = picker.apply(Message::List(list::Message::Last)).0;
+        assert_eq!(picker.selected().map(Entry::name), Some("notes.MD"));
+
+        let picker = picker.apply(Message::ToggleHidden).0;
+        assert_eq!(picker.selected().map(Entry::name), Some("notes.MD"));
+    }
+
+    #[test]
+    fn selects_the_first_entry_once_listed() {
+        let picker = listed(FilePicker::new(DIR));
+
+        assert_eq!(picker.selected().map(Entry::name), Some("src"));
+        assert!(!picker.is_loading());
+    }
+
+    #[test]
+    fn reselects_the_directory_left_when_going_up() {
+        let picker = FilePicker::new(dir().join("src"));
+        let (picker, effect) = picker.apply(Message::Parent);
+        assert_eq!(effect, Some(Effect::List(dir())));
+        assert!(picker.is_loading());
+
+        let picker = listed(picker);
+        assert_eq!(picker.selected().map(Entry::name), Some("src"));
+
+        // Only the first listing reselects it
+        let picker = picker.apply(Message::List(list::Message::Last)).0;
+        let picker = picker.apply(Message::ToggleHidden).0;
+        assert_eq!(picker.selected().map(Entry::name), Some("notes.MD"));
+    }
+
+    #[test]
+    fn ignores_stale_listings() {
+        let picker = FilePicker::new("/elsewhere");
+        let picker = listed(picker);
+
+        assert!(picker.entries().is_empty());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn going_up_stops_at_the_root() {
+        let (picker, effect) = FilePicker::new("/").apply(Message::Parent);
+
+        assert_eq!(picker.dir(), Path::new("/"));
+        assert_eq!(effect, None);
+    }
+
+    #[test]
+    fn the_directory_is_resolved_once_listed() {
+        let picker = FilePicker::new(".");
+        assert_eq!(picker.dir(), Path::new("."));
+
+        let (picker, effect) = picker.apply(Message::Refresh);
+        let listed = FilePicker::run(effect.unwrap()).unwrap();
+        let Message::Listed { dir, resolved, .. } = &listed else {
+            panic!("expected a listing, got {listed:?}");
+        };
+        assert_eq!(dir, Path::new("."));
+        assert!(resolved.is_absolute());
+
+        let resolved = resolved.clone();
+        let picker = picker.apply(listed).0;
+        assert_eq!(picker.dir(), resolved);
+        assert!(!picker.is_loading());
+    }
+
+    #[test]
+    fn directories_that_dont_exist_are_made_absolute() {
+        let listed = FilePicker::run(Effect::List(PathBuf::from("nonexistent/picker")));
+
+        let Some(Message::Listed {
+            resolved, entries, ..
+        }) = listed
+        else {
+            panic!("expected a listing, got {listed:?}");
+        };
+        assert_eq!(
+            resolved,
+            std::env::current_dir().unwrap().join("nonexistent/picker")
+        );
+        assert!(entries.is_err());
+    }
+
+    #[test]
+    fn relative_directories_not_listed_yet_have_no_parent() {
+        let (picker, effect) = FilePicker::new("src").apply(Message::Parent);
+
+        assert_eq!(picker.dir(), Path::new("src"));
+        assert_eq!(effect, None);
+    }
+}
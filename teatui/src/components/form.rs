@@ -0,0 +1,834 @@
+//! Form of labelled fields, validated as they are filled and submitted as a whole.
+//!
+//! Text, number and password fields are [`TextInput`]s, selects cycle through their options
+//! with `←`/`→` or `space`, and checkboxes toggle with `space`. `tab` and the arrows move
+//! between the fields and `enter` submits.
+//!
+//! The errors of a field are shown once it has been left, or once the form was submitted.
+//! The parent gives the messages of the form to [`Form::apply`], which returns the
+//! [`Values`] of the form once it is submitted while valid:
+//!
+//! ```no_run
+//! # use teatui::components::form::{self, Form, Values};
+//! # use teatui::update::Update;
+//! # struct Model { form: Form }
+//! # enum Message { Form(form::Message) }
+//! # fn save(model: Model, _values: Values) -> Update<Model, ()> { Update::Next(model, None) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Form(msg) => match model.form.apply(msg) {
+//!     (form, Some(values)) => save(Model { form, ..model }, values),
+//!     (form, None) => Update::Next(Model { form, ..model }, None),
+//! },
+//! # }
+//! # }
+//! ```
+use super::text_input::{self, Motion, TextInput, Validator};
+use crate::component::Component;
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
+use std::convert::Infallible;
+use unicode_width::UnicodeWidthStr;
+
+/// Checks the values of the whole form, returning the key of the field to show the error
+/// under, and why the values are not valid.
+pub type Check = fn(&Values) -> Result<(), (&'static str, String)>;
+
+/// Value of a field, see [`Values`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Value of a text, password or select field.
+    Text(String),
+    Number(f64),
+    /// Value of a checkbox.
+    Bool(bool),
+}
+
+/// Values of the fields of a form, by key.
+///
+/// Empty number fields have no value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Values {
+    values: Vec<(String, Value)>,
+}
+
+impl Values {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values
+            .iter()
+            .find(|(candidate, _)| candidate == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(Value::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self, key: &str) -> Option<f64> {
+        match self.get(key) {
+            Some(Value::Number(number)) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key) {
+            Some(Value::Bool(checked)) => Some(*checked),
+            _ => None,
+        }
+    }
+
+    /// Keys and values, in the order of the fields.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Text(TextInput),
+    Number {
+        input: TextInput,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Password(TextInput),
+    Select {
+        options: Vec<String>,
+        selected: usize,
+    },
+    Checkbox(bool),
+}
+
+/// Field of a form.
+#[derive(Debug, Clone)]
+pub struct Field {
+    key: &'static str,
+    label: String,
+    kind: Kind,
+    required: bool,
+    validator: Option<Validator>,
+    /// Whether the field has been left once, so its errors are shown.
+    touched: bool,
+}
+
+impl Field {
+    fn new(key: &'static str, label: impl Into<String>, kind: Kind) -> Self {
+        Self {
+            key,
+            label: label.into(),
+            kind,
+            required: false,
+            validator: None,
+            touched: false,
+        }
+    }
+
+    pub fn text(key: &'static str, label: impl Into<String>) -> Self {
+        Self::new(key, label, Kind::Text(TextInput::new().with_focused(false)))
+    }
+
+    pub fn number(key: &'static str, label: impl Into<String>) -> Self {
+        let kind = Kind::Number {
+            input: TextInput::new().with_focused(false),
+            min: None,
+            max: None,
+        };
+
+        Self::new(key, label, kind)
+    }
+
+    /// Text field whose value is masked.
+    pub fn password(key: &'static str, label: impl Into<String>) -> Self {
+        let input = TextInput::new().with_mask('•').with_focused(false);
+        Self::new(key, label, Kind::Password(input))
+    }
+
+    /// Field choosing one of the given options, the first one by default.
+    pub fn select<S: Into<String>>(
+        key: &'static str,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let kind = Kind::Select {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: 0,
+        };
+
+        Self::new(key, label, kind)
+    }
+
+    /// Unchecked checkbox.
+    pub fn checkbox(key: &'static str, label: impl Into<String>) -> Self {
+        Self::new(key, label, Kind::Checkbox(false))
+    }
+
+    /// Initial value of a text, number or password field, or option selected by a select.
+    pub fn with_value(self, value: impl Into<String>) -> Self {
+        let value = value.into();
+        let kind = match self.kind {
+            Kind::Text(input) => Kind::Text(input.with_value(value)),
+            Kind::Number { input, min, max } => Kind::Number {
+                input: input.with_value(value),
+                min,
+                max,
+            },
+            Kind::Password(input) => Kind::Password(input.with_value(value)),
+            Kind::Select { options, selected } => Kind::Select {
+                selected: options
+                    .iter()
+                    .position(|option| *option == value)
+                    .unwrap_or(selected),
+                options,
+            },
+            kind @ Kind::Checkbox(_) => kind,
+        };
+
+        Self { kind, ..self }
+    }
+
+    /// Initial state of a checkbox.
+    pub fn with_checked(self, checked: bool) -> Self {
+        match self.kind {
+            Kind::Checkbox(_) => Self {
+                kind: Kind::Checkbox(checked),
+                ..self
+            },
+            _ => self,
+        }
+    }
+
+    /// Text shown in an empty text, number or password field.
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        self.map_input(|input| input.with_placeholder(placeholder))
+    }
+
+    /// Whether a text, number or password field can't be left empty, `false` by default.
+    pub fn with_required(self, required: bool) -> Self {
+        Self { required, ..self }
+    }
+
+    /// Checks the text of a text, number or password field once it is not empty.
+    pub fn with_validator(self, validator: Validator) -> Self {
+        Self {
+            validator: Some(validator),
+            ..self
+        }
+    }
+
+    /// Smallest and largest values of a number field, both included.
+    pub fn with_range(self, min: Option<f64>, max: Option<f64>) -> Self {
+        match self.kind {
+            Kind::Number { input, .. } => Self {
+                kind: Kind::Number { input, min, max },
+                ..self
+            },
+            _ => self,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn input(&self) -> Option<&TextInput> {
+        match &self.kind {
+            Kind::Text(input) | Kind::Number { input, .. } | Kind::Password(input) => Some(input),
+            Kind::Select { .. } | Kind::Checkbox(_) => None,
+        }
+    }
+
+    fn map_input(self, f: impl FnOnce(TextInput) -> TextInput) -> Self {
+        let kind = match self.kind {
+            Kind::Text(input) => Kind::Text(f(input)),
+            Kind::Number { input, min, max } => Kind::Number {
+                input: f(input),
+                min,
+                max,
+            },
+            Kind::Password(input) => Kind::Password(f(input)),
+            kind => kind,
+        };
+
+        Self { kind, ..self }
+    }
+
+    fn value(&self) -> Option<Value> {
+        match &self.kind {
+            Kind::Text(input) | Kind::Password(input) => {
+                Some(Value::Text(input.value().to_string()))
+            }
+            Kind::Number { input, .. } => input.value().trim().parse().ok().map(Value::Number),
+            Kind::Select { options, selected } => options
+                .get(*selected)
+                .map(|option| Value::Text(option.clone())),
+            Kind::Checkbox(checked) => Some(Value::Bool(*checked)),
+        }
+    }
+
+    /// Why the value of the field alone is not valid.
+    fn error(&self) -> Option<String> {
+        let text = self.input()?.value();
+
+        if text.trim().is_empty() {
+            return self.required.then(|| "Required".to_string());
+        }
+
+        if let Kind::Number { min, max, .. } = &self.kind {
+            // Infinity and NaN parse, but are not numbers anyone would type in a form
+            let Some(number) = text
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+            else {
+                return Some("Not a number".to_string());
+            };
+
+            match (min, max) {
+                (Some(min), Some(max)) if number < *min || number > *max => {
+                    return Some(format!("Between {min} and {max}"));
+                }
+                (Some(min), _) if number < *min => return Some(format!("At least {min}")),
+                (_, Some(max)) if number > *max => return Some(format!("At most {max}")),
+                _ => {}
+            }
+        }
+
+        self.validator.and_then(|validator| validator(text).err())
+    }
+
+    /// Gives an edit to the field, which interprets it according to its kind.
+    fn edit(self, msg: text_input::Message) -> Self {
+        let kind = match (self.kind, msg) {
+            (Kind::Checkbox(checked), text_input::Message::Insert(' ' | 'x')) => {
+                Kind::Checkbox(!checked)
+            }
+            (
+                Kind::Select { options, selected },
+                text_input::Message::Move(Motion::Right) | text_input::Message::Insert(' '),
+            ) => Kind::Select {
+                selected: (selected + 1) % options.len().max(1),
+                options,
+            },
+            (Kind::Select { options, selected }, text_input::Message::Move(Motion::Left)) => {
+                Kind::Select {
+                    selected: selected
+                        .checked_sub(1)
+                        .unwrap_or(options.len().saturating_sub(1)),
+                    options,
+                }
+            }
+            (kind @ (Kind::Select { .. } | Kind::Checkbox(_)), _) => kind,
+            (kind, msg) => return Self { kind, ..self }.map_input(|input| input.apply(msg)),
+        };
+
+        Self { kind, ..self }
+    }
+}
+
+/// Model of a form.
+#[derive(Debug, Clone)]
+pub struct Form {
+    fields: Vec<Field>,
+    check: Option<Check>,
+    /// Position of the focused field, the submit button coming after the fields.
+    focused: usize,
+    /// Whether submitting was tried, so every error is shown.
+    submitted: bool,
+    submit_label: String,
+    label_style: Style,
+    focused_style: Style,
+    error_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Focuses the next field, or the submit button after the last field.
+    Next,
+    Previous,
+    /// Focuses the field at the given position.
+    Focus(usize),
+    /// Edits the focused field.
+    Input(text_input::Message),
+    /// Submits the form if every field is valid, shows the errors otherwise.
+    Submit,
+}
+
+impl Message {
+    /// Converts `tab`, `shift+tab`, `↑` and `↓` to move between the fields, `enter` to submit,
+    /// and the other keys to edit the focused field.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        if let Event::Key(key) = event
+            && key.kind != KeyEventKind::Release
+        {
+            match key.code {
+                KeyCode::Tab | KeyCode::Down => return Some(Self::Next),
+                KeyCode::BackTab | KeyCode::Up => return Some(Self::Previous),
+                KeyCode::Enter => return Some(Self::Submit),
+                _ => {}
+            }
+        }
+
+        text_input::Message::from_event(event).map(Self::Input)
+    }
+}
+
+impl Form {
+    /// Creates a form of the given fields, the first one being focused.
+    pub fn new(fields: impl IntoIterator<Item = Field>) -> Self {
+        Self {
+            fields: fields.into_iter().collect(),
+            check: None,
+            focused: 0,
+            submitted: false,
+            submit_label: "Submit".to_string(),
+            label_style: Style::new(),
+            focused_style: Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            error_style: Style::new().fg(Color::Red),
+        }
+        .focus(0)
+    }
+
+    /// Checks the values once every field is valid on its own, for rules spanning several fields.
+    pub fn with_check(self, check: Check) -> Self {
+        Self {
+            check: Some(check),
+            ..self
+        }
+    }
+
+    /// Text of the submit button, `Submit` by default.
+    pub fn with_submit_label(self, submit_label: impl Into<String>) -> Self {
+        Self {
+            submit_label: submit_label.into(),
+            ..self
+        }
+    }
+
+    pub fn with_label_style(self, label_style: impl Into<Style>) -> Self {
+        Self {
+            label_style: label_style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the label of the focused field and of the focused submit button,
+    /// bold cyan by default.
+    pub fn with_focused_style(self, focused_style: impl Into<Style>) -> Self {
+        Self {
+            focused_style: focused_style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the errors, red by default.
+    pub fn with_error_style(self, error_style: impl Into<Style>) -> Self {
+        Self {
+            error_style: error_style.into(),
+            ..self
+        }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Key of the focused field, `None` when the submit button is focused.
+    pub fn focused(&self) -> Option<&'static str> {
+        self.fields.get(self.focused).map(Field::key)
+    }
+
+    pub fn values(&self) -> Values {
+        let values = self
+            .fields
+            .iter()
+            .filter_map(|field| field.value().map(|value| (field.key.to_string(), value)))
+            .collect();
+
+        Values { values }
+    }
+
+    /// Errors of the fields by key, the ones of the whole form coming last.
+    pub fn errors(&self) -> Vec<(&'static str, String)> {
+        let errors: Vec<_> = self
+            .fields
+            .iter()
+            .filter_map(|field| field.error().map(|error| (field.key, error)))
+            .collect();
+
+        if !errors.is_empty() {
+            return errors;
+        }
+
+        self.check
+            .and_then(|check| check(&self.values()).err())
+            .into_iter()
+            .collect()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors().is_empty()
+    }
+
+    /// Error shown under the field with the given key.
+    pub fn error(&self, key: &str) -> Option<String> {
+        let field = self.fields.iter().find(|field| field.key == key)?;
+
+        self.shown_error(field, &self.errors()).map(str::to_string)
+    }
+
+    /// Error of `field` among the errors of the form, shown once the field was left
+    /// or the form submitted.
+    fn shown_error<'a>(
+        &self,
+        field: &Field,
+        errors: &'a [(&'static str, String)],
+    ) -> Option<&'a str> {
+        if !field.touched && !self.submitted {
+            return None;
+        }
+
+        errors
+            .iter()
+            .find(|(key, _)| *key == field.key)
+            .map(|(_, error)| error.as_str())
+    }
+
+    /// Rows taken by the form: a row per field and per error shown,
+    /// a blank row and the submit button.
+    pub fn height(&self) -> u16 {
+        let errors = self.errors();
+        let shown = self
+            .fields
+            .iter()
+            .filter(|field| self.shown_error(field, &errors).is_some())
+            .count();
+
+        (self.fields.len() + shown + 2) as u16
+    }
+
+    /// Focuses the field at `position`, leaving the one focused before.
+    fn focus(self, position: usize) -> Self {
+        let position = position.min(self.fields.len());
+        let left = self.focused;
+        let fields = self
+            .fields
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let touched = field.touched || (index == left && index != position);
+                Field { touched, ..field }.map_input(|input| input.with_focused(index == position))
+            })
+            .collect();
+
+        Self {
+            fields,
+            focused: position,
+            ..self
+        }
+    }
+
+    /// Handles a message, returning the values of the form if it was submitted while valid.
+    pub fn apply(self, message: Message) -> (Self, Option<Values>) {
+        let positions = self.fields.len() + 1;
+
+        let form = match message {
+            Message::Next => {
+                let position = (self.focused + 1) % positions;
+                self.focus(position)
+            }
+            Message::Previous => {
+                let position = (self.focused + positions - 1) % positions;
+                self.focus(position)
+            }
+            Message::Focus(position) => self.focus(position),
+            Message::Input(msg) if self.focused < self.fields.len() => {
+                let mut fields = self.fields;
+                let field = fields.remove(self.focused).edit(msg);
+                fields.insert(self.focused, field);
+
+                Self { fields, ..self }
+            }
+            Message::Input(_) => self,
+            Message::Submit => {
+                let form = Self {
+                    submitted: true,
+                    ..self
+                };
+                let errors = form.errors();
+
+                let Some((key, _)) = errors.first() else {
+                    let values = form.values();
+                    return (form, Some(values));
+                };
+
+                // The first field in error is focused, so it can be fixed right away
+                match form.fields.iter().position(|field| field.key == *key) {
+                    Some(position) => form.focus(position),
+                    None => form,
+                }
+            }
+        };
+
+        (form, None)
+    }
+
+    fn field_view(&self, field: &Field, focused: bool, area: Rect, buf: &mut Buffer) {
+        let style = if focused {
+            self.focused_style
+        } else {
+            Style::new()
+        };
+
+        match &field.kind {
+            Kind::Text(input) | Kind::Number { input, .. } | Kind::Password(input) => {
+                input.view(area, buf);
+            }
+            Kind::Select { options, selected } => {
+                let option = options.get(*selected).map_or("", String::as_str);
+                Line::from(vec![
+                    Span::styled("‹ ", style),
+                    Span::raw(option),
+                    Span::styled(" ›", style),
+                ])
+                .render(area, buf);
+            }
+            Kind::Checkbox(checked) => {
+                let symbol = if *checked { "[x]" } else { "[ ]" };
+                Line::styled(symbol, style).render(area, buf);
+            }
+        }
+    }
+}
+
+/// Updating a form drops the submitted values, see [`Form::apply`] to get them.
+impl Component for Form {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message).0, None)
+    }
+
+    /// Draws the labels in a column, the fields next to them with their errors below,
+    /// and the submit button, see [`Form::height`].
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let label_width = self
+            .fields
+            .iter()
+            .map(|field| field.label.width() as u16)
+            .max()
+            .unwrap_or_default()
+            .min(area.width);
+        let field_x = area.x + (label_width + 2).min(area.width);
+        let field_width = area.right().saturating_sub(field_x);
+        let errors = self.errors();
+
+        let mut y = area.y;
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if y >= area.bottom() {
+                return;
+            }
+
+            let focused = index == self.focused;
+            let label_style = if focused {
+                self.label_style.patch(self.focused_style)
+            } else {
+                self.label_style
+            };
+
+            let label = Rect::new(area.x, y, label_width, 1);
+            Line::styled(field.label.as_str(), label_style)
+                .right_aligned()
+                .render(label, buf);
+            self.field_view(field, focused, Rect::new(field_x, y, field_width, 1), buf);
+            y += 1;
+
+            if let Some(error) = self.shown_error(field, &errors)
+                && y < area.bottom()
+            {
+                Line::styled(error, self.error_style)
+                    .render(Rect::new(field_x, y, field_width, 1), buf);
+                y += 1;
+            }
+        }
+
+        y += 1;
+
+        if y < area.bottom() {
+            let style = if self.focused == self.fields.len() {
+                self.focused_style.add_modifier(Modifier::REVERSED)
+            } else {
+                self.label_style
+            };
+
+            Line::styled(format!("[ {} ]", self.submit_label), style)
+                .render(Rect::new(field_x, y, field_width, 1), buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form() -> Form {
+        Form::new([
+            Field::text("name", "Name").with_required(true),
+            Field::number("port", "Port").with_range(Some(1.0), Some(65535.0)),
+            Field::password("password", "Password").with_validator(|password| {
+                if password.len() < 8 {
+                    Err("Too short".to_string())
+                } else {
+                    Ok(())
+                }
+            }),
+            Field::password("confirm", "Confirm"),
+        ])
+        .with_check(|values| {
+            if values.text("password") == values.text("confirm") {
+                Ok(())
+            } else {
+                Err(("confirm", "Differs".to_string()))
+            }
+        })
+    }
+
+    /// Focuses the field with the given key and types `text` in it.
+    fn fill(form: Form, key: &str, text: &str) -> Form {
+        let position = form.fields().iter().position(|field| field.key() == key);
+        let (form, _) = form.apply(Message::Focus(position.unwrap()));
+        let (form, _) = form.apply(Message::Input(text_input::Message::SetValue(
+            text.to_string(),
+        )));
+
+        form
+    }
+
+    #[test]
+    fn empty_optional_fields_are_valid() {
+        assert_eq!(form().errors(), [("name", "Required".to_string())]);
+    }
+
+    #[test]
+    fn checks_numbers_and_their_range() {
+        let form = fill(form(), "name", "alice");
+
+        for (text, error) in [
+            ("port", "Not a number"),
+            ("inf", "Not a number"),
+            ("NaN", "Not a number"),
+            ("0", "Between 1 and 65535"),
+            ("70000", "Between 1 and 65535"),
+        ] {
+            let form = fill(form.clone(), "port", text);
+            assert_eq!(form.errors(), [("port", error.to_string())], "{text}");
+        }
+
+        assert!(fill(form, "port", " 22 ").is_valid());
+    }
+
+    #[test]
+    fn runs_the_validators_on_non_empty_fields() {
+        let form = fill(form(), "name", "alice");
+        let form = fill(form, "password", "short");
+
+        assert_eq!(form.errors(), [("password", "Too short".to_string())]);
+    }
+
+    #[test]
+    fn checks_the_whole_form_once_every_field_is_valid() {
+        let form = fill(form(), "password", "long enough");
+        assert_eq!(form.errors(), [("name", "Required".to_string())]);
+
+        let form = fill(form, "name", "alice");
+        assert_eq!(form.errors(), [("confirm", "Differs".to_string())]);
+
+        let form = fill(form, "confirm", "long enough");
+        assert!(form.is_valid());
+    }
+
+    #[test]
+    fn shows_the_errors_of_the_fields_left_only() {
+        let form = form();
+        assert_eq!(form.error("name"), None);
+
+        let (form, _) = form.apply(Message::Next);
+        assert_eq!(form.error("name"), Some("Required".to_string()));
+    }
+
+    #[test]
+    fn submitting_an_invalid_form_focuses_the_first_error() {
+        let form = fill(form(), "name", "alice");
+        let form = fill(form, "password", "short");
+
+        let (form, values) = form.apply(Message::Submit);
+        assert!(values.is_none());
+        assert_eq!(form.focused(), Some("password"));
+    }
+
+    #[test]
+    fn submitting_a_valid_form_returns_the_values() {
+        let form = fill(form(), "name", "alice");
+        let form = fill(form, "port", "22");
+
+        let (_, values) = form.apply(Message::Submit);
+        let values = values.unwrap();
+        assert_eq!(values.text("name"), Some("alice"));
+        assert_eq!(values.number("port"), Some(22.0));
+        assert_eq!(values.text("password"), Some(""));
+    }
+
+    #[test]
+    fn submitting_focuses_the_field_in_error_for_the_whole_form() {
+        let form = fill(form(), "name", "alice");
+        let form = fill(form, "password", "long enough");
+        let form = fill(form, "confirm", "different");
+        let (form, _) = form.apply(Message::Focus(0));
+
+        let (form, values) = form.apply(Message::Submit);
+        assert!(values.is_none());
+        assert_eq!(form.focused(), Some("confirm"));
+    }
+
+    #[test]
+    fn submitting_shows_the_errors_of_every_field() {
+        let (form, _) = form().apply(Message::Submit);
+
+        assert_eq!(form.error("name"), Some("Required".to_string()));
+        assert_eq!(form.focused(), Some("name"));
+        // A row per field, the error, a blank row and the button
+        assert_eq!(form.height(), 7);
+    }
+
+    #[test]
+    fn values_of_every_kind_of_field() {
+        let form = Form::new([
+            Field::select("level", "Level", ["low", "high"]).with_value("high"),
+            Field::checkbox("notify", "Notify").with_checked(true),
+            Field::number("retries", "Retries").with_value("3"),
+            Field::number("timeout", "Timeout").with_value("soon"),
+        ]);
+        let values = form.values();
+
+        assert_eq!(values.text("level"), Some("high"));
+        assert_eq!(values.bool("notify"), Some(true));
+        assert_eq!(values.number("retries"), Some(3.0));
+        // Numbers which don't parse have no value
+        assert_eq!(values.get("timeout"), None);
+    }
+}
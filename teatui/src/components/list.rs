@@ -0,0 +1,705 @@
+//! Selectable list whose selection and scroll offset are plain values of its model.
+//!
+//! Unlike ratatui's `ListState`, nothing is mutated while rendering: the update function
+//! moves the selection and scrolls it into view, and the view builds a [`List`] and a
+//! [`ListState`] out of the model every frame.
+//!
+//! Only the items fitting in the area are given to ratatui, so drawing a long list costs
+//! as much as drawing a short one:
+//!
+//! ```no_run
+//! # use ratatui::{buffer::Buffer, layout::Rect, widgets::{ListItem, StatefulWidget}};
+//! # use teatui::components::list::SelectList;
+//! # struct Todo(String);
+//! # impl<'a> From<&'a Todo> for ListItem<'a> {
+//! #     fn from(todo: &'a Todo) -> Self { ListItem::new(todo.0.as_str()) }
+//! # }
+//! # struct Model { todos: SelectList<Todo> }
+//! # fn view(model: &Model, area: Rect, buf: &mut Buffer) {
+//! let list = model.todos.list(area.height, |_, todo| todo.into());
+//! StatefulWidget::render(list, area, buf, &mut model.todos.state(area.height));
+//! # }
+//! ```
+use crate::component::Component;
+use crate::fuzzy;
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::ops::Range;
+
+/// Tells whether an item matches the filter query.
+pub type Matcher<T> = fn(&T, &str) -> bool;
+
+/// How the items are filtered by the query.
+#[derive(Debug)]
+enum Filter<T> {
+    Matcher(Matcher<T>),
+    /// Fuzzy matches the text of every item, see [`crate::fuzzy`].
+    Fuzzy(fn(&T) -> &str),
+}
+
+// Function pointers are copied whatever the items are
+impl<T> Clone for Filter<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Filter<T> {}
+
+/// Model of a list with at most one selected item.
+///
+/// The page size is the number of rows the list is expected to be shown in,
+/// used to keep the selection in view and to move it a page at a time.
+#[derive(Debug, Clone)]
+pub struct SelectList<T> {
+    items: Vec<T>,
+    /// Indices of the items matching the filter, in order, or best match first.
+    visible: Vec<usize>,
+    /// Characters of every visible item matching a fuzzy query.
+    highlights: Vec<Vec<usize>>,
+    /// Position of the selected item among the visible ones.
+    selected: Option<usize>,
+    /// Position of the first visible item shown.
+    offset: usize,
+    page_size: usize,
+    wrap_around: bool,
+    query: String,
+    filter: Option<Filter<T>>,
+    highlight_style: Style,
+    highlight_symbol: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Next,
+    Previous,
+    First,
+    Last,
+    PageDown,
+    PageUp,
+    /// Selects the item at the given index, if it matches the filter.
+    Select(usize),
+    SelectNone,
+    /// Only shows the items matching the query, see [`SelectList::with_filter`].
+    Filter(String),
+}
+
+impl Message {
+    /// Converts the arrow keys, `j` and `k`, `home`, `end`, `page up`, `page down`
+    /// and the mouse wheel.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Down | KeyCode::Char('j') => Some(Self::Next),
+                KeyCode::Up | KeyCode::Char('k') => Some(Self::Previous),
+                KeyCode::Home => Some(Self::First),
+                KeyCode::End => Some(Self::Last),
+                KeyCode::PageDown => Some(Self::PageDown),
+                KeyCode::PageUp => Some(Self::PageUp),
+                _ => None,
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollDown => Some(Self::Next),
+                MouseEventKind::ScrollUp => Some(Self::Previous),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T> Default for SelectList<T> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            visible: vec![],
+            highlights: vec![],
+            selected: None,
+            offset: 0,
+            page_size: 10,
+            wrap_around: false,
+            query: String::new(),
+            filter: None,
+            highlight_style: Style::new().add_modifier(Modifier::REVERSED),
+            highlight_symbol: "",
+        }
+    }
+}
+
+impl<T> SelectList<T> {
+    /// Creates a list of the given items, the first one being selected.
+    pub fn new(items: impl IntoIterator<Item = T>) -> Self {
+        let list = Self::default().with_items(items);
+        let selected = (!list.visible.is_empty()).then_some(0);
+
+        Self { selected, ..list }
+    }
+
+    /// Replaces the items, keeping the selected position when possible.
+    pub fn with_items(self, items: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+            ..self
+        }
+        .refilter(None, true)
+    }
+
+    /// Rows the list is shown in, 10 by default.
+    pub fn with_page_size(self, page_size: usize) -> Self {
+        Self {
+            page_size: page_size.max(1),
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Whether moving past the last item selects the first one and the other way around,
+    /// `false` by default.
+    pub fn with_wrap_around(self, wrap_around: bool) -> Self {
+        Self {
+            wrap_around,
+            ..self
+        }
+    }
+
+    /// Enables [`Message::Filter`], showing only the items for which `matcher` returns `true`.
+    pub fn with_filter(self, matcher: Matcher<T>) -> Self {
+        Self {
+            filter: Some(Filter::Matcher(matcher)),
+            ..self
+        }
+        .refilter(None, false)
+    }
+
+    /// Enables [`Message::Filter`], showing only the items whose text fuzzy matches the query,
+    /// best match first.
+    ///
+    /// The matched characters of every item are given by [`SelectList::highlights`].
+    pub fn with_fuzzy_filter(self, text: fn(&T) -> &str) -> Self {
+        Self {
+            filter: Some(Filter::Fuzzy(text)),
+            ..self
+        }
+        .refilter(None, false)
+    }
+
+    pub fn with_highlight_style(self, highlight_style: impl Into<Style>) -> Self {
+        Self {
+            highlight_style: highlight_style.into(),
+            ..self
+        }
+    }
+
+    /// Symbol drawn before the selected item, nothing by default.
+    pub fn with_highlight_symbol(self, highlight_symbol: &'static str) -> Self {
+        Self {
+            highlight_symbol,
+            ..self
+        }
+    }
+
+    /// Every item, including the ones not matching the filter.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Items matching the filter, with their index.
+    pub fn visible(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.visible
+            .iter()
+            .map(|&index| (index, &self.items[index]))
+    }
+
+    /// Index of the selected item.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected.map(|position| self.visible[position])
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        self.selected_index().map(|index| &self.items[index])
+    }
+
+    /// Index of the first item shown, among the ones matching the filter.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Byte offsets of the characters matching the fuzzy query in the text of the item
+    /// at `position` in the list, see [`crate::fuzzy::highlight`].
+    pub fn highlights(&self, position: usize) -> &[usize] {
+        self.highlights.get(position).map_or(&[], Vec::as_slice)
+    }
+
+    /// Changes the selected item, the filter being applied again as it might not match anymore.
+    pub fn map_selected(mut self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(index) = self.selected_index() {
+            f(&mut self.items[index]);
+        }
+
+        self.refilter(None, true)
+    }
+
+    /// Sorts the items, keeping the selected one selected.
+    ///
+    /// The sort is stable, and items are shown in their new order when they are filtered.
+    pub fn sort_by(self, mut compare: impl FnMut(&T, &T) -> Ordering) -> Self {
+        let selected_index = self.selected_index();
+        let mut items: Vec<(usize, T)> = self.items.into_iter().enumerate().collect();
+        items.sort_by(|(_, a), (_, b)| compare(a, b));
+
+        let selected_index = selected_index
+            .and_then(|selected| items.iter().position(|(index, _)| *index == selected));
+        let list = Self {
+            items: items.into_iter().map(|(_, item)| item).collect(),
+            ..self
+        }
+        .refilter(None, true);
+
+        match selected_index {
+            Some(index) => list.apply(Message::Select(index)),
+            None => list,
+        }
+    }
+
+    /// Positions of the items shown in `height` rows: from the offset, scrolled further
+    /// if the list is drawn in fewer rows than the page size.
+    pub fn window(&self, height: u16) -> Range<usize> {
+        window(self.visible.len(), self.selected, self.offset, height)
+    }
+
+    /// Items shown in `height` rows, with their index.
+    pub fn shown(&self, height: u16) -> impl Iterator<Item = (usize, &T)> {
+        self.visible[self.window(height)]
+            .iter()
+            .map(|&index| (index, &self.items[index]))
+    }
+
+    /// State to render [`SelectList::list`] with, for the same height.
+    pub fn state(&self, height: u16) -> ListState {
+        let window = self.window(height);
+
+        ListState::default().with_selected(self.selected.map(|selected| selected - window.start))
+    }
+
+    /// Builds the ratatui list of the items matching the filter shown in `height` rows,
+    /// see [`SelectList::window`].
+    ///
+    /// `item` receives the position of every item in the list, for alternating styles.
+    pub fn list<'a>(
+        &'a self,
+        height: u16,
+        mut item: impl FnMut(usize, &'a T) -> ListItem<'a>,
+    ) -> List<'a> {
+        let window = self.window(height);
+        let items = self.visible[window.clone()]
+            .iter()
+            .zip(window)
+            .map(|(&index, position)| item(position, &self.items[index]));
+
+        List::new(items)
+            .highlight_style(self.highlight_style)
+            .highlight_symbol(self.highlight_symbol)
+    }
+
+    /// Items among `candidates` matching the query, with the characters to highlight.
+    fn filter(&self, candidates: Vec<usize>) -> (Vec<usize>, Vec<Vec<usize>>) {
+        match self.filter {
+            Some(Filter::Matcher(matcher)) => {
+                let visible = candidates
+                    .into_iter()
+                    .filter(|&index| matcher(&self.items[index], &self.query))
+                    .collect();
+
+                (visible, vec![])
+            }
+            Some(Filter::Fuzzy(text)) if !self.query.is_empty() => {
+                let texts = candidates.iter().map(|&index| text(&self.items[index]));
+
+                fuzzy::rank(&self.query, texts)
+                    .into_iter()
+                    .map(|(position, found)| (candidates[position], found.indices))
+                    .unzip()
+            }
+            _ => (candidates, vec![]),
+        }
+    }
+
+    /// Filters the items with a new query.
+    ///
+    /// A fuzzy filter selects the best match, and only looks among the items matching
+    /// the previous query when the new one extends it.
+    fn with_query(self, query: String) -> Self {
+        let Some(Filter::Fuzzy(_)) = self.filter else {
+            return Self { query, ..self }.refilter(None, false);
+        };
+
+        let narrowed = !self.query.is_empty() && query.starts_with(&self.query);
+        let mut candidates = narrowed.then(|| self.visible.clone());
+
+        // Ties are ranked in the order of the items
+        if let Some(candidates) = candidates.as_mut() {
+            candidates.sort_unstable();
+        }
+
+        let list = Self { query, ..self }.refilter(candidates, false);
+        let selected = (!list.visible.is_empty()).then_some(0);
+
+        Self { selected, ..list }.scroll()
+    }
+
+    /// Applies the filter again, to `candidates` or every item, keeping the selected item
+    /// if it still matches.
+    ///
+    /// Otherwise the item at the same position is selected when `keep_position` is set,
+    /// the first one if not.
+    fn refilter(self, candidates: Option<Vec<usize>>, keep_position: bool) -> Self {
+        let selected_index = self
+            .selected
+            .and_then(|position| self.visible.get(position).copied());
+        let candidates = candidates.unwrap_or_else(|| (0..self.items.len()).collect());
+        let (visible, highlights) = self.filter(candidates);
+
+        let last = visible.len().checked_sub(1);
+        let selected = match (self.selected, selected_index) {
+            // Nothing could be selected while no item matched
+            (None, _) if !keep_position && self.visible.is_empty() => Some(0),
+            (None, _) => None,
+            (Some(position), Some(index)) => visible
+                .iter()
+                .position(|&candidate| candidate == index)
+                .or(Some(if keep_position { position } else { 0 })),
+            (Some(position), None) => Some(position),
+        }
+        .and_then(|position| last.map(|last| position.min(last)));
+
+        Self {
+            visible,
+            highlights,
+            selected,
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Moves the offset so the selected item is shown within the page.
+    fn scroll(self) -> Self {
+        let offset = scroll_offset(
+            self.visible.len(),
+            self.selected,
+            self.offset,
+            self.page_size,
+        );
+        Self { offset, ..self }
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        let Some(last) = self.visible.len().checked_sub(1) else {
+            return match message {
+                Message::Filter(query) => self.with_query(query),
+                _ => self,
+            };
+        };
+
+        let selected = match message {
+            Message::Select(index) => self
+                .visible
+                .iter()
+                .position(|&candidate| candidate == index)
+                .or(self.selected),
+            Message::Filter(query) => return self.with_query(query),
+            message => navigate(
+                &message,
+                self.selected,
+                last,
+                self.page_size,
+                self.wrap_around,
+            ),
+        };
+
+        Self { selected, ..self }.scroll()
+    }
+}
+
+impl<T> Component for SelectList<T>
+where
+    for<'a> &'a T: Into<ListItem<'a>>,
+{
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let list = self.list(area.height, |_, item| item.into());
+        StatefulWidget::render(list, area, buf, &mut self.state(area.height));
+    }
+}
+
+/// Positions of the rows shown in `height` rows of a list of `len` rows,
+/// starting at `offset` unless the selected row would not be shown.
+pub(crate) fn window(
+    len: usize,
+    selected: Option<usize>,
+    offset: usize,
+    height: u16,
+) -> Range<usize> {
+    let height = height as usize;
+    let start = scroll_offset(len, selected.filter(|_| height > 0), offset, height);
+
+    start..(start + height).min(len)
+}
+
+/// First row shown in pages of `page_size` rows of a list of `len` rows,
+/// moved from `offset` only as far as needed to show the selected row.
+pub(crate) fn scroll_offset(
+    len: usize,
+    selected: Option<usize>,
+    offset: usize,
+    page_size: usize,
+) -> usize {
+    let mut offset = offset.min(len.saturating_sub(page_size));
+
+    if let Some(selected) = selected {
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + page_size {
+            offset = selected + 1 - page_size;
+        }
+    }
+
+    offset
+}
+
+/// Position selected after a move through a list whose last row is at `last`,
+/// from `selected` if a row is selected.
+///
+/// [`Message::Select`] and [`Message::Filter`] depend on the rows, so they keep the selection.
+pub(crate) fn navigate(
+    message: &Message,
+    selected: Option<usize>,
+    last: usize,
+    page_size: usize,
+    wrap_around: bool,
+) -> Option<usize> {
+    match (message, selected) {
+        (Message::Next, None) | (Message::First, _) => Some(0),
+        (Message::Next, Some(position)) if position == last && wrap_around => Some(0),
+        (Message::Next, Some(position)) => Some((position + 1).min(last)),
+        (Message::Previous, None) | (Message::Last, _) => Some(last),
+        (Message::Previous, Some(0)) if wrap_around => Some(last),
+        (Message::Previous, Some(position)) => Some(position.saturating_sub(1)),
+        (Message::PageDown, None) => Some(0),
+        (Message::PageDown, Some(position)) => Some((position + page_size).min(last)),
+        (Message::PageUp, None) => Some(last),
+        (Message::PageUp, Some(position)) => Some(position.saturating_sub(page_size)),
+        (Message::SelectNone, _) => None,
+        (Message::Select(_) | Message::Filter(_), selected) => selected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_starts_at_the_offset() {
+        assert_eq!(window(10, None, 0, 4), 0..4);
+        assert_eq!(window(10, None, 3, 4), 3..7);
+        assert_eq!(window(10, Some(4), 3, 4), 3..7);
+    }
+
+    #[test]
+    fn window_follows_the_selection() {
+        assert_eq!(window(10, Some(8), 0, 4), 5..9);
+        assert_eq!(window(10, Some(1), 5, 4), 1..5);
+    }
+
+    #[test]
+    fn window_never_ends_past_the_list() {
+        assert_eq!(window(10, None, 8, 4), 6..10);
+        assert_eq!(window(3, Some(2), 0, 4), 0..3);
+        assert_eq!(window(0, None, 5, 4), 0..0);
+    }
+
+    #[test]
+    fn empty_window_shows_nothing() {
+        assert_eq!(window(10, Some(5), 2, 0), 2..2);
+    }
+
+    #[test]
+    fn scroll_offset_moves_only_as_far_as_needed() {
+        assert_eq!(scroll_offset(20, Some(5), 3, 5), 3);
+        assert_eq!(scroll_offset(20, Some(8), 3, 5), 4);
+        assert_eq!(scroll_offset(20, Some(2), 3, 5), 2);
+        assert_eq!(scroll_offset(20, None, 18, 5), 15);
+    }
+
+    #[test]
+    fn list_window_is_computed_for_the_rendered_height() {
+        let list = SelectList::new(0..20)
+            .with_page_size(10)
+            .apply(Message::Select(9));
+
+        assert_eq!(list.offset(), 0);
+        assert_eq!(list.window(4), 6..10);
+        assert_eq!(list.state(4).selected(), Some(3));
+    }
+
+    const FRUITS: [&str; 4] = ["apple", "banana", "cherry", "avocado"];
+
+    fn sent(list: SelectList<i32>, messages: &[Message]) -> SelectList<i32> {
+        messages
+            .iter()
+            .fold(list, |list, message| list.apply(message.clone()))
+    }
+
+    fn fruits() -> SelectList<&'static str> {
+        SelectList::new(FRUITS).with_filter(|fruit, query| fruit.contains(query))
+    }
+
+    fn filtered(list: SelectList<&'static str>, query: &str) -> SelectList<&'static str> {
+        list.apply(Message::Filter(query.to_string()))
+    }
+
+    fn visible(list: &SelectList<&'static str>) -> Vec<&'static str> {
+        list.visible().map(|(_, fruit)| *fruit).collect()
+    }
+
+    #[test]
+    fn moves_one_item_at_a_time_and_stops_at_the_ends() {
+        let list = SelectList::new(0..3);
+
+        assert_eq!(
+            sent(list.clone(), &[Message::Next]).selected_index(),
+            Some(1)
+        );
+        assert_eq!(
+            sent(list.clone(), &[Message::Next, Message::Next, Message::Next]).selected_index(),
+            Some(2)
+        );
+        assert_eq!(sent(list, &[Message::Previous]).selected_index(), Some(0));
+    }
+
+    #[test]
+    fn wraps_around_when_enabled() {
+        let list = SelectList::new(0..3).with_wrap_around(true);
+
+        let list = sent(list, &[Message::Previous]);
+        assert_eq!(list.selected_index(), Some(2));
+
+        let list = sent(list, &[Message::Next]);
+        assert_eq!(list.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn moves_a_page_at_a_time_and_scrolls() {
+        let list = SelectList::new(0..10).with_page_size(3);
+
+        let list = sent(list, &[Message::PageDown, Message::PageDown]);
+        assert_eq!(list.selected_index(), Some(6));
+        assert_eq!(list.offset(), 4);
+
+        let list = sent(list, &[Message::PageDown, Message::PageDown]);
+        assert_eq!(list.selected_index(), Some(9));
+
+        let list = sent(list, &[Message::PageUp]);
+        assert_eq!(list.selected_index(), Some(6));
+        assert_eq!(list.offset(), 6);
+    }
+
+    #[test]
+    fn jumps_to_the_first_and_last_items() {
+        let list = sent(SelectList::new(0..10).with_page_size(3), &[Message::Last]);
+        assert_eq!(list.selected_index(), Some(9));
+        assert_eq!(list.offset(), 7);
+
+        let list = sent(list, &[Message::First]);
+        assert_eq!(list.selected_index(), Some(0));
+        assert_eq!(list.offset(), 0);
+    }
+
+    #[test]
+    fn moving_without_a_selection_starts_from_an_end() {
+        let list = sent(SelectList::new(0..3), &[Message::SelectNone]);
+        assert_eq!(list.selected(), None);
+
+        assert_eq!(
+            sent(list.clone(), &[Message::Next]).selected_index(),
+            Some(0)
+        );
+        assert_eq!(sent(list, &[Message::Previous]).selected_index(), Some(2));
+    }
+
+    #[test]
+    fn an_empty_list_selects_nothing() {
+        let list = SelectList::new(Vec::<i32>::new());
+        assert_eq!(list.selected(), None);
+
+        for message in [
+            Message::Next,
+            Message::Last,
+            Message::PageDown,
+            Message::Select(0),
+        ] {
+            assert_eq!(sent(list.clone(), &[message]).selected(), None);
+        }
+
+        assert_eq!(list.window(10), 0..0);
+    }
+
+    #[test]
+    fn the_filter_narrows_the_list_and_keeps_the_selected_item() {
+        let list = fruits().apply(Message::Select(3));
+
+        let list = filtered(list, "a");
+        assert_eq!(visible(&list), ["apple", "banana", "avocado"]);
+        assert_eq!(list.selected(), Some(&"avocado"));
+    }
+
+    #[test]
+    fn the_filter_selects_the_first_match_once_the_selected_item_is_hidden() {
+        let list = filtered(fruits().apply(Message::Select(3)), "an");
+
+        assert_eq!(visible(&list), ["banana"]);
+        assert_eq!(list.selected(), Some(&"banana"));
+    }
+
+    #[test]
+    fn a_filter_matching_nothing_selects_nothing_until_it_matches_again() {
+        let list = filtered(fruits(), "kiwi");
+        assert_eq!(list.selected(), None);
+
+        let list = filtered(list, "");
+        assert_eq!(visible(&list), FRUITS);
+        assert_eq!(list.selected(), Some(&"apple"));
+    }
+
+    #[test]
+    fn hidden_items_cannot_be_selected() {
+        let list = filtered(fruits(), "an").apply(Message::Select(0));
+
+        assert_eq!(list.selected(), Some(&"banana"));
+    }
+
+    #[test]
+    fn the_fuzzy_filter_selects_the_best_match() {
+        let list = SelectList::new(["src/main.rs", "README.md", "src/lib.rs"])
+            .with_fuzzy_filter(|path| path)
+            .apply(Message::Filter("lib".to_string()));
+
+        assert_eq!(list.selected(), Some(&"src/lib.rs"));
+        assert_eq!(list.highlights(0), [4, 5, 6]);
+    }
+}
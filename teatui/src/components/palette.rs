@@ -0,0 +1,379 @@
+//! Command palette: a fuzzy searchable list of the actions of the program, opened with a key.
+//!
+//! Once registered with [`crate::options::ProgramOptions::with_command_palette`],
+//! the runtime opens it with `ctrl+p` and gives it every key while it is open.
+//! `enter` closes it and sends the message of the selected command, as if its key was pressed.
+//! The parent only stores the palette in its model, updates it and draws it last:
+//!
+//! ```no_run
+//! # use teatui::component::Component;
+//! # use teatui::components::palette::{self, CommandPalette};
+//! # use teatui::update::Update;
+//! # #[derive(Clone)]
+//! # enum Message { Palette(palette::Message) }
+//! # struct Model { palette: CommandPalette<Message> }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Palette(msg) => model
+//!     .palette
+//!     .update(msg)
+//!     .lift_model(|palette| Model { palette, ..model }),
+//! # }
+//! # }
+//! ```
+//!
+//! [`CommandPalette::from_keymap`] lists the bindings of a keymap, so the palette never
+//! drifts from the keys of the program.
+use super::list::{self, SelectList};
+use super::text_input::{self, TextInput};
+use crate::component::Component;
+use crate::fuzzy;
+use crate::keymap::{self, KeyCombo, Keymap};
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, Clear, ListItem, StatefulWidget, Widget};
+use std::convert::Infallible;
+
+/// Commands shown at once.
+const PAGE_SIZE: usize = 10;
+
+/// An action of the palette.
+#[derive(Debug, Clone)]
+pub struct Command<Msg> {
+    name: String,
+    keys: String,
+    message: Msg,
+}
+
+impl<Msg> Command<Msg> {
+    pub fn new(name: impl Into<String>, message: Msg) -> Self {
+        Self {
+            name: name.into(),
+            keys: String::new(),
+            message,
+        }
+    }
+
+    /// Keys triggering the same message, shown next to the name.
+    pub fn with_keys(self, keys: impl Into<String>) -> Self {
+        Self {
+            keys: keys.into(),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn keys(&self) -> &str {
+        &self.keys
+    }
+
+    pub fn message(&self) -> &Msg {
+        &self.message
+    }
+}
+
+/// Model of a command palette.
+#[derive(Debug, Clone)]
+pub struct CommandPalette<Msg> {
+    open: bool,
+    key: KeyCombo,
+    input: TextInput,
+    commands: SelectList<Command<Msg>>,
+    width: u16,
+    style: Style,
+    matched_style: Style,
+    keys_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Shows the palette with an empty search.
+    Open,
+    Close,
+    Input(text_input::Message),
+    List(list::Message),
+}
+
+impl Message {
+    /// Converts an event while the palette is open: arrows and page keys move the selection,
+    /// `esc` closes the palette and the other keys edit the search.
+    ///
+    /// `enter` is left to the parent, which sends the message of [`CommandPalette::selected`].
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Key(key) = event else {
+            return text_input::Message::from_event(event).map(Self::Input);
+        };
+
+        match key.code {
+            KeyCode::Esc => Some(Self::Close),
+            KeyCode::Enter => None,
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown => {
+                list::Message::from_event(event).map(Self::List)
+            }
+            _ => text_input::Message::from_event(event).map(Self::Input),
+        }
+    }
+}
+
+impl<Msg> CommandPalette<Msg> {
+    pub fn new(commands: impl IntoIterator<Item = Command<Msg>>) -> Self {
+        Self {
+            open: false,
+            key: KeyCombo::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+            input: TextInput::new().with_placeholder("Type a command"),
+            commands: SelectList::new(commands)
+                .with_page_size(PAGE_SIZE)
+                .with_fuzzy_filter(|command: &Command<Msg>| &command.name),
+            width: 60,
+            style: Style::new(),
+            matched_style: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            keys_style: Style::new().fg(Color::DarkGray),
+        }
+    }
+
+    /// Lists the active bindings of a keymap having a description, named after it.
+    ///
+    /// Bindings sharing a description are a single command, showing all their keys.
+    pub fn from_keymap(keymap: &Keymap<Msg>) -> Self
+    where
+        Msg: Clone,
+    {
+        let mut commands: Vec<Command<Msg>> = vec![];
+
+        for binding in keymap.active_bindings() {
+            if binding.description().is_empty() {
+                continue;
+            }
+
+            let keys = binding
+                .keys()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            match commands
+                .iter_mut()
+                .find(|command| command.name == binding.description())
+            {
+                Some(command) => {
+                    command.keys = format!("{}/{keys}", command.keys);
+                }
+                None => commands.push(
+                    Command::new(binding.description(), binding.message().clone()).with_keys(keys),
+                ),
+            }
+        }
+
+        Self::new(commands)
+    }
+
+    /// Key opening the palette, `ctrl+p` by default.
+    pub fn with_key(self, key: KeyCombo) -> Self {
+        Self { key, ..self }
+    }
+
+    /// Adds a command after the ones already listed.
+    pub fn with_command(self, command: Command<Msg>) -> Self
+    where
+        Msg: Clone,
+    {
+        let mut commands = self.commands.items().to_vec();
+        commands.push(command);
+
+        Self {
+            commands: self.commands.with_items(commands),
+            ..self
+        }
+    }
+
+    /// Maximum width of the palette, 60 columns by default.
+    pub fn with_width(self, width: u16) -> Self {
+        Self { width, ..self }
+    }
+
+    pub fn with_style(self, style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the characters of the command names matching the search.
+    pub fn with_matched_style(self, matched_style: impl Into<Style>) -> Self {
+        Self {
+            matched_style: matched_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_keys_style(self, keys_style: impl Into<Style>) -> Self {
+        Self {
+            keys_style: keys_style.into(),
+            ..self
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn query(&self) -> &str {
+        self.input.value()
+    }
+
+    /// Command run when `enter` is pressed.
+    pub fn selected(&self) -> Option<&Command<Msg>> {
+        self.commands.selected()
+    }
+
+    /// Messages the runtime turns an event into, `None` letting the event go through
+    /// the rest of the pipeline.
+    pub(crate) fn route(&self, event: &Event, wrap: &dyn Fn(Message) -> Msg) -> Option<Vec<Msg>>
+    where
+        Msg: Clone,
+    {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Paste(_) if self.open => {
+                return Some(Message::from_event(event).map(wrap).into_iter().collect());
+            }
+            // Nothing below the palette is clicked or scrolled while it is open
+            Event::Mouse(_) if self.open => return Some(vec![]),
+            _ => return None,
+        };
+
+        if !self.open {
+            return (keymap::is_press(key) && KeyCombo::from(*key) == self.key)
+                .then(|| vec![wrap(Message::Open)]);
+        }
+
+        // Every key belongs to the palette while it is open
+        if !keymap::is_press(key) {
+            return Some(vec![]);
+        }
+
+        if key.code == KeyCode::Enter {
+            let command = self.selected().map(|command| command.message.clone());
+            return Some([wrap(Message::Close)].into_iter().chain(command).collect());
+        }
+
+        Some(Message::from_event(event).map(wrap).into_iter().collect())
+    }
+}
+
+impl<Msg> Component for CommandPalette<Msg>
+where
+    Msg: Clone,
+{
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        let palette = match message {
+            Message::Open => Self {
+                open: true,
+                input: self.input.with_value(""),
+                commands: self
+                    .commands
+                    .apply(list::Message::Filter(String::new()))
+                    .apply(list::Message::First),
+                ..self
+            },
+            Message::Close => Self {
+                open: false,
+                ..self
+            },
+            Message::Input(msg) => {
+                let input = self.input.apply(msg);
+                let query = input.value().to_string();
+
+                Self {
+                    input,
+                    commands: self.commands.apply(list::Message::Filter(query)),
+                    ..self
+                }
+            }
+            Message::List(msg) => Self {
+                commands: self.commands.apply(msg),
+                ..self
+            },
+        };
+
+        Update::Next(palette, None)
+    }
+
+    /// Draws the palette near the top of `area`, usually the whole frame, if it is open.
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        if !self.open {
+            return;
+        }
+
+        let rows = self.commands.visible().count().clamp(1, PAGE_SIZE) as u16;
+        let width = self.width.min(area.width);
+        let height = (rows + 3).min(area.height);
+
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [_, area] = Layout::vertical([
+            Constraint::Length(area.height / 5),
+            Constraint::Length(height),
+        ])
+        .areas(area);
+
+        let block = Block::bordered().title(" Commands ").style(self.style);
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+        let [prompt_area, input_area] =
+            Layout::horizontal([Constraint::Length(2), Constraint::Fill(1)]).areas(input_area);
+
+        Span::styled("> ", self.keys_style).render(prompt_area, buf);
+        self.input.view(input_area, buf);
+
+        if self.commands.visible().next().is_none() {
+            Span::styled("No matching command", self.keys_style).render(list_area, buf);
+            return;
+        }
+
+        let width = list_area.width as usize;
+        let list = self.commands.list(list_area.height, |position, command| {
+            let mut line = fuzzy::highlight(
+                &command.name,
+                self.commands.highlights(position),
+                self.style,
+                self.matched_style,
+            );
+
+            // Keys are aligned on the right, when there is room for them
+            let keys = Span::styled(command.keys.as_str(), self.keys_style);
+            let padding = width.saturating_sub(line.width() + keys.width());
+
+            if padding > 0 {
+                line.push_span(" ".repeat(padding));
+                line.push_span(keys);
+            }
+
+            ListItem::new(line)
+        });
+
+        StatefulWidget::render(
+            list,
+            list_area,
+            buf,
+            &mut self.commands.state(list_area.height),
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Progress bar, showing how much of a task is done and when it should be over,
+//! or only that it is running when that is not known.
+//!
+//! The parent sets the progress as the task reports it, usually from the messages
+//! of an effect. Indeterminate bars move on their own with the ticks registered with
+//! [`ProgramOptions::with_ticks`](crate::options::ProgramOptions::with_ticks),
+//! see [`ProgressBar::deadline`].
+//!
+//! ```no_run
+//! # use teatui::component::Component;
+//! # use teatui::components::progress::{self, ProgressBar};
+//! # use teatui::update::Update;
+//! # struct Model { progress: ProgressBar }
+//! # enum Message { Downloaded { done: u64, total: u64 } }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Downloaded { done, total } => model
+//!     .progress
+//!     .update(progress::Message::Set(done as f64 / total as f64))
+//!     .lift_model(|progress| Model { progress, ..model }),
+//! # }
+//! # }
+//! ```
+use super::spinner::following_frame;
+use crate::component::Component;
+use crate::update::Update;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+/// Time an indeterminate bar takes to move by one cell.
+const INTERVAL: Duration = Duration::from_millis(40);
+
+/// Filled part of a cell, in eighths.
+const EIGHTHS: [&str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Model of a progress bar.
+#[derive(Debug, Clone)]
+pub struct ProgressBar {
+    /// Fraction of the task done, `None` while it is not known.
+    ratio: Option<f64>,
+    /// When the progress was first set, to estimate the remaining time.
+    /// Time spent indeterminate before that is not counted.
+    started: Option<Instant>,
+    /// Remaining time estimated at the last progress.
+    eta: Option<Duration>,
+    /// Position of the moving part of an indeterminate bar.
+    frame: usize,
+    /// When the indeterminate bar moves next, `None` while it is not moving.
+    next_frame: Option<Instant>,
+    label: String,
+    filled_style: Style,
+    unfilled_style: Style,
+    label_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Shows that the task is running, without knowing how much of it is done,
+    /// until the progress is set.
+    Indeterminate,
+    /// Sets the fraction of the task done, between `0.0` and `1.0`. NaN is ignored.
+    Set(f64),
+    /// Fills the bar.
+    Finish,
+    /// Empties the bar, forgetting when the task started.
+    Reset,
+    /// Moves an indeterminate bar if it is due.
+    Tick(Instant),
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressBar {
+    /// Creates an empty bar.
+    pub fn new() -> Self {
+        Self {
+            ratio: Some(0.0),
+            started: None,
+            eta: None,
+            frame: 0,
+            next_frame: None,
+            label: String::new(),
+            filled_style: Style::new().fg(Color::Cyan),
+            unfilled_style: Style::new().fg(Color::DarkGray),
+            label_style: Style::new(),
+        }
+    }
+
+    /// Text shown before the bar.
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            ..self
+        }
+    }
+
+    /// Style of the done part of the bar, cyan by default.
+    pub fn with_filled_style(self, filled_style: impl Into<Style>) -> Self {
+        Self {
+            filled_style: filled_style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the rest of the bar, dark gray by default.
+    pub fn with_unfilled_style(self, unfilled_style: impl Into<Style>) -> Self {
+        Self {
+            unfilled_style: unfilled_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_label_style(self, label_style: impl Into<Style>) -> Self {
+        Self {
+            label_style: label_style.into(),
+            ..self
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Fraction of the task done, `None` for an indeterminate bar.
+    pub fn ratio(&self) -> Option<f64> {
+        self.ratio
+    }
+
+    /// Remaining time, estimated from the average pace since the progress was first set.
+    ///
+    /// `None` until some progress is made, once finished, or when too far away to be told.
+    pub fn eta(&self) -> Option<Duration> {
+        self.eta
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.ratio == Some(1.0)
+    }
+
+    /// Instant at which an indeterminate bar wants its next tick, `None` otherwise.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.next_frame
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        match message {
+            Message::Indeterminate => Self {
+                ratio: None,
+                eta: None,
+                next_frame: self.next_frame.or_else(|| Some(Instant::now() + INTERVAL)),
+                ..self
+            },
+            Message::Set(ratio) if ratio.is_nan() => self,
+            Message::Set(ratio) => {
+                let ratio = ratio.clamp(0.0, 1.0);
+                let now = Instant::now();
+                let started = self.started.unwrap_or(now);
+
+                // The rest of the task is expected to go as fast as what is done,
+                // a tiny progress giving an estimate too large to be told
+                let eta = if ratio > 0.0 && ratio < 1.0 {
+                    let elapsed = now.duration_since(started).as_secs_f64();
+                    Duration::try_from_secs_f64(elapsed * (1.0 - ratio) / ratio).ok()
+                } else {
+                    None
+                };
+
+                Self {
+                    ratio: Some(ratio),
+                    started: Some(started),
+                    eta,
+                    next_frame: None,
+                    ..self
+                }
+            }
+            Message::Finish => Self {
+                ratio: Some(1.0),
+                eta: None,
+                next_frame: None,
+                ..self
+            },
+            Message::Reset => Self {
+                ratio: Some(0.0),
+                started: None,
+                eta: None,
+                frame: 0,
+                next_frame: None,
+                ..self
+            },
+            Message::Tick(now) => match self.next_frame {
+                Some(next_frame) if next_frame <= now => Self {
+                    frame: self.frame.wrapping_add(1),
+                    next_frame: Some(following_frame(next_frame, INTERVAL, now)),
+                    ..self
+                },
+                _ => self,
+            },
+        }
+    }
+
+    /// Draws the filled cells between `start` and `end`, in eighths of a cell.
+    fn fill(&self, area: Rect, start: usize, end: usize, buf: &mut Buffer) {
+        for (column, x) in (area.left()..area.right()).enumerate() {
+            let filled = (end.saturating_sub(column * 8)).min(8);
+            let empty = column * 8 + 8 <= start || filled == 0;
+
+            let (symbol, style) = match filled {
+                _ if empty => ("░", self.unfilled_style),
+                8 => ("█", self.filled_style),
+                eighths => (EIGHTHS[eighths], self.filled_style),
+            };
+
+            buf[(x, area.y)].set_symbol(symbol).set_style(style);
+        }
+    }
+}
+
+/// Formats a duration as `1h02m`, `3m05s` or `12s`.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    match seconds {
+        3600.. => format!("{}h{:02}m", seconds / 3600, seconds / 60 % 60),
+        60.. => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!("{seconds}s"),
+    }
+}
+
+impl Component for ProgressBar {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    /// Draws the label, the bar, the percentage and the remaining time on the first row of `area`.
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let mut area = Rect { height: 1, ..area };
+
+        if !self.label.is_empty() {
+            buf.set_stringn(
+                area.x,
+                area.y,
+                &self.label,
+                area.width as usize,
+                self.label_style,
+            );
+
+            let width = (self.label.width() as u16 + 1).min(area.width);
+            area.x += width;
+            area.width -= width;
+        }
+
+        let status = match (self.ratio, self.eta) {
+            (Some(ratio), Some(eta)) => {
+                format!(
+                    " {:>3}% ETA {}",
+                    (ratio * 100.0) as u8,
+                    format_duration(eta)
+                )
+            }
+            (Some(ratio), None) => format!(" {:>3}%", (ratio * 100.0) as u8),
+            (None, _) => String::new(),
+        };
+
+        let status_width = (status.width() as u16).min(area.width);
+        area.width -= status_width;
+        buf.set_stringn(
+            area.right(),
+            area.y,
+            &status,
+            status_width as usize,
+            self.label_style,
+        );
+
+        let cells = area.width as usize;
+
+        match self.ratio {
+            Some(ratio) => {
+                let end = (ratio * (cells * 8) as f64).round() as usize;
+                self.fill(area, 0, end, buf);
+            }
+            None => {
+                // A quarter of the bar bouncing from one side to the other
+                let length = (cells / 4).max(1);
+                let course = cells.saturating_sub(length);
+                let position = match course {
+                    0 => 0,
+                    course => {
+                        let step = self.frame % (course * 2);
+                        step.min(course * 2 - step)
+                    }
+                };
+
+                self.fill(area, position * 8, (position + length) * 8, buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Progress bar set for the first time `elapsed` ago.
+    fn started(elapsed: Duration) -> ProgressBar {
+        ProgressBar {
+            started: Some(Instant::now() - elapsed),
+            ..ProgressBar::new()
+        }
+    }
+
+    #[test]
+    fn estimates_from_the_average_pace() {
+        let eta = started(Duration::from_secs(10))
+            .apply(Message::Set(0.25))
+            .eta()
+            .unwrap();
+
+        assert!(eta >= Duration::from_secs(30) && eta < Duration::from_secs(31));
+    }
+
+    #[test]
+    fn no_estimate_without_progress_or_once_finished() {
+        let bar = started(Duration::from_secs(10));
+
+        assert_eq!(bar.clone().apply(Message::Set(0.0)).eta(), None);
+        assert_eq!(bar.clone().apply(Message::Set(1.0)).eta(), None);
+        assert_eq!(bar.apply(Message::Finish).eta(), None);
+    }
+
+    #[test]
+    fn no_estimate_too_far_away() {
+        let bar = started(Duration::from_secs(10)).apply(Message::Set(f64::MIN_POSITIVE));
+
+        assert_eq!(bar.eta(), None);
+        assert_eq!(bar.ratio(), Some(f64::MIN_POSITIVE));
+    }
+
+    #[test]
+    fn clamps_the_ratio_and_ignores_nan() {
+        let bar = ProgressBar::new().apply(Message::Set(1.5));
+        assert!(bar.is_finished());
+
+        let bar = bar.apply(Message::Set(-1.0)).apply(Message::Set(f64::NAN));
+        assert_eq!(bar.ratio(), Some(0.0));
+    }
+
+    #[test]
+    fn animates_only_while_indeterminate() {
+        let bar = ProgressBar::new();
+        assert_eq!(bar.deadline(), None);
+
+        let bar = bar.apply(Message::Indeterminate);
+        assert_eq!(bar.ratio(), None);
+        assert!(bar.deadline().is_some());
+
+        let bar = bar.apply(Message::Set(0.5));
+        assert_eq!(bar.deadline(), None);
+    }
+}
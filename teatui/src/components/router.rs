@@ -0,0 +1,449 @@
+//! Navigation between the screens of a program, kept in a stack.
+//!
+//! Every screen is a component, usually an enum of the screens of the program implementing
+//! [`Screen`]. The [`Router`] stored in the model shows the screen on top of its stack and
+//! keeps the screens below as they were left, so going back finds them unchanged.
+//! Pushing a screen runs its [`Component::init`] effect, such as loading the data it shows:
+//!
+//! ```no_run
+//! # use ratatui::{buffer::Buffer, layout::Rect};
+//! # use teatui::component::Component;
+//! # use teatui::components::router::{self, Router};
+//! # use teatui::update::Update;
+//! # mod list { pub enum Message { Open(u64) } }
+//! # struct Detail;
+//! # impl Detail { fn new(_id: u64) -> Self { Detail } }
+//! # enum Screen { Detail(Detail) }
+//! # enum ScreenMessage { List(list::Message) }
+//! # struct PageEffect;
+//! # impl Component for Screen {
+//! #     type Message = ScreenMessage;
+//! #     type Effect = PageEffect;
+//! #     fn update(self, _msg: ScreenMessage) -> Update<Self, PageEffect> { Update::Next(self, None) }
+//! #     fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+//! # }
+//! # impl router::Screen for Screen { fn title(&self) -> String { String::new() } }
+//! # struct Model { router: Router<Screen> }
+//! # enum Message { Router(router::Message<Screen>) }
+//! # enum Effect { Screen(router::ScreenEffect<PageEffect>) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+//! # match msg {
+//! Message::Router(router::Message::Screen(ScreenMessage::List(list::Message::Open(id)))) => {
+//!     let detail = Screen::Detail(Detail::new(id));
+//!     update(model, Message::Router(router::Message::push(detail)))
+//! }
+//! Message::Router(msg) => model
+//!     .router
+//!     .update(msg)
+//!     .lift(|router| Model { router, ..model }, Effect::Screen),
+//! # }
+//! # }
+//! ```
+//!
+//! The effects of the screens are tagged with the screen which asked for them, so their
+//! results are given back to that screen even once another one is shown above it, and
+//! dropped once it was popped or replaced:
+//!
+//! ```no_run
+//! # use ratatui::{buffer::Buffer, layout::Rect};
+//! # use teatui::component::Component;
+//! # use teatui::components::router::{self, Router};
+//! # use teatui::update::Update;
+//! # mod list { pub enum Message { Open(u64) } }
+//! # struct Detail;
+//! # impl Detail { fn new(_id: u64) -> Self { Detail } }
+//! # enum Screen { Detail(Detail) }
+//! # enum ScreenMessage { List(list::Message) }
+//! # struct PageEffect;
+//! # impl Component for Screen {
+//! #     type Message = ScreenMessage;
+//! #     type Effect = PageEffect;
+//! #     fn update(self, _msg: ScreenMessage) -> Update<Self, PageEffect> { Update::Next(self, None) }
+//! #     fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+//! # }
+//! # impl router::Screen for Screen { fn title(&self) -> String { String::new() } }
+//! # struct Model { router: Router<Screen> }
+//! # enum Message { Router(router::Message<Screen>) }
+//! # enum Effect { Screen(router::ScreenEffect<PageEffect>) }
+//! # fn run_screen_effect(_effect: PageEffect) -> Option<ScreenMessage> { None }
+//! fn effects(_model: Model, effect: Effect) -> Option<Message> {
+//!     match effect {
+//!         Effect::Screen(effect) => effect.run(run_screen_effect).map(Message::Router),
+//!     }
+//! }
+//! ```
+//!
+//! Once registered with [`ProgramOptions::with_router`](crate::options::ProgramOptions::with_router),
+//! the runtime goes back to the previous screen when `esc` is pressed.
+use crate::component::Component;
+use crate::keymap::KeyCombo;
+use crate::update::Update;
+use crossterm::event::KeyCode;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+/// A component the router can navigate to, its [`Component::init`] effect being run
+/// when it is pushed.
+pub trait Screen: Component {
+    /// Name of the screen, see [`Router::titles`].
+    fn title(&self) -> String;
+}
+
+/// Identifies a screen pushed to a router, see [`ScreenEffect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScreenId(u64);
+
+/// Model of the stack of screens, never empty.
+#[derive(Debug, Clone)]
+pub struct Router<S> {
+    /// From the first screen to the one shown.
+    stack: Vec<S>,
+    /// Identifier of every screen of the stack.
+    ids: Vec<ScreenId>,
+    next_id: u64,
+    back_key: Option<KeyCombo>,
+}
+
+/// Effect of a screen, tagged with the screen which asked for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenEffect<E> {
+    pub screen: ScreenId,
+    pub effect: E,
+}
+
+impl<E> ScreenEffect<E> {
+    /// Runs the effect with `run`, giving back the message it produces to the screen
+    /// which asked for it, see [`Message::ToScreen`].
+    pub fn run<S>(self, run: impl FnOnce(E) -> Option<S::Message>) -> Option<Message<S>>
+    where
+        S: Component<Effect = E>,
+    {
+        let screen = self.screen;
+        run(self.effect).map(|message| Message::ToScreen(screen, message))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message<S: Component> {
+    /// Shows a new screen above the current one, see [`Message::push`].
+    Push(Box<S>),
+    /// Goes back to the previous screen, if any.
+    Pop,
+    /// Shows a new screen instead of the current one, see [`Message::replace`].
+    Replace(Box<S>),
+    /// Goes back to the first screen.
+    PopToRoot,
+    /// Gives a message to the screen shown.
+    Screen(S::Message),
+    /// Gives a message to the given screen, if it is still in the stack.
+    ToScreen(ScreenId, S::Message),
+}
+
+impl<S: Component> Message<S> {
+    pub fn push(screen: S) -> Self {
+        Self::Push(Box::new(screen))
+    }
+
+    pub fn replace(screen: S) -> Self {
+        Self::Replace(Box::new(screen))
+    }
+}
+
+impl<S: Screen> Router<S> {
+    /// Creates a router showing `root`.
+    ///
+    /// The init effect of the root screen is left to the init function of the program.
+    pub fn new(root: S) -> Self {
+        Self {
+            stack: vec![root],
+            ids: vec![ScreenId(0)],
+            next_id: 1,
+            back_key: Some(KeyCombo::from(KeyCode::Esc)),
+        }
+    }
+
+    /// Key going back to the previous screen, `esc` by default.
+    /// `None` leaves going back to the program.
+    pub fn with_back_key(self, back_key: Option<KeyCombo>) -> Self {
+        Self { back_key, ..self }
+    }
+
+    /// Screen shown.
+    pub fn current(&self) -> &S {
+        self.stack
+            .last()
+            .expect("the stack of screens is never empty")
+    }
+
+    /// Screens from the first one to the one shown.
+    pub fn screens(&self) -> &[S] {
+        &self.stack
+    }
+
+    /// Number of screens in the stack.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.stack.len() > 1
+    }
+
+    /// Titles of the screens from the first one to the one shown, for breadcrumbs.
+    pub fn titles(&self) -> impl Iterator<Item = String> {
+        self.stack.iter().map(Screen::title)
+    }
+
+    /// Identifier of the screen shown.
+    pub fn current_id(&self) -> ScreenId {
+        *self
+            .ids
+            .last()
+            .expect("the stack of screens is never empty")
+    }
+
+    /// Shows a screen above the others, returning its init effect.
+    fn push(&mut self, screen: S) -> Option<ScreenEffect<S::Effect>> {
+        let id = ScreenId(self.next_id);
+        self.next_id += 1;
+
+        let effect = screen
+            .init()
+            .map(|effect| ScreenEffect { screen: id, effect });
+        self.stack.push(screen);
+        self.ids.push(id);
+        effect
+    }
+
+    fn pop(&mut self) {
+        self.stack.pop();
+        self.ids.pop();
+    }
+
+    /// Gives a message to the screen at the given position in the stack.
+    fn update_at(
+        mut self,
+        position: usize,
+        message: S::Message,
+    ) -> Update<Self, ScreenEffect<S::Effect>> {
+        let screen = self.stack.remove(position);
+        let id = self.ids[position];
+
+        screen
+            .update(message)
+            .map_model(|screen| {
+                self.stack.insert(position, screen);
+                self
+            })
+            .map_effect(|effect| ScreenEffect { screen: id, effect })
+    }
+
+    /// Whether `key` goes back from the screen shown.
+    pub(crate) fn is_back(&self, key: KeyCombo) -> bool {
+        self.can_go_back() && self.back_key == Some(key)
+    }
+}
+
+impl<S: Screen> Component for Router<S> {
+    type Message = Message<S>;
+    type Effect = ScreenEffect<S::Effect>;
+
+    /// Init effect of the screen shown.
+    fn init(&self) -> Option<Self::Effect> {
+        let effect = self.current().init()?;
+
+        Some(ScreenEffect {
+            screen: self.current_id(),
+            effect,
+        })
+    }
+
+    fn update(mut self, message: Message<S>) -> Update<Self, Self::Effect> {
+        match message {
+            Message::Push(screen) => {
+                let effect = self.push(*screen);
+                Update::Next(self, effect)
+            }
+            Message::Pop => {
+                if self.can_go_back() {
+                    self.pop();
+                }
+
+                Update::Next(self, None)
+            }
+            Message::Replace(screen) => {
+                self.pop();
+                let effect = self.push(*screen);
+                Update::Next(self, effect)
+            }
+            Message::PopToRoot => {
+                self.stack.truncate(1);
+                self.ids.truncate(1);
+                Update::Next(self, None)
+            }
+            Message::Screen(msg) => {
+                let position = self.stack.len() - 1;
+                self.update_at(position, msg)
+            }
+            // Results for a screen which was popped or replaced since then are stale
+            Message::ToScreen(id, msg) => match self.ids.iter().position(|screen| *screen == id) {
+                Some(position) => self.update_at(position, msg),
+                None => Update::Next(self, None),
+            },
+        }
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        self.current().view(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Page {
+        name: &'static str,
+        visits: u32,
+    }
+
+    fn page(name: &'static str) -> Page {
+        Page { name, visits: 0 }
+    }
+
+    impl Component for Page {
+        type Message = u32;
+        type Effect = &'static str;
+
+        fn init(&self) -> Option<&'static str> {
+            Some(self.name)
+        }
+
+        fn update(self, visits: u32) -> Update<Self, &'static str> {
+            let visits = self.visits + visits;
+            Update::Next(Self { visits, ..self }, Some("visited"))
+        }
+
+        fn view(&self, _area: Rect, _buf: &mut Buffer) {}
+    }
+
+    impl Screen for Page {
+        fn title(&self) -> String {
+            self.name.to_string()
+        }
+    }
+
+    type PageEffect = ScreenEffect<&'static str>;
+
+    fn next(update: Update<Router<Page>, PageEffect>) -> (Router<Page>, Option<PageEffect>) {
+        match update {
+            Update::Next(router, effect) => (router, effect),
+            Update::Exit => panic!("the router never exits"),
+        }
+    }
+
+    fn titles(router: &Router<Page>) -> Vec<String> {
+        router.titles().collect()
+    }
+
+    #[test]
+    fn pushing_shows_the_screen_and_runs_its_init_effect() {
+        let (router, effect) =
+            next(Router::new(page("home")).update(Message::push(page("detail"))));
+
+        assert_eq!(titles(&router), ["home", "detail"]);
+        assert_eq!(
+            effect,
+            Some(ScreenEffect {
+                screen: router.current_id(),
+                effect: "detail",
+            })
+        );
+    }
+
+    #[test]
+    fn popping_finds_the_previous_screen_as_it_was_left() {
+        let (router, _) = next(Router::new(page("home")).update(Message::Screen(2)));
+        let (router, _) = next(router.update(Message::push(page("detail"))));
+        let (router, effect) = next(router.update(Message::Pop));
+
+        assert_eq!(
+            router.current(),
+            &Page {
+                name: "home",
+                visits: 2
+            }
+        );
+        assert_eq!(effect, None);
+    }
+
+    #[test]
+    fn the_root_screen_is_never_popped() {
+        let (router, _) = next(Router::new(page("home")).update(Message::Pop));
+
+        assert_eq!(titles(&router), ["home"]);
+        assert!(!router.can_go_back());
+    }
+
+    #[test]
+    fn replacing_swaps_the_screen_shown() {
+        let (router, _) = next(Router::new(page("home")).update(Message::push(page("list"))));
+        let (router, effect) = next(router.update(Message::replace(page("detail"))));
+
+        assert_eq!(titles(&router), ["home", "detail"]);
+        assert_eq!(effect.map(|effect| effect.effect), Some("detail"));
+    }
+
+    #[test]
+    fn popping_to_the_root_drops_every_other_screen() {
+        let (router, _) = next(Router::new(page("home")).update(Message::push(page("list"))));
+        let (router, _) = next(router.update(Message::push(page("detail"))));
+        let (router, _) = next(router.update(Message::PopToRoot));
+
+        assert_eq!(titles(&router), ["home"]);
+    }
+
+    #[test]
+    fn the_back_key_only_goes_back_above_the_root() {
+        let esc = KeyCombo::from(KeyCode::Esc);
+        let router = Router::new(page("home"));
+        assert!(!router.is_back(esc));
+
+        let (router, _) = next(router.update(Message::push(page("detail"))));
+        assert!(router.is_back(esc));
+        assert!(!router.is_back(KeyCombo::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(!router.with_back_key(None).is_back(esc));
+    }
+
+    #[test]
+    fn effects_of_a_screen_are_given_back_to_it_below_others() {
+        let (router, effect) = next(Router::new(page("home")).update(Message::push(page("list"))));
+        let list = effect.unwrap().screen;
+        let (router, _) = next(router.update(Message::push(page("detail"))));
+
+        let (router, effect) = next(router.update(Message::ToScreen(list, 3)));
+
+        assert_eq!(router.screens()[1].visits, 3);
+        assert_eq!(router.current().visits, 0);
+        assert_eq!(effect.map(|effect| effect.screen), Some(list));
+    }
+
+    #[test]
+    fn effects_of_screens_no_longer_shown_are_dropped() {
+        let (router, effect) = next(Router::new(page("home")).update(Message::push(page("list"))));
+        let list = effect.unwrap().screen;
+
+        // A replaced screen is gone even if the new one takes its place in the stack
+        let (router, _) = next(router.update(Message::replace(page("list"))));
+        let (router, effect) = next(router.update(Message::ToScreen(list, 3)));
+        assert_eq!(router.current().visits, 0);
+        assert_eq!(effect, None);
+
+        let (router, _) = next(router.update(Message::Pop));
+        let (router, effect) = next(router.update(Message::ToScreen(list, 3)));
+        assert_eq!(router.current().visits, 0);
+        assert_eq!(effect, None);
+    }
+}
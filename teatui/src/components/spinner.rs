@@ -0,0 +1,280 @@
+//! Animated spinner, showing that something is going on.
+//!
+//! A spinner only moves while it is started, asking for its next frame with
+//! [`Spinner::deadline`]. The parent gives it the ticks registered with
+//! [`ProgramOptions::with_ticks`](crate::options::ProgramOptions::with_ticks):
+//!
+//! ```no_run
+//! # use std::time::Instant;
+//! # use teatui::component::Component;
+//! # use teatui::components::spinner::{self, Spinner};
+//! # use teatui::update::Update;
+//! # struct Model { spinner: Spinner }
+//! # enum Message { Tick(Instant) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Tick(now) => model
+//!     .spinner
+//!     .update(spinner::Message::Tick(now))
+//!     .lift_model(|spinner| Model { spinner, ..model }),
+//! # }
+//! # }
+//! ```
+use crate::component::Component;
+use crate::update::Update;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+/// Frames of a spinner, and how long each of them is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Animation {
+    /// `|/-\`
+    Line,
+    /// Braille dots going round.
+    #[default]
+    Dots,
+    /// A single braille dot going round.
+    MiniDots,
+    /// `◐◓◑◒`
+    Circle,
+    /// `⠁⠂⠄⡀⢀⠠⠐⠈`
+    Bounce,
+    /// `█▓▒░`
+    Pulse,
+    /// A dot moving back and forth between brackets.
+    Points,
+    /// Moon phases.
+    Moon,
+}
+
+impl Animation {
+    pub fn frames(self) -> &'static [&'static str] {
+        match self {
+            Self::Line => &["|", "/", "-", "\\"],
+            Self::Dots => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            Self::MiniDots => &["⠁", "⠈", "⠐", "⠠", "⢀", "⡀", "⠄", "⠂"],
+            Self::Circle => &["◐", "◓", "◑", "◒"],
+            Self::Bounce => &["⠁", "⠂", "⠄", "⡀", "⢀", "⠠", "⠐", "⠈"],
+            Self::Pulse => &["█", "▓", "▒", "░", "▒", "▓"],
+            Self::Points => &["∙∙∙", "●∙∙", "∙●∙", "∙∙●", "∙●∙", "●∙∙"],
+            Self::Moon => &["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"],
+        }
+    }
+
+    /// Time each frame is shown.
+    pub fn interval(self) -> Duration {
+        let millis = match self {
+            Self::Line | Self::Circle => 120,
+            Self::Dots | Self::MiniDots | Self::Bounce => 80,
+            Self::Pulse | Self::Points => 140,
+            Self::Moon => 125,
+        };
+
+        Duration::from_millis(millis)
+    }
+}
+
+/// Model of a spinner.
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    animation: Animation,
+    frame: usize,
+    /// When the next frame is due, `None` while the spinner is stopped.
+    next_frame: Option<Instant>,
+    label: String,
+    style: Style,
+    label_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Start,
+    Stop,
+    /// Shows the next frame if it is due.
+    Tick(Instant),
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new(Animation::default())
+    }
+}
+
+impl Spinner {
+    /// Creates a stopped spinner.
+    pub fn new(animation: Animation) -> Self {
+        Self {
+            animation,
+            frame: 0,
+            next_frame: None,
+            label: String::new(),
+            style: Style::new(),
+            label_style: Style::new(),
+        }
+    }
+
+    /// Text shown after the spinner.
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            ..self
+        }
+    }
+
+    pub fn with_style(self, style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_label_style(self, label_style: impl Into<Style>) -> Self {
+        Self {
+            label_style: label_style.into(),
+            ..self
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn is_spinning(&self) -> bool {
+        self.next_frame.is_some()
+    }
+
+    /// Instant at which the spinner wants its next tick, `None` while it is stopped.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.next_frame
+    }
+
+    /// Frame currently shown.
+    pub fn frame(&self) -> &'static str {
+        let frames = self.animation.frames();
+        frames[self.frame % frames.len()]
+    }
+
+    /// Spinner and label, to be shown within other text.
+    pub fn line(&self) -> Line<'_> {
+        let mut line = Line::from(Span::styled(self.frame(), self.style));
+
+        if !self.label.is_empty() {
+            line.push_span(" ");
+            line.push_span(Span::styled(self.label.as_str(), self.label_style));
+        }
+
+        line
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        let interval = self.animation.interval();
+
+        match message {
+            Message::Start if self.next_frame.is_some() => self,
+            Message::Start => Self {
+                next_frame: Some(Instant::now() + interval),
+                ..self
+            },
+            Message::Stop => Self {
+                next_frame: None,
+                ..self
+            },
+            Message::Tick(now) => match self.next_frame {
+                Some(next_frame) if next_frame <= now => Self {
+                    frame: (self.frame + 1) % self.animation.frames().len(),
+                    next_frame: Some(following_frame(next_frame, interval, now)),
+                    ..self
+                },
+                _ => self,
+            },
+        }
+    }
+}
+
+/// Instant of the frame following the one due at `due`, shown at `now`.
+///
+/// Frames missed while the program was busy are skipped.
+pub(crate) fn following_frame(due: Instant, interval: Duration, now: Instant) -> Instant {
+    Some(due + interval)
+        .filter(|next| *next > now)
+        .unwrap_or(now + interval)
+}
+
+impl Component for Spinner {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        self.line().render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn next_frame_keeps_the_pace() {
+        let due = Instant::now();
+
+        assert_eq!(following_frame(due, INTERVAL, due), due + INTERVAL);
+        assert_eq!(
+            following_frame(due, INTERVAL, due + INTERVAL / 2),
+            due + INTERVAL
+        );
+    }
+
+    #[test]
+    fn missed_frames_are_skipped() {
+        let due = Instant::now();
+        let now = due + INTERVAL * 5;
+
+        assert_eq!(following_frame(due, INTERVAL, now), now + INTERVAL);
+        assert_eq!(
+            following_frame(due, INTERVAL, due + INTERVAL),
+            due + INTERVAL * 2
+        );
+    }
+
+    #[test]
+    fn moves_only_while_started_and_when_due() {
+        let spinner = Spinner::new(Animation::Line);
+        assert_eq!(spinner.deadline(), None);
+
+        let spinner = spinner.apply(Message::Tick(Instant::now() + INTERVAL * 10));
+        assert_eq!(spinner.frame(), "|");
+
+        let spinner = spinner.apply(Message::Start);
+        let due = spinner.deadline().unwrap();
+        assert_eq!(
+            spinner.clone().apply(Message::Tick(due - INTERVAL)).frame(),
+            "|"
+        );
+
+        let spinner = spinner.apply(Message::Tick(due));
+        assert_eq!(spinner.frame(), "/");
+        assert!(spinner.deadline().unwrap() > due);
+
+        let spinner = spinner.apply(Message::Stop);
+        assert!(!spinner.is_spinning());
+    }
+
+    #[test]
+    fn starting_twice_keeps_the_deadline() {
+        let spinner = Spinner::default().apply(Message::Start);
+        let due = spinner.deadline();
+
+        assert_eq!(spinner.apply(Message::Start).deadline(), due);
+    }
+}
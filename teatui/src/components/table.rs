@@ -0,0 +1,583 @@
+//! Table of rows with a selected row, a current column, sortable columns
+//! and horizontal scrolling for tables wider than the screen.
+//!
+//! Rows are any type, every [`Column`] tells how to show a row in a cell
+//! and how to compare two rows to sort them:
+//!
+//! ```no_run
+//! # use teatui::components::table::{Column, Table};
+//! # struct Crate { name: String, downloads: u64 }
+//! # let crates: Vec<Crate> = vec![];
+//! let table = Table::new(crates, [
+//!     Column::new("Name", |krate: &Crate| krate.name.clone()),
+//!     Column::new("Downloads", |krate: &Crate| krate.downloads.to_string())
+//!         .with_compare(|a, b| a.downloads.cmp(&b.downloads)),
+//! ]);
+//! ```
+//!
+//! Rows are selected and scrolled like a [`SelectList`], whose messages the table accepts
+//! with [`Message::Row`], including filtering. Only the rows shown are built when drawing,
+//! and columns are sized when the rows change, so long tables draw as fast as short ones.
+use super::list::{self, SelectList};
+use crate::component::Component;
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Row, StatefulWidget, TableState};
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns are separated by a single space.
+const COLUMN_SPACING: u16 = 1;
+
+/// Columns sized automatically are never wider than this.
+const MAX_AUTO_WIDTH: u16 = 40;
+
+/// A column of a table showing rows of type `T`.
+#[derive(Debug, Clone)]
+pub struct Column<T> {
+    title: String,
+    cell: fn(&T) -> String,
+    compare: Option<fn(&T, &T) -> Ordering>,
+    width: Option<u16>,
+}
+
+impl<T> Column<T> {
+    /// Creates a column, sorted by the text of its cells and sized after its widest cell.
+    pub fn new(title: impl Into<String>, cell: fn(&T) -> String) -> Self {
+        Self {
+            title: title.into(),
+            cell,
+            compare: None,
+            width: None,
+        }
+    }
+
+    /// Compares rows with `compare` rather than by the text of their cells,
+    /// for numbers or dates.
+    pub fn with_compare(self, compare: fn(&T, &T) -> Ordering) -> Self {
+        Self {
+            compare: Some(compare),
+            ..self
+        }
+    }
+
+    /// Fixed width, rather than the width of the widest cell.
+    pub fn with_width(self, width: u16) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        match self.compare {
+            Some(compare) => compare(a, b),
+            None => (self.cell)(a).cmp(&(self.cell)(b)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Model of a table.
+#[derive(Debug, Clone)]
+pub struct Table<T> {
+    rows: SelectList<T>,
+    columns: Vec<Column<T>>,
+    /// Column sorted, resized or scrolled to by the keyboard.
+    column: usize,
+    /// First column shown, following the current column within [`Table::with_width`].
+    column_offset: usize,
+    width: u16,
+    /// Width of the widest cell of every column, measured when the rows change.
+    measured: Vec<u16>,
+    sort: Option<(usize, SortDirection)>,
+    header_style: Style,
+    current_column_style: Style,
+    highlight_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Moves the selected row, or filters the rows.
+    Row(list::Message),
+    NextColumn,
+    PreviousColumn,
+    /// Sorts by the current column, in ascending order first and toggling the order after.
+    Sort,
+    /// Sorts by the given column, as [`Message::Sort`] does.
+    SortBy(usize),
+    /// Widens the current column by one cell.
+    Widen,
+    /// Narrows the current column by one cell.
+    Narrow,
+    /// Sizes the current column after its widest cell again.
+    AutoSize,
+}
+
+impl Message {
+    /// Converts the keys of [`list::Message::from_event`] into row movements,
+    /// `←`/`→` and `h`/`l` into column movements, `s` into [`Message::Sort`],
+    /// `+`, `-` and `=` into resizing.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        if let Event::Key(key) = event
+            && key.kind != KeyEventKind::Release
+        {
+            let message = match key.code {
+                KeyCode::Left | KeyCode::Char('h') => Some(Self::PreviousColumn),
+                KeyCode::Right | KeyCode::Char('l') => Some(Self::NextColumn),
+                KeyCode::Char('s') => Some(Self::Sort),
+                KeyCode::Char('+') => Some(Self::Widen),
+                KeyCode::Char('-') => Some(Self::Narrow),
+                KeyCode::Char('=') => Some(Self::AutoSize),
+                _ => None,
+            };
+
+            if message.is_some() {
+                return message;
+            }
+        }
+
+        list::Message::from_event(event).map(Self::Row)
+    }
+}
+
+impl<T> Table<T> {
+    /// Creates a table, the first row being selected.
+    pub fn new(
+        rows: impl IntoIterator<Item = T>,
+        columns: impl IntoIterator<Item = Column<T>>,
+    ) -> Self {
+        Self {
+            rows: SelectList::new(rows),
+            columns: columns.into_iter().collect(),
+            column: 0,
+            column_offset: 0,
+            width: 80,
+            measured: vec![],
+            sort: None,
+            header_style: Style::new().add_modifier(Modifier::BOLD),
+            current_column_style: Style::new().add_modifier(Modifier::UNDERLINED),
+            highlight_style: Style::new().add_modifier(Modifier::REVERSED),
+        }
+        .measure()
+    }
+
+    /// Replaces the rows, sorting them like the previous ones.
+    pub fn with_rows(self, rows: impl IntoIterator<Item = T>) -> Self {
+        let table = Self {
+            rows: self.rows.with_items(rows),
+            ..self
+        }
+        .measure();
+
+        match table.sort {
+            Some((column, direction)) => table.sort_by(column, direction),
+            None => table,
+        }
+    }
+
+    /// Maps the rows, to set their page size or their filter.
+    pub fn map_rows(self, f: impl FnOnce(SelectList<T>) -> SelectList<T>) -> Self {
+        Self {
+            rows: f(self.rows),
+            ..self
+        }
+        .measure()
+    }
+
+    pub fn with_header_style(self, header_style: impl Into<Style>) -> Self {
+        Self {
+            header_style: header_style.into(),
+            ..self
+        }
+    }
+
+    /// Style patched on the header of the current column, underlined by default.
+    pub fn with_current_column_style(self, current_column_style: impl Into<Style>) -> Self {
+        Self {
+            current_column_style: current_column_style.into(),
+            ..self
+        }
+    }
+
+    /// Width of the area the table is drawn in, 80 by default.
+    ///
+    /// The columns scroll horizontally once the current column goes past that width,
+    /// and scroll back once the current column goes before the first one shown.
+    pub fn with_width(self, width: u16) -> Self {
+        Self { width, ..self }.scroll()
+    }
+
+    /// Style of the selected row, reversed by default.
+    pub fn with_highlight_style(self, highlight_style: impl Into<Style>) -> Self {
+        Self {
+            highlight_style: highlight_style.into(),
+            ..self
+        }
+    }
+
+    pub fn rows(&self) -> &SelectList<T> {
+        &self.rows
+    }
+
+    pub fn columns(&self) -> &[Column<T>] {
+        &self.columns
+    }
+
+    /// Index of the current column.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Column the rows are sorted by, and in which order.
+    pub fn sort(&self) -> Option<(usize, SortDirection)> {
+        self.sort
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        self.rows.selected()
+    }
+
+    /// Width of a column: its fixed width, or the width of its widest cell.
+    pub fn width(&self, column: usize) -> u16 {
+        self.columns[column]
+            .width
+            .unwrap_or_else(|| self.measured[column])
+    }
+
+    /// Measures the cells of the rows matching the filter.
+    fn measure(self) -> Self {
+        let measured = self
+            .columns
+            .iter()
+            .map(|column| {
+                // Room is kept for the sort indicator
+                let title = column.title.width() + 2;
+                let cells = self
+                    .rows
+                    .visible()
+                    .map(|(_, row)| (column.cell)(row).width());
+
+                cells.fold(title, usize::max).min(MAX_AUTO_WIDTH as usize) as u16
+            })
+            .collect();
+
+        Self { measured, ..self }
+    }
+
+    fn widths(&self) -> Vec<u16> {
+        (0..self.columns.len())
+            .map(|column| self.width(column))
+            .collect()
+    }
+
+    /// Moves the first column shown so the current column is within the width.
+    fn scroll(self) -> Self {
+        let column_offset =
+            first_column(&self.widths(), self.column, self.column_offset, self.width);
+
+        Self {
+            column_offset,
+            ..self
+        }
+    }
+
+    /// Columns shown in `width` cells, keeping the current column in view.
+    fn shown_columns(&self, widths: &[u16], width: u16) -> Vec<usize> {
+        let mut start = first_column(widths, self.column, self.column_offset, width);
+        let width = width as usize;
+
+        // Scroll back while the last columns leave room for the ones before them,
+        // in an area wider than the width of the model
+        while start > 0 && spaced_width(&widths[start - 1..]) <= width + COLUMN_SPACING as usize {
+            start -= 1;
+        }
+
+        let mut used = 0;
+        let mut shown = vec![];
+
+        for (column, &column_width) in widths.iter().enumerate().skip(start) {
+            if !shown.is_empty() && used + column_width as usize > width {
+                break;
+            }
+
+            used += column_width as usize + COLUMN_SPACING as usize;
+            shown.push(column);
+        }
+
+        shown
+    }
+
+    /// Builds the ratatui table of the columns fitting in `width` cells and the rows
+    /// fitting in `height` rows below the header, see [`Table::state`] for the state
+    /// to render it with.
+    pub fn table(&self, width: u16, height: u16) -> ratatui::widgets::Table<'_> {
+        let widths = self.widths();
+        let columns = self.shown_columns(&widths, width);
+
+        let header = columns.iter().map(|&index| {
+            let column = &self.columns[index];
+            let indicator = match self.sort {
+                Some((sorted, SortDirection::Ascending)) if sorted == index => " ▲",
+                Some((sorted, SortDirection::Descending)) if sorted == index => " ▼",
+                _ => "",
+            };
+            let style = if index == self.column {
+                self.header_style.patch(self.current_column_style)
+            } else {
+                self.header_style
+            };
+
+            Line::styled(format!("{}{indicator}", column.title), style)
+        });
+
+        let rows = self
+            .rows
+            .shown(height.saturating_sub(1))
+            .map(|(_, row)| Row::new(columns.iter().map(|&index| (self.columns[index].cell)(row))));
+
+        let constraints = columns
+            .iter()
+            .map(|&index| Constraint::Length(widths[index]));
+
+        ratatui::widgets::Table::new(rows, constraints)
+            .header(Row::new(header))
+            .column_spacing(COLUMN_SPACING)
+            .row_highlight_style(self.highlight_style)
+    }
+
+    /// State to render [`Table::table`] with, for the same height.
+    pub fn state(&self, height: u16) -> TableState {
+        let list = self.rows.state(height.saturating_sub(1));
+        TableState::default().with_selected(list.selected())
+    }
+
+    fn sort_by(self, column: usize, direction: SortDirection) -> Self {
+        let sorted = &self.columns[column];
+        let rows = self.rows.sort_by(|a, b| match direction {
+            SortDirection::Ascending => sorted.compare(a, b),
+            SortDirection::Descending => sorted.compare(b, a),
+        });
+
+        Self {
+            rows,
+            sort: Some((column, direction)),
+            ..self
+        }
+    }
+
+    fn resize(mut self, change: impl FnOnce(u16) -> Option<u16>) -> Self {
+        // A table without columns has none to resize
+        if self.column >= self.columns.len() {
+            return self;
+        }
+
+        let width = self.width(self.column);
+        self.columns[self.column].width = change(width).map(|width| width.max(1));
+        self
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        let last = self.columns.len().saturating_sub(1);
+
+        let table = match message {
+            Message::Row(msg @ list::Message::Filter(_)) => Self {
+                rows: self.rows.apply(msg),
+                ..self
+            }
+            .measure(),
+            Message::Row(msg) => Self {
+                rows: self.rows.apply(msg),
+                ..self
+            },
+            Message::NextColumn => Self {
+                column: (self.column + 1).min(last),
+                ..self
+            },
+            Message::PreviousColumn => Self {
+                column: self.column.saturating_sub(1),
+                ..self
+            },
+            Message::Sort => {
+                let column = self.column;
+                self.apply(Message::SortBy(column))
+            }
+            Message::SortBy(column) if column >= self.columns.len() => self,
+            Message::SortBy(column) => {
+                let direction = match self.sort {
+                    Some((sorted, SortDirection::Ascending)) if sorted == column => {
+                        SortDirection::Descending
+                    }
+                    _ => SortDirection::Ascending,
+                };
+
+                self.sort_by(column, direction)
+            }
+            Message::Widen => self.resize(|width| Some(width.saturating_add(1))),
+            Message::Narrow => self.resize(|width| Some(width.saturating_sub(1))),
+            Message::AutoSize => self.resize(|_| None),
+        };
+
+        table.scroll()
+    }
+}
+
+impl<T> Component for Table<T> {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let table = self.table(area.width, area.height);
+        StatefulWidget::render(table, area, buf, &mut self.state(area.height));
+    }
+}
+
+/// First column shown in `width` cells: from `offset`, scrolled so `column` is shown.
+fn first_column(widths: &[u16], column: usize, offset: usize, width: u16) -> usize {
+    let mut start = offset.min(column);
+
+    // Scroll right while the current column is cut
+    while start < column
+        && spaced_width(&widths[start..=column]) > width as usize + COLUMN_SPACING as usize
+    {
+        start += 1;
+    }
+
+    start
+}
+
+/// Width of the columns, each one followed by the spacing, summed without overflowing.
+fn spaced_width(widths: &[u16]) -> usize {
+    widths
+        .iter()
+        .map(|&width| width as usize + COLUMN_SPACING as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table<(&'static str, u32)> {
+        Table::new(
+            [("carol", 9), ("alice", 30), ("bob", 100)],
+            [
+                Column::new("Name", |row: &(&str, u32)| row.0.to_string()),
+                Column::new("Size", |row: &(&str, u32)| row.1.to_string())
+                    .with_compare(|a, b| a.1.cmp(&b.1)),
+            ],
+        )
+    }
+
+    fn names(table: &Table<(&'static str, u32)>) -> Vec<&'static str> {
+        table.rows().items().iter().map(|row| row.0).collect()
+    }
+
+    #[test]
+    fn sorts_ascending_then_toggles() {
+        let table = table().apply(Message::Sort);
+        assert_eq!(names(&table), ["alice", "bob", "carol"]);
+        assert_eq!(table.sort(), Some((0, SortDirection::Ascending)));
+
+        let table = table.apply(Message::Sort);
+        assert_eq!(names(&table), ["carol", "bob", "alice"]);
+        assert_eq!(table.sort(), Some((0, SortDirection::Descending)));
+
+        let table = table.apply(Message::Sort);
+        assert_eq!(table.sort(), Some((0, SortDirection::Ascending)));
+    }
+
+    #[test]
+    fn sorts_with_the_compare_function() {
+        let table = table().apply(Message::SortBy(1));
+        assert_eq!(names(&table), ["carol", "alice", "bob"]);
+
+        // As text, "100" would come before "30" and "9"
+        let table = table.apply(Message::NextColumn).apply(Message::Sort);
+        assert_eq!(names(&table), ["bob", "alice", "carol"]);
+    }
+
+    #[test]
+    fn another_column_sorts_ascending_first() {
+        let table = table().apply(Message::SortBy(0)).apply(Message::SortBy(1));
+
+        assert_eq!(table.sort(), Some((1, SortDirection::Ascending)));
+    }
+
+    #[test]
+    fn sorting_keeps_the_selected_row() {
+        let table = table().apply(Message::Row(list::Message::Last));
+        assert_eq!(table.selected().map(|row| row.0), Some("bob"));
+
+        let table = table.apply(Message::Sort);
+        assert_eq!(table.selected().map(|row| row.0), Some("bob"));
+    }
+
+    #[test]
+    fn new_rows_keep_the_sort() {
+        let table = table()
+            .apply(Message::SortBy(1))
+            .with_rows([("dave", 50), ("erin", 5)]);
+
+        assert_eq!(names(&table), ["erin", "dave"]);
+    }
+
+    #[test]
+    fn sorting_a_missing_column_does_nothing() {
+        let table = table().apply(Message::SortBy(2));
+
+        assert_eq!(table.sort(), None);
+        assert_eq!(names(&table), ["carol", "alice", "bob"]);
+    }
+
+    #[test]
+    fn scrolls_columns_to_show_the_current_one() {
+        let widths = [10, 10, 10, 10];
+
+        assert_eq!(first_column(&widths, 1, 0, 21), 0);
+        assert_eq!(first_column(&widths, 2, 0, 21), 1);
+        assert_eq!(first_column(&widths, 3, 0, 21), 2);
+        assert_eq!(first_column(&widths, 1, 2, 21), 1);
+        assert_eq!(first_column(&widths, 3, 3, 5), 3);
+    }
+
+    #[test]
+    fn sorting_a_table_without_columns_does_nothing() {
+        let table: Table<u8> = Table::new([1, 2], []);
+
+        assert_eq!(table.clone().apply(Message::Sort).sort(), None);
+        assert_eq!(table.apply(Message::SortBy(0)).sort(), None);
+    }
+
+    #[test]
+    fn wide_columns_do_not_overflow() {
+        let widths = [u16::MAX; 4];
+
+        assert_eq!(first_column(&widths, 3, 0, u16::MAX), 3);
+
+        let table = Table::new(
+            [0u8],
+            (0..4).map(|_| Column::new("", |_: &u8| String::new()).with_width(u16::MAX)),
+        )
+        .apply(Message::NextColumn);
+        assert_eq!(table.shown_columns(&table.widths(), u16::MAX), [1]);
+    }
+}
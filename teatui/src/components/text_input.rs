@@ -0,0 +1,742 @@
+//! Single-line text input, for searches, renames or commands.
+//!
+//! The parent converts terminal events with [`Message::from_event`] while the input is focused,
+//! and embeds its update with [`Update::lift_model`]:
+//!
+//! ```no_run
+//! # use crossterm::event::Event;
+//! # use teatui::component::Component;
+//! # use teatui::components::text_input::{self, TextInput};
+//! # use teatui::update::Update;
+//! # struct Model { search: TextInput }
+//! # enum Message { Event(Event) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Event(event) => match text_input::Message::from_event(&event) {
+//!     Some(msg) => model
+//!         .search
+//!         .update(msg)
+//!         .lift_model(|search| Model { search, ..model }),
+//!     None => Update::Next(model, None),
+//! },
+//! # }
+//! # }
+//! ```
+use super::edit;
+use crate::component::Component;
+use crate::update::Update;
+use crate::view;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use std::convert::Infallible;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Checks the value of an input, returning why it is not valid.
+pub type Validator = fn(&str) -> Result<(), String>;
+
+/// Model of a single-line text input.
+///
+/// Positions are byte offsets into the value, always on grapheme boundaries.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    /// Where the selection started, the cursor being its other end.
+    anchor: Option<usize>,
+    /// Text removed by the last kill, inserted back by [`Message::Yank`].
+    killed: String,
+    placeholder: String,
+    mask: Option<char>,
+    max_chars: Option<usize>,
+    validator: Option<Validator>,
+    error: Option<String>,
+    /// First column shown, scrolled so the cursor stays within [`TextInput::with_width`].
+    offset: usize,
+    width: u16,
+    focused: bool,
+    style: Style,
+    placeholder_style: Style,
+    selection_style: Style,
+}
+
+/// Where a movement takes the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    Home,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Types a character, replacing the selection.
+    Insert(char),
+    /// Inserts text, replacing the selection. Line breaks become spaces.
+    Paste(String),
+    /// Moves the cursor, clearing the selection.
+    Move(Motion),
+    /// Moves the cursor, extending the selection.
+    Select(Motion),
+    SelectAll,
+    DeleteBackward,
+    DeleteForward,
+    /// Removes the word before the cursor, so it can be yanked.
+    DeleteWordBackward,
+    /// Removes the word after the cursor, so it can be yanked.
+    DeleteWordForward,
+    /// Removes the text after the cursor, so it can be yanked.
+    KillToEnd,
+    /// Removes the text before the cursor, so it can be yanked.
+    KillToStart,
+    /// Inserts the text removed last.
+    Yank,
+    /// Replaces the whole value, moving the cursor to the end.
+    SetValue(String),
+}
+
+impl Message {
+    /// Converts a terminal event with the usual readline key bindings.
+    ///
+    /// Characters typed with `ctrl` or `alt` held, other than the bindings,
+    /// are ignored so they can be used by the keymap of the program.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Paste(text) => Some(Self::Paste(text.clone())),
+            Event::Key(key) => Self::from_key(key),
+            _ => None,
+        }
+    }
+
+    pub fn from_key(key: &KeyEvent) -> Option<Self> {
+        if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return None;
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        let motion = |motion| {
+            if shift {
+                Self::Select(motion)
+            } else {
+                Self::Move(motion)
+            }
+        };
+
+        let message = match key.code {
+            KeyCode::Char(c) if !ctrl && !alt => Self::Insert(c),
+            KeyCode::Char('a') if ctrl => Self::Move(Motion::Home),
+            KeyCode::Char('e') if ctrl => Self::Move(Motion::End),
+            KeyCode::Char('b') if ctrl => Self::Move(Motion::Left),
+            KeyCode::Char('f') if ctrl => Self::Move(Motion::Right),
+            KeyCode::Char('b') if alt => Self::Move(Motion::WordLeft),
+            KeyCode::Char('f') if alt => Self::Move(Motion::WordRight),
+            KeyCode::Char('h') if ctrl => Self::DeleteBackward,
+            KeyCode::Char('d') if ctrl => Self::DeleteForward,
+            KeyCode::Char('w') if ctrl => Self::DeleteWordBackward,
+            KeyCode::Char('d') if alt => Self::DeleteWordForward,
+            KeyCode::Char('k') if ctrl => Self::KillToEnd,
+            KeyCode::Char('u') if ctrl => Self::KillToStart,
+            KeyCode::Char('y') if ctrl => Self::Yank,
+            KeyCode::Left if ctrl || alt => motion(Motion::WordLeft),
+            KeyCode::Right if ctrl || alt => motion(Motion::WordRight),
+            KeyCode::Left => motion(Motion::Left),
+            KeyCode::Right => motion(Motion::Right),
+            KeyCode::Home => motion(Motion::Home),
+            KeyCode::End => motion(Motion::End),
+            KeyCode::Backspace if ctrl || alt => Self::DeleteWordBackward,
+            KeyCode::Backspace => Self::DeleteBackward,
+            KeyCode::Delete if ctrl || alt => Self::DeleteWordForward,
+            KeyCode::Delete => Self::DeleteForward,
+            _ => return None,
+        };
+
+        Some(message)
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            value: String::new(),
+            cursor: 0,
+            anchor: None,
+            killed: String::new(),
+            placeholder: String::new(),
+            mask: None,
+            max_chars: None,
+            validator: None,
+            error: None,
+            offset: 0,
+            width: 80,
+            focused: true,
+            style: Style::new(),
+            placeholder_style: Style::new().fg(Color::DarkGray),
+            selection_style: Style::new().add_modifier(Modifier::REVERSED),
+        }
+    }
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the initial value, with the cursor at its end.
+    pub fn with_value(self, value: impl Into<String>) -> Self {
+        self.set_value(value.into()).scroll()
+    }
+
+    /// Text shown while the input is empty.
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        Self {
+            placeholder: placeholder.into(),
+            ..self
+        }
+    }
+
+    /// Shows every character as `mask`, for passwords.
+    pub fn with_mask(self, mask: char) -> Self {
+        Self {
+            mask: Some(mask),
+            ..self
+        }
+    }
+
+    /// Width of the area the input is drawn in, 80 by default.
+    ///
+    /// The value scrolls horizontally once the cursor reaches the end of that width,
+    /// and scrolls back once the cursor reaches its start.
+    pub fn with_width(self, width: u16) -> Self {
+        Self { width, ..self }.scroll()
+    }
+
+    /// Maximum number of characters, extra typed or pasted characters are dropped.
+    pub fn with_max_chars(self, max_chars: usize) -> Self {
+        let input = Self {
+            max_chars: Some(max_chars),
+            ..self
+        };
+        let value = input.value.clone();
+        input.set_value(value)
+    }
+
+    /// Checks the value every time it changes, see [`TextInput::error`].
+    pub fn with_validator(self, validator: Validator) -> Self {
+        Self {
+            validator: Some(validator),
+            ..self
+        }
+        .validate()
+    }
+
+    /// Whether the input shows the terminal cursor, `true` by default.
+    ///
+    /// Parents with several inputs should only keep the focused one focused.
+    pub fn with_focused(self, focused: bool) -> Self {
+        Self { focused, ..self }
+    }
+
+    pub fn with_style(self, style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_placeholder_style(self, placeholder_style: impl Into<Style>) -> Self {
+        Self {
+            placeholder_style: placeholder_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_selection_style(self, selection_style: impl Into<Style>) -> Self {
+        Self {
+            selection_style: selection_style.into(),
+            ..self
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Byte offset of the cursor in the value.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Byte range of the selected text, if any.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        let range = anchor.min(self.cursor)..anchor.max(self.cursor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.value[range])
+    }
+
+    /// Reason the value is not valid, as returned by the validator.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_value(mut self, value: String) -> Self {
+        let value = value.replace(['\r', '\n'], " ");
+        self.value = match self.max_chars {
+            Some(max) => value.chars().take(max).collect(),
+            None => value,
+        };
+        self.cursor = self.value.len();
+        self.anchor = None;
+        self.validate()
+    }
+
+    /// Column of the cursor, as if the area had no end.
+    fn column(&self) -> usize {
+        let before = &self.value[..self.cursor];
+
+        match self.mask {
+            Some(mask) => before.graphemes(true).count() * mask.to_string().width(),
+            None => before.graphemes(true).map(UnicodeWidthStr::width).sum(),
+        }
+    }
+
+    /// Moves the first column shown so the cursor stays within the width.
+    fn scroll(self) -> Self {
+        let column = self.column();
+        let width = self.width.max(1) as usize;
+        let offset = self
+            .offset
+            .min(column)
+            .max((column + 1).saturating_sub(width));
+
+        Self { offset, ..self }
+    }
+
+    fn validate(self) -> Self {
+        let error = self
+            .validator
+            .and_then(|validator| validator(&self.value).err());
+
+        Self { error, ..self }
+    }
+
+    /// Start of the word before `position`.
+    ///
+    /// Masked values are a single word, so their words can't be guessed.
+    fn word_start(&self, position: usize) -> usize {
+        match self.mask {
+            Some(_) => 0,
+            None => edit::word_start(&self.value, position),
+        }
+    }
+
+    /// End of the word after `position`.
+    fn word_end(&self, position: usize) -> usize {
+        match self.mask {
+            Some(_) => self.value.len(),
+            None => edit::word_end(&self.value, position),
+        }
+    }
+
+    fn target(&self, motion: Motion) -> usize {
+        match motion {
+            Motion::Left => edit::previous_boundary(&self.value, self.cursor),
+            Motion::Right => edit::next_boundary(&self.value, self.cursor),
+            Motion::WordLeft => self.word_start(self.cursor),
+            Motion::WordRight => self.word_end(self.cursor),
+            Motion::Home => 0,
+            Motion::End => self.value.len(),
+        }
+    }
+
+    /// Removes a range of the value, leaving the cursor at its start.
+    fn remove(mut self, range: Range<usize>) -> (Self, String) {
+        let removed = self.value.drain(range.clone()).collect();
+        self.cursor = range.start;
+        self.anchor = None;
+        (self.validate(), removed)
+    }
+
+    /// Removes the selection, if any.
+    fn remove_selection(self) -> Self {
+        match self.selection() {
+            Some(range) => self.remove(range).0,
+            None => self,
+        }
+    }
+
+    /// Removes a range of the value, or the selection if there is one.
+    fn delete(self, range: Range<usize>) -> Self {
+        match self.selection() {
+            Some(_) => self.remove_selection(),
+            None => self.remove(range).0,
+        }
+    }
+
+    /// Removes a range of the value, or the selection if there is one, so it can be yanked.
+    fn kill(self, range: Range<usize>) -> Self {
+        let range = self.selection().unwrap_or(range);
+
+        if range.is_empty() {
+            return self;
+        }
+
+        let (input, killed) = self.remove(range);
+        Self { killed, ..input }
+    }
+
+    fn insert(self, text: &str) -> Self {
+        let mut input = self.remove_selection();
+        let text = text.replace(['\r', '\n'], " ");
+
+        let text = match input.max_chars {
+            Some(max) => {
+                let available = max.saturating_sub(input.value.chars().count());
+                text.chars().take(available).collect()
+            }
+            None => text,
+        };
+
+        input.value.insert_str(input.cursor, &text);
+        input.cursor += text.len();
+        input.validate()
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        let input = match message {
+            Message::Insert(c) => self.insert(c.encode_utf8(&mut [0; 4])),
+            Message::Paste(text) => self.insert(&text),
+            Message::Move(motion) => {
+                // Moving sideways collapses the selection to the side of the motion
+                let cursor = match (self.selection(), motion) {
+                    (Some(range), Motion::Left) => range.start,
+                    (Some(range), Motion::Right) => range.end,
+                    _ => self.target(motion),
+                };
+
+                Self {
+                    cursor,
+                    anchor: None,
+                    ..self
+                }
+            }
+            Message::Select(motion) => Self {
+                cursor: self.target(motion),
+                anchor: self.anchor.or(Some(self.cursor)),
+                ..self
+            },
+            Message::SelectAll => Self {
+                cursor: self.value.len(),
+                anchor: Some(0),
+                ..self
+            },
+            Message::DeleteBackward => {
+                let range = edit::previous_boundary(&self.value, self.cursor)..self.cursor;
+                self.delete(range)
+            }
+            Message::DeleteForward => {
+                let range = self.cursor..edit::next_boundary(&self.value, self.cursor);
+                self.delete(range)
+            }
+            Message::DeleteWordBackward => {
+                let range = self.word_start(self.cursor)..self.cursor;
+                self.kill(range)
+            }
+            Message::DeleteWordForward => {
+                let range = self.cursor..self.word_end(self.cursor);
+                self.kill(range)
+            }
+            Message::KillToEnd => {
+                let range = self.cursor..self.value.len();
+                self.kill(range)
+            }
+            Message::KillToStart => {
+                let range = 0..self.cursor;
+                self.kill(range)
+            }
+            Message::Yank => {
+                let killed = self.killed.clone();
+                self.insert(&killed)
+            }
+            Message::SetValue(value) => self.set_value(value),
+        };
+
+        input.scroll()
+    }
+}
+
+impl Component for TextInput {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let area = Rect { height: 1, ..area };
+        buf.set_style(area, self.style);
+
+        if self.value.is_empty() {
+            buf.set_stringn(
+                area.x,
+                area.y,
+                &self.placeholder,
+                area.width as usize,
+                self.style.patch(self.placeholder_style),
+            );
+
+            if self.focused {
+                view::set_cursor(area.as_position());
+            }
+
+            return;
+        }
+
+        let mut encoded = [0; 4];
+        let mask = self.mask.map(|mask| &*mask.encode_utf8(&mut encoded));
+        let selection = self.selection().unwrap_or_default();
+
+        // Column of every grapheme, and of the cursor, as if the area had no end
+        let mut column = 0;
+        let mut cursor_column = 0;
+        let mut graphemes = vec![];
+
+        for (index, grapheme) in self.value.grapheme_indices(true) {
+            if index == self.cursor {
+                cursor_column = column;
+            }
+
+            let shown = mask.unwrap_or(grapheme);
+            graphemes.push((index, column, shown));
+            column += shown.width();
+        }
+
+        if self.cursor == self.value.len() {
+            cursor_column = column;
+        }
+
+        // Scroll back to fill an area wider than the width of the model,
+        // and further if the area is narrower, so the cursor is always visible
+        let width = area.width as usize;
+        let offset = self
+            .offset
+            .min((column + 1).saturating_sub(width))
+            .max((cursor_column + 1).saturating_sub(width));
+
+        for (index, column, shown) in graphemes {
+            if column < offset || column + shown.width() > offset + width {
+                continue;
+            }
+
+            let style = if selection.contains(&index) {
+                self.style.patch(self.selection_style)
+            } else {
+                self.style
+            };
+
+            buf.set_string(area.x + (column - offset) as u16, area.y, shown, style);
+        }
+
+        if self.focused {
+            view::set_cursor(Position::new(
+                area.x + (cursor_column - offset) as u16,
+                area.y,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(input: TextInput, text: &str) -> TextInput {
+        text.chars()
+            .fold(input, |input, c| input.apply(Message::Insert(c)))
+    }
+
+    fn moved(input: TextInput, motion: Motion, times: usize) -> TextInput {
+        (0..times).fold(input, |input, _| input.apply(Message::Move(motion)))
+    }
+
+    #[test]
+    fn killed_text_is_yanked_back() {
+        let input = TextInput::new()
+            .with_value("hello world")
+            .apply(Message::Move(Motion::WordLeft))
+            .apply(Message::KillToEnd);
+        assert_eq!(input.value(), "hello ");
+
+        let input = input
+            .apply(Message::Move(Motion::Home))
+            .apply(Message::Yank);
+        assert_eq!(input.value(), "worldhello ");
+        assert_eq!(input.cursor(), 5);
+    }
+
+    #[test]
+    fn kills_before_the_cursor_and_whole_words() {
+        let input = TextInput::new()
+            .with_value("one two")
+            .apply(Message::DeleteWordBackward);
+        assert_eq!(input.value(), "one ");
+
+        let input = input.apply(Message::KillToStart);
+        assert_eq!(input.value(), "");
+
+        // The last kill replaces the previous one
+        assert_eq!(input.apply(Message::Yank).value(), "one ");
+    }
+
+    #[test]
+    fn killing_nothing_keeps_the_killed_text() {
+        let input = TextInput::new()
+            .with_value("one two")
+            .apply(Message::DeleteWordBackward)
+            .apply(Message::KillToEnd)
+            .apply(Message::Yank);
+
+        assert_eq!(input.value(), "one two");
+    }
+
+    #[test]
+    fn kills_the_selection() {
+        let input = TextInput::new()
+            .with_value("one two")
+            .apply(Message::Select(Motion::WordLeft))
+            .apply(Message::KillToStart);
+        assert_eq!(input.value(), "one ");
+
+        let input = input.apply(Message::Yank).apply(Message::Yank);
+        assert_eq!(input.value(), "one twotwo");
+    }
+
+    #[test]
+    fn drops_characters_beyond_the_maximum() {
+        let input = typed(TextInput::new().with_max_chars(5), "abcdefg");
+        assert_eq!(input.value(), "abcde");
+
+        let input = TextInput::new()
+            .with_max_chars(5)
+            .with_value("abc")
+            .apply(Message::Move(Motion::Home))
+            .apply(Message::Paste("xyz".into()));
+        assert_eq!(input.value(), "xyabc");
+
+        let input = TextInput::new().with_value("héllo world").with_max_chars(5);
+        assert_eq!(input.value(), "héllo");
+    }
+
+    #[test]
+    fn jumps_over_words_and_the_separators_before_them() {
+        let input = TextInput::new().with_value("one two  three");
+
+        let input = input.apply(Message::Move(Motion::WordLeft));
+        assert_eq!(input.cursor(), 9);
+
+        let input = input.apply(Message::Move(Motion::WordLeft));
+        assert_eq!(input.cursor(), 4);
+
+        let input = input.apply(Message::Move(Motion::WordRight));
+        assert_eq!(input.cursor(), 7);
+    }
+
+    #[test]
+    fn masked_values_are_a_single_word() {
+        let input = TextInput::new()
+            .with_mask('•')
+            .with_value("one two")
+            .apply(Message::Move(Motion::WordLeft));
+        assert_eq!(input.cursor(), 0);
+
+        let input = input.apply(Message::DeleteWordForward);
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn masked_values_scroll_by_the_width_of_the_mask() {
+        // Wide characters take one column each once masked
+        let input = TextInput::new()
+            .with_mask('*')
+            .with_width(10)
+            .with_value("日本語の文章です");
+        assert_eq!(input.column(), 8);
+        assert_eq!(input.offset, 0);
+
+        let input = TextInput::new()
+            .with_width(10)
+            .with_value("日本語の文章です");
+        assert_eq!(input.column(), 16);
+        assert_eq!(input.offset, 7);
+    }
+
+    #[test]
+    fn validates_every_change() {
+        let input = TextInput::new().with_validator(|value| {
+            if value.len() < 3 {
+                Err("too short".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(input.error(), Some("too short"));
+
+        let input = typed(input, "abc");
+        assert!(input.is_valid());
+
+        let input = input.apply(Message::DeleteBackward);
+        assert_eq!(input.error(), Some("too short"));
+    }
+
+    #[test]
+    fn scrolls_only_when_the_cursor_leaves_the_width() {
+        let input = TextInput::new()
+            .with_width(10)
+            .with_value("0123456789abcdef");
+        assert_eq!(input.offset, 7);
+
+        let input = moved(input, Motion::Left, 3);
+        assert_eq!(input.offset, 7);
+
+        let input = moved(input, Motion::Left, 7);
+        assert_eq!(input.offset, 6);
+
+        let input = input.apply(Message::Move(Motion::Home));
+        assert_eq!(input.offset, 0);
+
+        let input = moved(input, Motion::Right, 9);
+        assert_eq!(input.offset, 0);
+    }
+
+    #[test]
+    fn line_breaks_become_spaces() {
+        let input = TextInput::new().apply(Message::Paste("one\r\ntwo\nthree".into()));
+
+        assert_eq!(input.value(), "one  two three");
+    }
+}
@@ -0,0 +1,1073 @@
+//! Multi-line text editor, for commit messages, notes or descriptions.
+//!
+//! It is embedded like a [`crate::components::text_input::TextInput`], converting events
+//! with [`Message::from_event`] while it is focused:
+//!
+//! ```no_run
+//! # use crossterm::event::Event;
+//! # use teatui::component::Component;
+//! # use teatui::components::textarea::{self, TextArea};
+//! # use teatui::update::Update;
+//! # struct Model { notes: TextArea }
+//! # enum Message { Event(Event) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Event(event) => match textarea::Message::from_event(&event) {
+//!     Some(msg) => model
+//!         .notes
+//!         .update(msg)
+//!         .lift_model(|notes| Model { notes, ..model }),
+//!     None => Update::Next(model, None),
+//! },
+//! # }
+//! # }
+//! ```
+//!
+//! Long lines are wrapped at word boundaries by default, and the text scrolls vertically
+//! to keep the cursor within [`TextArea::with_size`]. [`TextArea::height`] tells how many rows the text needs,
+//! so the parent can grow the editor with its content up to [`TextArea::with_max_height`].
+use super::edit;
+use crate::component::Component;
+use crate::update::Update;
+use crate::view;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use std::convert::Infallible;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Number of edits that can be undone.
+const HISTORY: usize = 100;
+
+/// Tabs are expanded when inserted, as their width depends on the terminal.
+const TAB: &str = "    ";
+
+/// Model of a multi-line text editor.
+///
+/// The cursor is a line index and a byte offset into that line, always on a grapheme boundary.
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    cursor: (usize, usize),
+    /// Column the cursor goes back to when moving up and down through shorter lines.
+    goal: Option<usize>,
+    /// First line shown, scrolled so the cursor stays within [`TextArea::with_size`].
+    top: usize,
+    /// First column shown of lines that are not wrapped.
+    left: usize,
+    width: u16,
+    height: u16,
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+    /// Kind of the last edit, consecutive edits of the same kind are undone at once.
+    last_edit: Option<Edit>,
+    placeholder: String,
+    wrap: bool,
+    line_numbers: bool,
+    max_height: Option<u16>,
+    focused: bool,
+    style: Style,
+    placeholder_style: Style,
+    line_number_style: Style,
+}
+
+/// Edit of the text, replacing `removed` at `at` with `inserted`.
+#[derive(Debug, Clone)]
+struct Change {
+    at: (usize, usize),
+    removed: String,
+    inserted: String,
+    /// Cursor before the edit, where undoing it puts the cursor back.
+    before: (usize, usize),
+    /// Cursor after the edit, where redoing it puts the cursor back.
+    after: (usize, usize),
+}
+
+impl Change {
+    /// Extends this change with the one following it, if it continues it.
+    ///
+    /// Typing goes on after the inserted text, erasing goes on before or after the removed text.
+    fn merge(&mut self, next: &Change) -> bool {
+        if self.removed.is_empty() && next.removed.is_empty() {
+            if next.at != end_of(self.at, &self.inserted) {
+                return false;
+            }
+
+            self.inserted.push_str(&next.inserted);
+        } else if self.inserted.is_empty() && next.inserted.is_empty() {
+            if next.at == self.at {
+                self.removed.push_str(&next.removed);
+            } else if end_of(next.at, &next.removed) == self.at {
+                self.removed.insert_str(0, &next.removed);
+                self.at = next.at;
+            } else {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        self.after = next.after;
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Type,
+    Erase,
+    Other,
+}
+
+/// Where a movement takes the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordLeft,
+    WordRight,
+    /// Start of the line.
+    Home,
+    /// End of the line.
+    End,
+    /// Start of the text.
+    Top,
+    /// End of the text.
+    Bottom,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Insert(char),
+    /// Inserts text, which may span several lines.
+    Paste(String),
+    /// Splits the line at the cursor.
+    Newline,
+    Move(Motion),
+    /// Removes the grapheme before the cursor, joining the line with the previous one
+    /// at its start.
+    DeleteBackward,
+    /// Removes the grapheme after the cursor, joining the line with the next one at its end.
+    DeleteForward,
+    DeleteWordBackward,
+    DeleteWordForward,
+    /// Removes the text after the cursor on its line.
+    DeleteToLineEnd,
+    Undo,
+    Redo,
+    /// Replaces the whole text, moving the cursor to the end. It can be undone.
+    SetValue(String),
+}
+
+impl Message {
+    /// Converts a terminal event with the usual editor key bindings,
+    /// `ctrl+z` and `ctrl+y` undoing and redoing.
+    ///
+    /// Characters typed with `ctrl` or `alt` held, other than the bindings,
+    /// are ignored so they can be used by the keymap of the program.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Paste(text) => Some(Self::Paste(text.clone())),
+            Event::Key(key) => Self::from_key(key),
+            _ => None,
+        }
+    }
+
+    pub fn from_key(key: &KeyEvent) -> Option<Self> {
+        if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return None;
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        let message = match key.code {
+            KeyCode::Char(c) if !ctrl && !alt => Self::Insert(c),
+            KeyCode::Char('a') if ctrl => Self::Move(Motion::Home),
+            KeyCode::Char('e') if ctrl => Self::Move(Motion::End),
+            KeyCode::Char('h') if ctrl => Self::DeleteBackward,
+            KeyCode::Char('d') if ctrl => Self::DeleteForward,
+            KeyCode::Char('w') if ctrl => Self::DeleteWordBackward,
+            KeyCode::Char('d') if alt => Self::DeleteWordForward,
+            KeyCode::Char('k') if ctrl => Self::DeleteToLineEnd,
+            KeyCode::Char('z') if ctrl => Self::Undo,
+            KeyCode::Char('y') if ctrl => Self::Redo,
+            KeyCode::Enter => Self::Newline,
+            KeyCode::Left if ctrl || alt => Self::Move(Motion::WordLeft),
+            KeyCode::Right if ctrl || alt => Self::Move(Motion::WordRight),
+            KeyCode::Left => Self::Move(Motion::Left),
+            KeyCode::Right => Self::Move(Motion::Right),
+            KeyCode::Up => Self::Move(Motion::Up),
+            KeyCode::Down => Self::Move(Motion::Down),
+            KeyCode::Home if ctrl => Self::Move(Motion::Top),
+            KeyCode::End if ctrl => Self::Move(Motion::Bottom),
+            KeyCode::Home => Self::Move(Motion::Home),
+            KeyCode::End => Self::Move(Motion::End),
+            KeyCode::Backspace if ctrl || alt => Self::DeleteWordBackward,
+            KeyCode::Backspace => Self::DeleteBackward,
+            KeyCode::Delete if ctrl || alt => Self::DeleteWordForward,
+            KeyCode::Delete => Self::DeleteForward,
+            _ => return None,
+        };
+
+        Some(message)
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            cursor: (0, 0),
+            goal: None,
+            top: 0,
+            left: 0,
+            width: 80,
+            height: 24,
+            undo: vec![],
+            redo: vec![],
+            last_edit: None,
+            placeholder: String::new(),
+            wrap: true,
+            line_numbers: false,
+            max_height: None,
+            focused: true,
+            style: Style::new(),
+            placeholder_style: Style::new().fg(Color::DarkGray),
+            line_number_style: Style::new().fg(Color::DarkGray),
+        }
+    }
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the initial text, with the cursor at its end. It can't be undone.
+    pub fn with_value(self, value: impl AsRef<str>) -> Self {
+        let mut textarea = self.set_value(value.as_ref());
+        textarea.undo.clear();
+        textarea.scroll()
+    }
+
+    /// Text shown while the editor is empty.
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        Self {
+            placeholder: placeholder.into(),
+            ..self
+        }
+    }
+
+    /// Whether long lines are wrapped, `true` by default.
+    ///
+    /// Lines that are not wrapped scroll horizontally with the cursor.
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        Self { wrap, ..self }.scroll()
+    }
+
+    /// Whether line numbers are shown on the left, `false` by default.
+    pub fn with_line_numbers(self, line_numbers: bool) -> Self {
+        Self {
+            line_numbers,
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Size of the area the editor is drawn in, 80 columns by 24 rows by default,
+    /// line numbers included.
+    ///
+    /// The text scrolls once the cursor reaches the edges of that area.
+    pub fn with_size(self, width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Maximum number of rows taken by the editor, the text scrolling beyond them.
+    pub fn with_max_height(self, max_height: u16) -> Self {
+        Self {
+            max_height: Some(max_height),
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Whether the editor shows the terminal cursor, `true` by default.
+    pub fn with_focused(self, focused: bool) -> Self {
+        Self { focused, ..self }
+    }
+
+    pub fn with_style(self, style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_placeholder_style(self, placeholder_style: impl Into<Style>) -> Self {
+        Self {
+            placeholder_style: placeholder_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_line_number_style(self, line_number_style: impl Into<Style>) -> Self {
+        Self {
+            line_number_style: line_number_style.into(),
+            ..self
+        }
+    }
+
+    /// The whole text, lines being separated by `\n`.
+    pub fn value(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Lines of the text, there is always at least one.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    /// Line of the cursor, and its byte offset in that line.
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Whether long lines are wrapped, see [`TextArea::with_wrap`].
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    /// Rows needed to show the whole text in `width` columns, at most the maximum height.
+    ///
+    /// Parents use it to grow the editor with its content.
+    pub fn height(&self, width: u16) -> u16 {
+        let width = width.saturating_sub(self.gutter_width()) as usize;
+
+        let rows: usize = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| self.rows(line, width, self.cursor_in(index)).len())
+            .sum();
+
+        let rows = u16::try_from(rows).unwrap_or(u16::MAX);
+        self.max_height.map_or(rows, |max| rows.min(max))
+    }
+
+    fn line(&self) -> &str {
+        &self.lines[self.cursor.0]
+    }
+
+    fn cursor_in(&self, line: usize) -> Option<usize> {
+        (self.cursor.0 == line).then_some(self.cursor.1)
+    }
+
+    /// Rows taken by a line in `width` columns, see [`TextArea::rows`].
+    fn line_rows(&self, line: usize, width: usize) -> Vec<Range<usize>> {
+        self.rows(&self.lines[line], width, self.cursor_in(line))
+    }
+
+    /// Index of the row of the cursor among the rows of its line.
+    fn cursor_row(&self, rows: &[Range<usize>]) -> usize {
+        rows.iter()
+            .rposition(|row| row.start <= self.cursor.1)
+            .unwrap_or_default()
+    }
+
+    fn set_value(self, value: &str) -> Self {
+        let last = self.lines.len() - 1;
+        let end = (last, self.lines[last].len());
+        self.edit(Edit::Other, (0, 0), end, value)
+    }
+
+    /// Text between two positions.
+    fn text(&self, from: (usize, usize), to: (usize, usize)) -> String {
+        if from.0 == to.0 {
+            return self.lines[from.0][from.1..to.1].to_string();
+        }
+
+        let mut text = self.lines[from.0][from.1..].to_string();
+
+        for line in &self.lines[from.0 + 1..to.0] {
+            text.push('\n');
+            text.push_str(line);
+        }
+
+        text.push('\n');
+        text.push_str(&self.lines[to.0][..to.1]);
+        text
+    }
+
+    /// Replaces the text between two positions, saving the change so it can be undone
+    /// unless it continues the last one. Edits leaving the text as it was are not saved.
+    fn edit(self, edit: Edit, from: (usize, usize), to: (usize, usize), text: &str) -> Self {
+        let inserted = normalize(text);
+        let removed = self.text(from, to);
+
+        if removed == inserted {
+            return self;
+        }
+
+        let before = self.cursor;
+        let textarea = Self {
+            cursor: from,
+            ..self.remove(from, to)
+        };
+        let mut textarea = textarea.insert(&inserted);
+
+        let change = Change {
+            at: from,
+            removed,
+            inserted,
+            before,
+            after: textarea.cursor,
+        };
+
+        let merged = edit != Edit::Other
+            && textarea.last_edit == Some(edit)
+            && textarea
+                .undo
+                .last_mut()
+                .is_some_and(|last| last.merge(&change));
+
+        if !merged {
+            if textarea.undo.len() == HISTORY {
+                textarea.undo.remove(0);
+            }
+
+            textarea.undo.push(change);
+        }
+
+        textarea.redo.clear();
+        textarea.last_edit = Some(edit);
+        textarea
+    }
+
+    /// Replaces the text of a change, undoing it when `undo` is set and redoing it otherwise.
+    fn revert(self, change: &Change, undo: bool) -> Self {
+        let (old, new, cursor) = if undo {
+            (&change.inserted, &change.removed, change.before)
+        } else {
+            (&change.removed, &change.inserted, change.after)
+        };
+
+        let textarea = Self {
+            cursor: change.at,
+            ..self.remove(change.at, end_of(change.at, old))
+        };
+
+        Self {
+            cursor,
+            last_edit: None,
+            ..textarea.insert(new)
+        }
+    }
+
+    /// Column of the cursor on the screen, counted from the start of its line.
+    fn column(&self) -> usize {
+        self.line()[..self.cursor.1].width()
+    }
+
+    fn target(&self, motion: Motion) -> (usize, usize) {
+        let (row, column) = self.cursor;
+        let line = self.line();
+        let last = self.lines.len() - 1;
+
+        match motion {
+            Motion::Left if column == 0 && row > 0 => (row - 1, self.lines[row - 1].len()),
+            Motion::Left => (row, edit::previous_boundary(line, column)),
+            Motion::Right if column == line.len() && row < last => (row + 1, 0),
+            Motion::Right => (row, edit::next_boundary(line, column)),
+            Motion::Up if row == 0 => (0, 0),
+            Motion::Up => (row - 1, self.at_goal(row - 1)),
+            Motion::Down if row == last => (last, line.len()),
+            Motion::Down => (row + 1, self.at_goal(row + 1)),
+            Motion::WordLeft if column == 0 && row > 0 => (row - 1, self.lines[row - 1].len()),
+            Motion::WordLeft => (row, edit::word_start(line, column)),
+            Motion::WordRight if column == line.len() && row < last => (row + 1, 0),
+            Motion::WordRight => (row, edit::word_end(line, column)),
+            Motion::Home => (row, 0),
+            Motion::End => (row, line.len()),
+            Motion::Top => (0, 0),
+            Motion::Bottom => (last, self.lines[last].len()),
+        }
+    }
+
+    /// Offset in the given line closest to the goal column, without going past it.
+    fn at_goal(&self, row: usize) -> usize {
+        let goal = self.goal.unwrap_or_else(|| self.column());
+        let mut column = 0;
+
+        for (index, grapheme) in self.lines[row].grapheme_indices(true) {
+            column += grapheme.width();
+
+            if column > goal {
+                return index;
+            }
+        }
+
+        self.lines[row].len()
+    }
+
+    /// Moves the first line and column shown so the cursor stays within the size,
+    /// counting the rows of wrapped lines.
+    fn scroll(self) -> Self {
+        let height = self
+            .max_height
+            .map_or(self.height, |max| self.height.min(max))
+            .max(1) as usize;
+        let width = self.width.saturating_sub(self.gutter_width()).max(1) as usize;
+        let line = self.cursor.0;
+
+        let mut top = self.top.min(line);
+        let mut cursor_index = (top..line)
+            .map(|index| self.line_rows(index, width).len())
+            .sum::<usize>()
+            + self.cursor_row(&self.line_rows(line, width));
+
+        while cursor_index >= height && top < line {
+            cursor_index -= self.line_rows(top, width).len();
+            top += 1;
+        }
+
+        let column = self.column();
+        let left = if self.wrap {
+            0
+        } else {
+            self.left
+                .min(column)
+                .max((column + 1).saturating_sub(width))
+        };
+
+        Self { top, left, ..self }
+    }
+
+    fn insert(self, text: &str) -> Self {
+        let mut textarea = self;
+        let (row, column) = textarea.cursor;
+        let text = normalize(text);
+        let mut parts = text.split('\n');
+        let tail = textarea.lines[row].split_off(column);
+
+        // There is always a first part, even for an empty text
+        textarea.lines[row].push_str(parts.next().unwrap_or_default());
+
+        let mut new_lines: Vec<String> = parts.map(String::from).collect();
+        let last_row = row + new_lines.len();
+        let cursor = match new_lines.last_mut() {
+            Some(last) => {
+                let cursor = (last_row, last.len());
+                last.push_str(&tail);
+                cursor
+            }
+            None => {
+                let cursor = (row, textarea.lines[row].len());
+                textarea.lines[row].push_str(&tail);
+                cursor
+            }
+        };
+
+        textarea.lines.splice(row + 1..row + 1, new_lines.drain(..));
+        textarea.cursor = cursor;
+        textarea
+    }
+
+    /// Removes the text between two positions, leaving the cursor at the first one.
+    fn remove(self, from: (usize, usize), to: (usize, usize)) -> Self {
+        if from == to {
+            return self;
+        }
+
+        let mut textarea = self;
+        let tail = textarea.lines[to.0].split_off(to.1);
+        textarea.lines.drain(from.0 + 1..=to.0);
+        textarea.lines[from.0].truncate(from.1);
+        textarea.lines[from.0].push_str(&tail);
+        textarea.cursor = from;
+        textarea
+    }
+
+    fn apply(self, message: Message) -> Self {
+        let cursor = self.cursor;
+        // Moving up and down keeps the column the cursor had before
+        let goal = match message {
+            Message::Move(Motion::Up | Motion::Down) => Some(self.goal.unwrap_or(self.column())),
+            _ => None,
+        };
+
+        let textarea = match message {
+            Message::Insert(c) => {
+                // Every word typed is undone on its own
+                let edit = if c.is_whitespace() {
+                    Edit::Other
+                } else {
+                    Edit::Type
+                };
+
+                self.edit(edit, cursor, cursor, c.encode_utf8(&mut [0; 4]))
+            }
+            Message::Paste(text) => self.edit(Edit::Other, cursor, cursor, &text),
+            Message::Newline => self.edit(Edit::Other, cursor, cursor, "\n"),
+            Message::Move(motion) => Self {
+                cursor: self.target(motion),
+                last_edit: None,
+                ..self
+            },
+            Message::DeleteBackward => {
+                let from = self.target(Motion::Left);
+                self.edit(Edit::Erase, from, cursor, "")
+            }
+            Message::DeleteForward => {
+                let to = self.target(Motion::Right);
+                self.edit(Edit::Erase, cursor, to, "")
+            }
+            Message::DeleteWordBackward => {
+                let from = self.target(Motion::WordLeft);
+                self.edit(Edit::Other, from, cursor, "")
+            }
+            Message::DeleteWordForward => {
+                let to = self.target(Motion::WordRight);
+                self.edit(Edit::Other, cursor, to, "")
+            }
+            Message::DeleteToLineEnd => {
+                let to = (cursor.0, self.line().len());
+                self.edit(Edit::Other, cursor, to, "")
+            }
+            Message::Undo => {
+                let mut textarea = self;
+
+                match textarea.undo.pop() {
+                    Some(change) => {
+                        let mut textarea = textarea.revert(&change, true);
+                        textarea.redo.push(change);
+                        textarea
+                    }
+                    None => textarea,
+                }
+            }
+            Message::Redo => {
+                let mut textarea = self;
+
+                match textarea.redo.pop() {
+                    Some(change) => {
+                        let mut textarea = textarea.revert(&change, false);
+                        textarea.undo.push(change);
+                        textarea
+                    }
+                    None => textarea,
+                }
+            }
+            Message::SetValue(value) => self.set_value(&value),
+        };
+
+        Self { goal, ..textarea }.scroll()
+    }
+
+    fn gutter_width(&self) -> u16 {
+        if self.line_numbers {
+            self.lines.len().to_string().len() as u16 + 1
+        } else {
+            0
+        }
+    }
+
+    /// Byte ranges of the rows a line takes in `width` columns.
+    ///
+    /// A wrapped line whose last row is full gets an empty row for the cursor at its end.
+    fn rows(&self, line: &str, width: usize, cursor: Option<usize>) -> Vec<Range<usize>> {
+        if !self.wrap {
+            return std::iter::once(0..line.len()).collect();
+        }
+
+        let mut rows = vec![];
+        let mut start = 0;
+        let mut column = 0;
+        // End of the last whitespace of the row, and the column after it
+        let mut space = None;
+
+        for (index, grapheme) in line.grapheme_indices(true) {
+            let grapheme_width = grapheme.width();
+
+            if column + grapheme_width > width && index > start {
+                let (end, end_column) = space.unwrap_or((index, column));
+                rows.push(start..end);
+                start = end;
+                column -= end_column;
+                space = None;
+            }
+
+            column += grapheme_width;
+
+            if grapheme.chars().all(char::is_whitespace) {
+                space = Some((index + grapheme.len(), column));
+            }
+        }
+
+        rows.push(start..line.len());
+
+        if column >= width && cursor == Some(line.len()) && !line.is_empty() {
+            rows.push(line.len()..line.len());
+        }
+
+        rows
+    }
+
+    fn render_line_number(&self, number: Option<usize>, x: u16, y: u16, buf: &mut Buffer) {
+        let Some(number) = number else {
+            return;
+        };
+
+        let width = self.gutter_width() as usize - 1;
+        let style = if number == self.cursor.0 + 1 {
+            self.style
+        } else {
+            self.style.patch(self.line_number_style)
+        };
+
+        buf.set_stringn(x, y, format!("{number:>width$}"), width, style);
+    }
+}
+
+/// Position after `text` once inserted at `at`.
+fn end_of(at: (usize, usize), text: &str) -> (usize, usize) {
+    match text.rsplit_once('\n') {
+        Some((_, last)) => (at.0 + text.matches('\n').count(), last.len()),
+        None => (at.0, at.1 + text.len()),
+    }
+}
+
+/// Uses `\n` for every line break and expands tabs.
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\t', TAB)
+}
+
+impl Component for TextArea {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let height = self
+            .max_height
+            .map_or(area.height, |max| area.height.min(max));
+        let area = Rect { height, ..area };
+        let gutter = self.gutter_width().min(area.width);
+        let text_area = Rect {
+            x: area.x + gutter,
+            width: area.width - gutter,
+            ..area
+        };
+
+        buf.set_style(area, self.style);
+
+        if text_area.is_empty() {
+            return;
+        }
+
+        let width = text_area.width as usize;
+
+        if self.is_empty() && !self.placeholder.is_empty() {
+            self.render_line_number(Some(1), area.x, area.y, buf);
+            buf.set_stringn(
+                text_area.x,
+                text_area.y,
+                &self.placeholder,
+                width,
+                self.style.patch(self.placeholder_style),
+            );
+
+            if self.focused {
+                view::set_cursor(text_area.as_position());
+            }
+
+            return;
+        }
+
+        let (cursor_line, cursor_offset) = self.cursor;
+        let rows = |index: usize| self.line_rows(index, width);
+
+        // Scroll down further if the area is smaller than the size of the model
+        let mut top = self.top.min(cursor_line);
+        let mut cursor_index = (top..cursor_line)
+            .map(|index| rows(index).len())
+            .sum::<usize>()
+            + self.cursor_row(&rows(cursor_line));
+
+        while cursor_index >= height as usize && top < cursor_line {
+            cursor_index -= rows(top).len();
+            top += 1;
+        }
+
+        // The cursor line alone might not fit, its first rows are hidden then
+        let mut skip = (cursor_index + 1).saturating_sub(height as usize);
+
+        // Lines that are not wrapped scroll horizontally with the cursor, back to fill
+        // an area wider than the width of the model
+        let column = self.column();
+        let offset = if self.wrap {
+            0
+        } else {
+            self.left
+                .min((self.line().width() + 1).saturating_sub(width))
+                .max((column + 1).saturating_sub(width))
+        };
+
+        let mut y = area.y;
+
+        'lines: for index in top..self.lines.len() {
+            let line = &self.lines[index];
+            let line_rows = rows(index);
+
+            for (row_index, row) in line_rows.iter().enumerate() {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+
+                if y >= area.bottom() {
+                    break 'lines;
+                }
+
+                let number = (row_index == 0).then_some(index + 1);
+                self.render_line_number(number, area.x, y, buf);
+
+                let mut column = 0;
+
+                for grapheme in line[row.clone()].graphemes(true) {
+                    let grapheme_width = grapheme.width();
+
+                    if column >= offset && column + grapheme_width <= offset + width {
+                        let x = text_area.x + (column - offset) as u16;
+                        buf.set_string(x, y, grapheme, self.style);
+                    }
+
+                    column += grapheme_width;
+                }
+
+                if self.focused && index == cursor_line && row_index == self.cursor_row(&line_rows)
+                {
+                    let column = line[row.start..cursor_offset].width() - offset;
+                    view::set_cursor(Position::new(text_area.x + column as u16, y));
+                }
+
+                y += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(textarea: TextArea, text: &str) -> TextArea {
+        text.chars()
+            .fold(textarea, |textarea, c| textarea.apply(Message::Insert(c)))
+    }
+
+    fn numbers(count: usize) -> String {
+        (0..count)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn scrolls_only_when_the_cursor_leaves_the_area() {
+        let textarea = TextArea::new()
+            .with_size(80, 5)
+            .with_value(numbers(20))
+            .apply(Message::Move(Motion::Top));
+        assert_eq!(textarea.top, 0);
+
+        let textarea = (0..4).fold(textarea, |textarea, _| {
+            textarea.apply(Message::Move(Motion::Down))
+        });
+        assert_eq!(textarea.top, 0);
+
+        let textarea = textarea.apply(Message::Move(Motion::Down));
+        assert_eq!(textarea.top, 1);
+
+        let textarea = textarea.apply(Message::Move(Motion::Up));
+        assert_eq!(textarea.top, 1);
+
+        let textarea = textarea.apply(Message::Move(Motion::Top));
+        assert_eq!(textarea.top, 0);
+    }
+
+    #[test]
+    fn scrolls_to_the_cursor_when_the_value_is_set() {
+        let textarea = TextArea::new().with_size(80, 5).with_value(numbers(20));
+
+        assert_eq!(textarea.top, 15);
+    }
+
+    #[test]
+    fn the_max_height_limits_the_rows_shown() {
+        let textarea = TextArea::new().with_max_height(2).with_value(numbers(5));
+
+        assert_eq!(textarea.top, 3);
+    }
+
+    #[test]
+    fn wrapped_lines_take_several_rows_when_scrolling() {
+        let value = "aaaa bbbb cccc\nx\ny";
+        let textarea = TextArea::new().with_size(10, 3).with_value(value);
+        assert_eq!(textarea.top, 1);
+
+        let textarea = TextArea::new()
+            .with_wrap(false)
+            .with_size(10, 3)
+            .with_value(value);
+        assert_eq!(textarea.top, 0);
+    }
+
+    #[test]
+    fn long_lines_scroll_horizontally_without_pinning_the_cursor() {
+        let textarea = TextArea::new()
+            .with_wrap(false)
+            .with_size(10, 3)
+            .with_value("0123456789abcdef");
+        assert_eq!(textarea.left, 7);
+
+        let textarea = (0..3).fold(textarea, |textarea, _| {
+            textarea.apply(Message::Move(Motion::Left))
+        });
+        assert_eq!(textarea.left, 7);
+
+        let textarea = textarea.apply(Message::Move(Motion::Home));
+        assert_eq!(textarea.left, 0);
+    }
+
+    #[test]
+    fn words_typed_are_undone_one_at_a_time() {
+        let textarea = typed(TextArea::new(), "hello world");
+
+        let textarea = textarea.apply(Message::Undo);
+        assert_eq!(textarea.value(), "hello ");
+
+        let textarea = textarea.apply(Message::Undo);
+        assert_eq!(textarea.value(), "hello");
+
+        let textarea = textarea.apply(Message::Undo);
+        assert_eq!(textarea.value(), "");
+        assert!(!textarea.can_undo());
+
+        let textarea = textarea.apply(Message::Redo).apply(Message::Redo);
+        assert_eq!(textarea.value(), "hello ");
+        assert_eq!(textarea.cursor(), (0, 6));
+    }
+
+    #[test]
+    fn erasing_is_undone_at_once_in_both_directions() {
+        let textarea = TextArea::new().with_value("abcdef");
+
+        let textarea = (0..3).fold(textarea, |textarea, _| {
+            textarea.apply(Message::DeleteBackward)
+        });
+        assert_eq!(textarea.value(), "abc");
+
+        let textarea = textarea.apply(Message::Undo);
+        assert_eq!(textarea.value(), "abcdef");
+        assert_eq!(textarea.cursor(), (0, 6));
+
+        let textarea = (0..2).fold(
+            textarea.apply(Message::Move(Motion::Home)),
+            |textarea, _| textarea.apply(Message::DeleteForward),
+        );
+        assert_eq!(textarea.value(), "cdef");
+
+        let textarea = textarea.apply(Message::Undo);
+        assert_eq!(textarea.value(), "abcdef");
+        assert_eq!(textarea.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn moving_the_cursor_ends_the_edit_undone_at_once() {
+        let textarea = typed(TextArea::new(), "ab").apply(Message::Move(Motion::Left));
+        let textarea = typed(textarea, "c");
+        assert_eq!(textarea.value(), "acb");
+
+        let textarea = textarea.apply(Message::Undo);
+        assert_eq!(textarea.value(), "ab");
+    }
+
+    #[test]
+    fn editing_clears_what_can_be_redone() {
+        let textarea = typed(TextArea::new(), "one").apply(Message::Undo);
+        assert!(textarea.can_redo());
+
+        let textarea = typed(textarea, "two");
+        assert!(!textarea.can_redo());
+        assert_eq!(textarea.apply(Message::Redo).value(), "two");
+    }
+
+    #[test]
+    fn lines_wrap_after_the_last_space_that_fits() {
+        let textarea = TextArea::new();
+
+        assert_eq!(textarea.rows("aaaa bbbb cccc", 10, None), [0..10, 10..14]);
+        assert_eq!(
+            textarea.rows("abcdefghijkl", 5, None),
+            [0..5, 5..10, 10..12]
+        );
+    }
+
+    #[test]
+    fn full_rows_get_an_empty_row_for_the_cursor_at_their_end() {
+        let textarea = TextArea::new();
+
+        assert_eq!(textarea.rows("abcde", 5, Some(5)), [0..5, 5..5]);
+        assert_eq!(textarea.rows("abcde", 5, None).len(), 1);
+    }
+
+    #[test]
+    fn height_counts_wrapped_rows_up_to_the_max_height() {
+        let textarea = TextArea::new().with_value("aaaa bbbb cccc\nx");
+        assert_eq!(textarea.height(10), 3);
+        assert_eq!(textarea.clone().with_wrap(false).height(10), 2);
+        assert_eq!(textarea.with_max_height(2).height(10), 2);
+    }
+
+    #[test]
+    fn line_numbers_narrow_the_text() {
+        let textarea = TextArea::new()
+            .with_line_numbers(true)
+            .with_value("aaaa bbbb\nx");
+
+        // Two columns are taken by the numbers and the space after them
+        assert_eq!(textarea.height(10), 3);
+    }
+}
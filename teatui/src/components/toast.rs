@@ -0,0 +1,343 @@
+//! Notifications stacked in a corner of the screen, each one going away after a while.
+//!
+//! The model keeps the toasts shown in [`Toasts`], drawn as a layer above the view,
+//! and pushes new ones from update or from the messages returned by the effects:
+//!
+//! ```no_run
+//! # use teatui::components::toast::{self, Toast};
+//! # use teatui::update::Update;
+//! # struct Model;
+//! # enum Message { Saved(Result<(), String>), Toasts(toast::Message) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Saved(Err(error)) => {
+//!     let toast = Toast::error(format!("Could not save: {error}"));
+//!     update(model, Message::Toasts(toast::Message::Push(toast)))
+//! }
+//! # _ => Update::Next(model, None),
+//! # }
+//! # }
+//! ```
+//!
+//! Once registered with [`ProgramOptions::with_toasts`](crate::options::ProgramOptions::with_toasts),
+//! the runtime sends [`Message::Tick`] whenever a toast expires, so the model never
+//! keeps track of their lifetimes.
+use crate::component::Component;
+use crate::layers::{self, Corner, Placement};
+use crate::update::Update;
+use crate::view;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Clear, Padding, Paragraph, Widget, Wrap};
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Info => " Info ",
+            Self::Warn => " Warning ",
+            Self::Error => " Error ",
+        }
+    }
+
+    /// Time a toast is shown unless told otherwise, longer for the serious ones.
+    pub fn duration(self) -> Duration {
+        let secs = match self {
+            Self::Info => 4,
+            Self::Warn => 6,
+            Self::Error => 10,
+        };
+
+        Duration::from_secs(secs)
+    }
+}
+
+/// A notification.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    level: Level,
+    text: String,
+    duration: Duration,
+    /// Set once the toast is pushed.
+    expires: Option<Instant>,
+}
+
+impl Toast {
+    pub fn new(level: Level, text: impl Into<String>) -> Self {
+        Self {
+            level,
+            text: text.into(),
+            duration: level.duration(),
+            expires: None,
+        }
+    }
+
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(Level::Info, text)
+    }
+
+    pub fn warn(text: impl Into<String>) -> Self {
+        Self::new(Level::Warn, text)
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(Level::Error, text)
+    }
+
+    /// Time the toast is shown, see [`Level::duration`] for the default.
+    pub fn with_duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Model of the toasts shown, from the oldest to the newest.
+#[derive(Debug, Clone)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+    corner: Corner,
+    width: u16,
+    max: usize,
+    info_style: Style,
+    warn_style: Style,
+    error_style: Style,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Shows a toast until its duration is over.
+    Push(Toast),
+    /// Removes the newest toast.
+    Dismiss,
+    DismissAll,
+    /// Removes the toasts expired at the given instant.
+    Tick(Instant),
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self {
+            toasts: vec![],
+            corner: Corner::TopRight,
+            width: 40,
+            max: 5,
+            info_style: Style::new().fg(Color::Cyan),
+            warn_style: Style::new().fg(Color::Yellow),
+            error_style: Style::new().fg(Color::Red),
+        }
+    }
+
+    /// Corner the toasts are stacked in, the top right one by default.
+    pub fn with_corner(self, corner: Corner) -> Self {
+        Self { corner, ..self }
+    }
+
+    /// Width of every toast, 40 by default.
+    pub fn with_width(self, width: u16) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Number of toasts kept, the oldest ones going away first. 5 by default.
+    pub fn with_max(self, max: usize) -> Self {
+        Self {
+            max: max.max(1),
+            ..self
+        }
+    }
+
+    /// Style of the border of the toasts of the given level.
+    pub fn with_level_style(self, level: Level, style: impl Into<Style>) -> Self {
+        let style = style.into();
+
+        match level {
+            Level::Info => Self {
+                info_style: style,
+                ..self
+            },
+            Level::Warn => Self {
+                warn_style: style,
+                ..self
+            },
+            Level::Error => Self {
+                error_style: style,
+                ..self
+            },
+        }
+    }
+
+    /// Toasts shown, from the oldest to the newest.
+    pub fn toasts(&self) -> &[Toast] {
+        &self.toasts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Instant at which the next toast expires, `None` while none is shown.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.toasts.iter().filter_map(|toast| toast.expires).min()
+    }
+
+    fn style(&self, level: Level) -> Style {
+        match level {
+            Level::Info => self.info_style,
+            Level::Warn => self.warn_style,
+            Level::Error => self.error_style,
+        }
+    }
+
+    pub(crate) fn apply(mut self, message: Message) -> Self {
+        match message {
+            Message::Push(toast) => {
+                let expires = Some(Instant::now() + toast.duration);
+                self.toasts.push(Toast { expires, ..toast });
+
+                let excess = self.toasts.len().saturating_sub(self.max);
+                self.toasts.drain(..excess);
+            }
+            Message::Dismiss => {
+                self.toasts.pop();
+            }
+            Message::DismissAll => self.toasts.clear(),
+            Message::Tick(now) => self
+                .toasts
+                .retain(|toast| toast.expires.is_none_or(|expires| expires > now)),
+        }
+
+        self
+    }
+}
+
+impl Component for Toasts {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    /// Draws the toasts in their corner of `area`, the newest one nearest to the corner,
+    /// leaving out those which don't fit.
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        let width = self.width.min(area.width);
+        let mut remaining = area;
+
+        for toast in self.toasts.iter().rev() {
+            // Borders and padding
+            let height = 2 + layers::wrapped_height(&toast.text, width.saturating_sub(4));
+
+            if height > remaining.height {
+                break;
+            }
+
+            let toast_area = Placement::corner(self.corner, width, height).area(remaining);
+            remaining.height -= height;
+
+            if matches!(self.corner, Corner::TopLeft | Corner::TopRight) {
+                remaining.y += height;
+            }
+
+            Clear.render(toast_area, buf);
+            view::hide_cursor_in(toast_area);
+
+            let block = Block::bordered()
+                .title(toast.level.title())
+                .padding(Padding::horizontal(1))
+                .border_style(self.style(toast.level));
+
+            Paragraph::new(toast.text.as_str())
+                .wrap(Wrap { trim: true })
+                .block(block)
+                .render(toast_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(toasts: &Toasts) -> Vec<&str> {
+        toasts.toasts().iter().map(Toast::text).collect()
+    }
+
+    fn pushed(toasts: Toasts, texts: &[&str]) -> Toasts {
+        texts.iter().fold(toasts, |toasts, text| {
+            toasts.apply(Message::Push(Toast::info(*text)))
+        })
+    }
+
+    #[test]
+    fn toasts_expire_at_the_deadline() {
+        let toasts = Toasts::new()
+            .apply(Message::Push(Toast::error("failed")))
+            .apply(Message::Push(
+                Toast::info("saved").with_duration(Duration::from_secs(1)),
+            ));
+        let deadline = toasts.deadline().unwrap();
+
+        let toasts = toasts.apply(Message::Tick(deadline - Duration::from_millis(1)));
+        assert_eq!(texts(&toasts), ["failed", "saved"]);
+
+        let toasts = toasts.apply(Message::Tick(deadline));
+        assert_eq!(texts(&toasts), ["failed"]);
+
+        // The next deadline is the one of the toast left
+        let deadline = toasts.deadline().unwrap();
+        let toasts = toasts.apply(Message::Tick(deadline));
+        assert!(toasts.is_empty());
+        assert_eq!(toasts.deadline(), None);
+    }
+
+    #[test]
+    fn serious_toasts_stay_longer() {
+        assert!(Level::Info.duration() < Level::Warn.duration());
+        assert!(Level::Warn.duration() < Level::Error.duration());
+    }
+
+    #[test]
+    fn the_oldest_toasts_go_away_beyond_the_maximum() {
+        let toasts = pushed(Toasts::new().with_max(2), &["one", "two", "three"]);
+
+        assert_eq!(texts(&toasts), ["two", "three"]);
+    }
+
+    #[test]
+    fn at_least_one_toast_is_kept() {
+        let toasts = pushed(Toasts::new().with_max(0), &["one", "two"]);
+
+        assert_eq!(texts(&toasts), ["two"]);
+    }
+
+    #[test]
+    fn dismissing_removes_the_newest_toast() {
+        let toasts = pushed(Toasts::new(), &["one", "two"]).apply(Message::Dismiss);
+        assert_eq!(texts(&toasts), ["one"]);
+
+        let toasts = pushed(toasts, &["two", "three"]).apply(Message::DismissAll);
+        assert!(toasts.is_empty());
+    }
+}
@@ -0,0 +1,646 @@
+//! Tree of expandable nodes, whose children can be loaded when a node is first expanded.
+//!
+//! The nodes shown are flattened into rows, navigated like a [`SelectList`](super::list::SelectList):
+//! `→` expands the selected node or moves to its first child, `←` collapses it or moves to its parent.
+//!
+//! Children of a [`Node::lazy`] node are asked for with an [`Effect::Load`] the first time it is
+//! expanded. The parent loads them in its effects function and gives them back with
+//! [`Message::Loaded`], the node showing that it is loading in the meantime:
+//!
+//! ```no_run
+//! # use std::path::{Path, PathBuf};
+//! # use teatui::components::tree::{self, Node};
+//! # struct Model;
+//! # enum Message { Tree(tree::Message<PathBuf>) }
+//! # enum Effect { Tree(tree::Effect<PathBuf>) }
+//! # fn read_dir(dir: &Path) -> std::io::Result<Vec<Node<PathBuf>>> {
+//! #     Ok(vec![Node::leaf(dir.join("file"))])
+//! # }
+//! fn effects(_model: Model, effect: Effect) -> Option<Message> {
+//!     match effect {
+//!         Effect::Tree(tree::Effect::Load(path, dir)) => {
+//!             let children = read_dir(&dir).map_err(|error| error.to_string());
+//!             Some(Message::Tree(tree::Message::Loaded(path, children)))
+//!         }
+//!     }
+//! }
+//! ```
+use super::list;
+use crate::component::Component;
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+use std::ops::Range;
+
+/// Position of a node: the index of the root, then the index of every child down to the node.
+pub type Path = Vec<usize>;
+
+/// Node of a tree, with its children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node<T> {
+    item: T,
+    children: Children<T>,
+    expanded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Children<T> {
+    /// The node cannot be expanded.
+    None,
+    /// Not asked for yet, they are loaded when the node is expanded.
+    Unloaded,
+    Loading,
+    Loaded(Vec<Node<T>>),
+    /// Loading failed with the given error, expanding the node again retries.
+    Failed(String),
+}
+
+impl<T> Node<T> {
+    /// Creates a node without children.
+    pub fn leaf(item: T) -> Self {
+        Self {
+            item,
+            children: Children::None,
+            expanded: false,
+        }
+    }
+
+    /// Creates a collapsed node with the given children.
+    pub fn branch(item: T, children: impl IntoIterator<Item = Node<T>>) -> Self {
+        Self {
+            item,
+            children: Children::Loaded(children.into_iter().collect()),
+            expanded: false,
+        }
+    }
+
+    /// Creates a collapsed node whose children are loaded when it is expanded.
+    pub fn lazy(item: T) -> Self {
+        Self {
+            item,
+            children: Children::Unloaded,
+            expanded: false,
+        }
+    }
+
+    /// Expands the node.
+    ///
+    /// Lazy nodes stay collapsed until their children are loaded by expanding them in the tree.
+    pub fn with_expanded(self, expanded: bool) -> Self {
+        let expanded = expanded && !matches!(self.children, Children::Unloaded);
+        Self { expanded, ..self }
+    }
+
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    pub fn children(&self) -> &Children<T> {
+        &self.children
+    }
+
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// Whether the node has or might have children.
+    pub fn is_branch(&self) -> bool {
+        !matches!(&self.children, Children::None)
+            && !matches!(&self.children, Children::Loaded(children) if children.is_empty())
+    }
+}
+
+/// Model of a tree with at most one selected node.
+#[derive(Debug, Clone)]
+pub struct Tree<T> {
+    roots: Vec<Node<T>>,
+    /// Paths of the nodes whose ancestors are all expanded, in order.
+    rows: Vec<Path>,
+    /// Position of the selected row.
+    selected: Option<usize>,
+    /// Position of the first row shown.
+    offset: usize,
+    page_size: usize,
+    label: fn(&T) -> String,
+    highlight_style: Style,
+    loading_style: Style,
+    error_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message<T> {
+    Next,
+    Previous,
+    First,
+    Last,
+    PageDown,
+    PageUp,
+    /// Expands the selected node, or selects its first child if it is already expanded.
+    Expand,
+    /// Collapses the selected node, or selects its parent if it is already collapsed.
+    Collapse,
+    /// Expands or collapses the selected node.
+    Toggle,
+    /// Selects the node at the given path, expanding its ancestors.
+    Select(Path),
+    /// Gives the children loaded for the node at the given path, or the loading error.
+    Loaded(Path, Result<Vec<Node<T>>, String>),
+}
+
+impl<T> Message<T> {
+    /// Converts the arrow keys, `h`, `j`, `k` and `l`, `home`, `end`, `page up`, `page down`,
+    /// `enter` and `space` and the mouse wheel.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Down | KeyCode::Char('j') => Some(Self::Next),
+                KeyCode::Up | KeyCode::Char('k') => Some(Self::Previous),
+                KeyCode::Right | KeyCode::Char('l') => Some(Self::Expand),
+                KeyCode::Left | KeyCode::Char('h') => Some(Self::Collapse),
+                KeyCode::Enter | KeyCode::Char(' ') => Some(Self::Toggle),
+                KeyCode::Home => Some(Self::First),
+                KeyCode::End => Some(Self::Last),
+                KeyCode::PageDown => Some(Self::PageDown),
+                KeyCode::PageUp => Some(Self::PageUp),
+                _ => None,
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollDown => Some(Self::Next),
+                MouseEventKind::ScrollUp => Some(Self::Previous),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Effects asked for by a tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect<T> {
+    /// Loads the children of the node at the given path, holding the given item,
+    /// to be answered with [`Message::Loaded`].
+    Load(Path, T),
+}
+
+impl<T> Tree<T> {
+    /// Creates a tree of the given root nodes, the first one being selected.
+    ///
+    /// `label` gives the text of a node.
+    pub fn new(roots: impl IntoIterator<Item = Node<T>>, label: fn(&T) -> String) -> Self {
+        let tree = Self {
+            roots: roots.into_iter().collect(),
+            rows: vec![],
+            selected: None,
+            offset: 0,
+            page_size: 10,
+            label,
+            highlight_style: Style::new().add_modifier(Modifier::REVERSED),
+            loading_style: Style::new().fg(Color::DarkGray),
+            error_style: Style::new().fg(Color::Red),
+        }
+        .refresh();
+        let selected = (!tree.rows.is_empty()).then_some(0);
+
+        Self { selected, ..tree }
+    }
+
+    /// Rows the tree is shown in, 10 by default.
+    pub fn with_page_size(self, page_size: usize) -> Self {
+        Self {
+            page_size: page_size.max(1),
+            ..self
+        }
+        .scroll()
+    }
+
+    pub fn with_highlight_style(self, highlight_style: impl Into<Style>) -> Self {
+        Self {
+            highlight_style: highlight_style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the text shown after a node whose children are loading, dark gray by default.
+    pub fn with_loading_style(self, loading_style: impl Into<Style>) -> Self {
+        Self {
+            loading_style: loading_style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the error shown after a node whose children failed to load, red by default.
+    pub fn with_error_style(self, error_style: impl Into<Style>) -> Self {
+        Self {
+            error_style: error_style.into(),
+            ..self
+        }
+    }
+
+    pub fn roots(&self) -> &[Node<T>] {
+        &self.roots
+    }
+
+    /// Node at the given path.
+    pub fn node(&self, path: &[usize]) -> Option<&Node<T>> {
+        let (&root, path) = path.split_first()?;
+
+        path.iter()
+            .try_fold(self.roots.get(root)?, |node, &index| match &node.children {
+                Children::Loaded(children) => children.get(index),
+                _ => None,
+            })
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut Node<T>> {
+        let (&root, path) = path.split_first()?;
+
+        path.iter()
+            .try_fold(self.roots.get_mut(root)?, |node, &index| {
+                match &mut node.children {
+                    Children::Loaded(children) => children.get_mut(index),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Path of the selected node.
+    pub fn selected_path(&self) -> Option<&[usize]> {
+        self.selected.map(|position| self.rows[position].as_slice())
+    }
+
+    pub fn selected_node(&self) -> Option<&Node<T>> {
+        self.selected_path().and_then(|path| self.node(path))
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        self.selected_node().map(Node::item)
+    }
+
+    /// Number of nodes shown, those whose ancestors are all expanded.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Position of the first row shown.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Positions of the rows shown in `height` rows, see [`list::SelectList::window`].
+    pub fn window(&self, height: u16) -> Range<usize> {
+        list::window(self.rows.len(), self.selected, self.offset, height)
+    }
+
+    /// Paths of the nodes shown in `height` rows, with the nodes.
+    pub fn shown(&self, height: u16) -> impl Iterator<Item = (&[usize], &Node<T>)> {
+        self.rows[self.window(height)]
+            .iter()
+            .filter_map(|path| self.node(path).map(|node| (path.as_slice(), node)))
+    }
+
+    /// State to render [`Tree::list`] with, for the same height.
+    pub fn state(&self, height: u16) -> ListState {
+        let window = self.window(height);
+
+        ListState::default().with_selected(self.selected.map(|selected| selected - window.start))
+    }
+
+    /// Builds the ratatui list of the nodes shown in `height` rows: every node is indented
+    /// by its depth, preceded by `▸` or `▾` if it can be expanded, and followed by
+    /// whether its children are loading or failed to load.
+    pub fn list(&self, height: u16) -> List<'_> {
+        let items = self.shown(height).map(|(path, node)| {
+            let indent = "  ".repeat(path.len() - 1);
+            let symbol = match (node.is_branch(), node.expanded) {
+                (false, _) => "  ",
+                (true, false) => "▸ ",
+                (true, true) => "▾ ",
+            };
+
+            let mut line = Line::from(vec![
+                Span::raw(indent),
+                Span::raw(symbol),
+                Span::raw((self.label)(&node.item)),
+            ]);
+
+            match &node.children {
+                Children::Loading => line.push_span(Span::styled(" loading…", self.loading_style)),
+                Children::Failed(error) => {
+                    line.push_span(Span::styled(format!(" ✗ {error}"), self.error_style))
+                }
+                _ => {}
+            }
+
+            ListItem::new(line)
+        });
+
+        List::new(items).highlight_style(self.highlight_style)
+    }
+
+    /// Flattens the nodes whose ancestors are expanded into rows, keeping the selected node
+    /// selected, or its closest ancestor shown.
+    fn refresh(self) -> Self {
+        fn flatten<T>(nodes: &[Node<T>], path: &mut Path, rows: &mut Vec<Path>) {
+            for (index, node) in nodes.iter().enumerate() {
+                path.push(index);
+                rows.push(path.clone());
+
+                if let (true, Children::Loaded(children)) = (node.expanded, &node.children) {
+                    flatten(children, path, rows);
+                }
+
+                path.pop();
+            }
+        }
+
+        let mut rows = vec![];
+        flatten(&self.roots, &mut vec![], &mut rows);
+
+        let selected = self.selected.and_then(|position| {
+            let path = &self.rows[position];
+
+            (1..=path.len())
+                .rev()
+                .find_map(|len| rows.iter().position(|row| *row == path[..len]))
+                .or(Some(position.min(rows.len().checked_sub(1)?)))
+        });
+
+        Self {
+            rows,
+            selected,
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Moves the offset so the selected row is shown within the page.
+    fn scroll(self) -> Self {
+        let offset =
+            list::scroll_offset(self.rows.len(), self.selected, self.offset, self.page_size);
+        Self { offset, ..self }
+    }
+
+    fn select(self, selected: Option<usize>) -> Self {
+        Self { selected, ..self }.scroll()
+    }
+
+    /// Selects the row of the given path, if it is shown.
+    fn select_path(self, path: &[usize]) -> Self {
+        let selected = self.rows.iter().position(|row| row == path);
+
+        match selected {
+            Some(_) => self.select(selected),
+            None => self,
+        }
+    }
+}
+
+impl<T: Clone> Tree<T> {
+    /// Expands or collapses the node at the given path, asking for its children if they
+    /// are not loaded yet.
+    fn set_expanded(mut self, path: &[usize], expanded: bool) -> (Self, Option<Effect<T>>) {
+        let Some(node) = self.node_mut(path) else {
+            return (self, None);
+        };
+
+        let effect = match &node.children {
+            Children::None => return (self, None),
+            Children::Unloaded | Children::Failed(_) if expanded => {
+                node.children = Children::Loading;
+                Some(Effect::Load(path.to_vec(), node.item.clone()))
+            }
+            _ => None,
+        };
+
+        node.expanded = expanded;
+        (self.refresh(), effect)
+    }
+
+    pub(crate) fn apply(self, message: Message<T>) -> (Self, Option<Effect<T>>) {
+        let last = self.rows.len().checked_sub(1);
+        let selected_path = self.selected_path().map(<[usize]>::to_vec);
+        let selected_node = self.selected_node();
+        let expanded = selected_node.is_some_and(|node| node.expanded);
+        let unloaded =
+            selected_node.is_some_and(|node| matches!(node.children, Children::Unloaded));
+        let branch = selected_node.is_some_and(Node::is_branch);
+
+        let selected = match (message, self.selected, last) {
+            (Message::Loaded(path, children), _, _) => {
+                let mut tree = self;
+
+                // Children loaded for a node which was not waiting for them are stale
+                if let Some(node) = tree.node_mut(&path)
+                    && matches!(node.children, Children::Loading)
+                {
+                    node.children = match children {
+                        Ok(children) => Children::Loaded(children),
+                        Err(error) => Children::Failed(error),
+                    };
+                }
+
+                return (tree.refresh(), None);
+            }
+            (Message::Select(path), _, _) => {
+                let mut tree = self;
+                let mut effect = None;
+
+                // The ancestors below a lazy one don't exist until its children are loaded
+                for len in 1..path.len() {
+                    let (expanded, load) = tree.set_expanded(&path[..len], true);
+                    tree = expanded;
+
+                    if load.is_some() {
+                        effect = load;
+                        break;
+                    }
+                }
+
+                return (tree.select_path(&path), effect);
+            }
+            (Message::Expand, _, _) | (Message::Collapse, _, _) | (Message::Toggle, _, _)
+                if selected_path.is_none() =>
+            {
+                return (self, None);
+            }
+            (Message::Expand, Some(position), _) if expanded && !unloaded => {
+                let path = selected_path.unwrap_or_default();
+
+                // The first child, if any, is the next row
+                match self.rows.get(position + 1) {
+                    Some(row) if row.starts_with(&path) => Some(position + 1),
+                    _ => Some(position),
+                }
+            }
+            (Message::Expand, _, _) => {
+                return self.set_expanded(&selected_path.unwrap_or_default(), true);
+            }
+            (Message::Collapse, _, _) if expanded && branch => {
+                return self.set_expanded(&selected_path.unwrap_or_default(), false);
+            }
+            (Message::Collapse, _, _) => {
+                let path = selected_path.unwrap_or_default();
+                return (self.select_path(&path[..path.len() - 1]), None);
+            }
+            (Message::Toggle, _, _) => {
+                return self.set_expanded(&selected_path.unwrap_or_default(), !expanded);
+            }
+            (_, _, None) => return (self, None),
+            (Message::Next, None, _) | (Message::First, _, _) => Some(0),
+            (Message::Next, Some(position), Some(last)) => Some((position + 1).min(last)),
+            (Message::Previous, None, last) | (Message::Last, _, last) => last,
+            (Message::Previous, Some(position), _) => Some(position.saturating_sub(1)),
+            (Message::PageDown, None, _) => Some(0),
+            (Message::PageDown, Some(position), Some(last)) => {
+                Some((position + self.page_size).min(last))
+            }
+            (Message::PageUp, None, last) => last,
+            (Message::PageUp, Some(position), _) => Some(position.saturating_sub(self.page_size)),
+        };
+
+        (self.select(selected), None)
+    }
+}
+
+impl<T: Clone> Component for Tree<T> {
+    type Message = Message<T>;
+    type Effect = Effect<T>;
+
+    fn update(self, message: Message<T>) -> Update<Self, Effect<T>> {
+        let (tree, effect) = self.apply(message);
+        Update::Next(tree, effect)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        StatefulWidget::render(
+            self.list(area.height),
+            area,
+            buf,
+            &mut self.state(area.height),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(item: &&'static str) -> String {
+        item.to_string()
+    }
+
+    /// `a` holding `a1` holding `a11`, and `b`, every branch expanded.
+    fn tree() -> Tree<&'static str> {
+        Tree::new(
+            [
+                Node::branch(
+                    "a",
+                    [Node::branch("a1", [Node::leaf("a11")]).with_expanded(true)],
+                )
+                .with_expanded(true),
+                Node::leaf("b"),
+            ],
+            label,
+        )
+    }
+
+    fn rows(tree: &Tree<&'static str>) -> Vec<&'static str> {
+        tree.shown(u16::MAX).map(|(_, node)| *node.item()).collect()
+    }
+
+    #[test]
+    fn shows_the_nodes_whose_ancestors_are_expanded() {
+        let tree = tree();
+        assert_eq!(rows(&tree), ["a", "a1", "a11", "b"]);
+        assert_eq!(tree.selected(), Some(&"a"));
+
+        let (tree, _) = tree.apply(Message::Toggle);
+        assert_eq!(rows(&tree), ["a", "b"]);
+
+        // Expanding again shows the children as they were left
+        let (tree, _) = tree.apply(Message::Expand);
+        assert_eq!(rows(&tree), ["a", "a1", "a11", "b"]);
+    }
+
+    #[test]
+    fn keeps_the_selected_node_when_rows_move() {
+        let tree = Tree::new([Node::lazy("dir"), Node::leaf("file")], label);
+
+        let (tree, _) = tree.apply(Message::Expand);
+        let (tree, _) = tree.apply(Message::Next);
+        assert_eq!(tree.selected(), Some(&"file"));
+
+        let children = Ok(vec![Node::leaf("x"), Node::leaf("y")]);
+        let (tree, _) = tree.apply(Message::Loaded(vec![0], children));
+        assert_eq!(rows(&tree), ["dir", "x", "y", "file"]);
+        assert_eq!(tree.selected(), Some(&"file"));
+    }
+
+    #[test]
+    fn collapsing_hides_the_descendants() {
+        let (tree, _) = tree().apply(Message::Select(vec![0, 0]));
+        let (tree, _) = tree.apply(Message::Collapse);
+
+        assert_eq!(rows(&tree), ["a", "a1", "b"]);
+        assert_eq!(tree.selected(), Some(&"a1"));
+    }
+
+    #[test]
+    fn collapse_moves_to_the_parent() {
+        let (tree, _) = tree().apply(Message::Select(vec![0, 0, 0]));
+        let (tree, _) = tree.apply(Message::Collapse);
+
+        assert_eq!(tree.selected(), Some(&"a1"));
+        assert_eq!(rows(&tree), ["a", "a1", "a11", "b"]);
+    }
+
+    #[test]
+    fn loads_lazy_children_when_expanded() {
+        let tree = Tree::new([Node::lazy("dir"), Node::leaf("file")], label);
+
+        let (tree, effect) = tree.apply(Message::Expand);
+        assert_eq!(effect, Some(Effect::Load(vec![0], "dir")));
+        assert!(matches!(
+            tree.selected_node().unwrap().children(),
+            Children::Loading
+        ));
+        assert_eq!(rows(&tree), ["dir", "file"]);
+
+        let children = Ok(vec![Node::leaf("x"), Node::leaf("y")]);
+        let (tree, _) = tree.apply(Message::Loaded(vec![0], children));
+        assert_eq!(rows(&tree), ["dir", "x", "y", "file"]);
+        assert_eq!(tree.selected(), Some(&"dir"));
+    }
+
+    #[test]
+    fn failed_loads_are_retried() {
+        let tree = Tree::new([Node::lazy("dir")], label);
+
+        let (tree, _) = tree.apply(Message::Expand);
+        let (tree, _) = tree.apply(Message::Loaded(vec![0], Err("denied".into())));
+        assert_eq!(
+            tree.selected_node().unwrap().children(),
+            &Children::Failed("denied".into())
+        );
+
+        let (tree, _) = tree.apply(Message::Collapse);
+        let (_, effect) = tree.apply(Message::Expand);
+        assert_eq!(effect, Some(Effect::Load(vec![0], "dir")));
+    }
+
+    #[test]
+    fn stale_loads_are_ignored() {
+        let tree = tree();
+        let (tree, _) = tree.apply(Message::Loaded(vec![1], Ok(vec![Node::leaf("x")])));
+
+        assert_eq!(rows(&tree), ["a", "a1", "a11", "b"]);
+    }
+}
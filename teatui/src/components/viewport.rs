@@ -0,0 +1,722 @@
+//! Scrollable view of a long text, such as logs, help pages or diffs, searchable like `less`.
+//!
+//! The parent converts events with [`Message::from_event`], or with
+//! [`Message::from_search_event`] while [`Viewport::is_searching`], and tells the viewport
+//! its size when the terminal is resized, so page keys and scroll limits match what is drawn:
+//!
+//! ```no_run
+//! # use crossterm::event::Event;
+//! # use teatui::components::viewport::Viewport;
+//! # use teatui::update::Update;
+//! # struct Model { log: Viewport }
+//! # enum Message { Event(Event) }
+//! # fn update(model: Model, msg: Message) -> Update<Model, ()> {
+//! # match msg {
+//! Message::Event(Event::Resize(width, height)) => Update::Next(
+//!     Model {
+//!         log: model.log.with_size(width, height - 1),
+//!         ..model
+//!     },
+//!     None,
+//! ),
+//! # _ => Update::Next(model, None),
+//! # }
+//! # }
+//! ```
+//!
+//! `/` starts a search, highlighting the matches as the query is typed; `n` and `N` then move
+//! to the next and previous match.
+use super::text_input::{self, TextInput};
+use crate::component::Component;
+use crate::update::Update;
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{
+    Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
+};
+use std::convert::Infallible;
+use std::ops::Range;
+use std::sync::Arc;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns scrolled by the left and right keys.
+const HORIZONTAL_STEP: usize = 4;
+
+/// Columns a tab is expanded to.
+const TAB_WIDTH: usize = 4;
+
+/// Model of a viewport.
+#[derive(Debug, Clone)]
+pub struct Viewport {
+    /// Shared, so the model is cloned without copying the text.
+    lines: Arc<[Line<'static>]>,
+    /// Lines without their styles, to search them.
+    plain: Arc<[String]>,
+    /// Width of the widest line.
+    max_width: usize,
+    /// First line shown.
+    top: usize,
+    /// First column shown.
+    left: usize,
+    width: u16,
+    height: u16,
+    scrollbar: bool,
+    search: TextInput,
+    searching: bool,
+    /// Query of the last search confirmed with `enter`.
+    query: String,
+    /// Line and byte range of every match, in order.
+    matches: Vec<(usize, Range<usize>)>,
+    current: Option<usize>,
+    /// Position to go back to when a search is cancelled.
+    origin: (usize, usize),
+    style: Style,
+    match_style: Style,
+    current_match_style: Style,
+    prompt_style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    Top,
+    Bottom,
+    /// Scrolls back to the first column.
+    LineStart,
+    /// Scrolls to show the given line at the top.
+    GoTo(usize),
+    /// Opens the search prompt.
+    StartSearch,
+    Search(text_input::Message),
+    /// Closes the search prompt, keeping its matches.
+    ConfirmSearch,
+    /// Closes the search prompt, going back to the previous search and position.
+    CancelSearch,
+    NextMatch,
+    PreviousMatch,
+}
+
+impl Message {
+    /// Converts the keys of `less`: arrows, `h`/`j`/`k`/`l`, `space`, `b`, `d`, `u`,
+    /// `g`, `G`, `0`, the page keys, `/`, `n`, `N` and the mouse wheel.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+                match key.code {
+                    KeyCode::Down | KeyCode::Char('j') | KeyCode::Enter => Some(Self::Down),
+                    KeyCode::Up | KeyCode::Char('k') => Some(Self::Up),
+                    KeyCode::Left | KeyCode::Char('h') => Some(Self::Left),
+                    KeyCode::Right | KeyCode::Char('l') => Some(Self::Right),
+                    KeyCode::Char('f') if ctrl => Some(Self::PageDown),
+                    KeyCode::Char('b') if ctrl => Some(Self::PageUp),
+                    KeyCode::Char('d') if ctrl => Some(Self::HalfPageDown),
+                    KeyCode::Char('u') if ctrl => Some(Self::HalfPageUp),
+                    KeyCode::PageDown | KeyCode::Char(' ' | 'f') => Some(Self::PageDown),
+                    KeyCode::PageUp | KeyCode::Char('b') => Some(Self::PageUp),
+                    KeyCode::Char('d') => Some(Self::HalfPageDown),
+                    KeyCode::Char('u') => Some(Self::HalfPageUp),
+                    KeyCode::Home | KeyCode::Char('g') => Some(Self::Top),
+                    KeyCode::End | KeyCode::Char('G') => Some(Self::Bottom),
+                    KeyCode::Char('0') => Some(Self::LineStart),
+                    KeyCode::Char('/') => Some(Self::StartSearch),
+                    KeyCode::Char('n') => Some(Self::NextMatch),
+                    KeyCode::Char('N') => Some(Self::PreviousMatch),
+                    _ => None,
+                }
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollDown => Some(Self::Down),
+                MouseEventKind::ScrollUp => Some(Self::Up),
+                MouseEventKind::ScrollRight => Some(Self::Right),
+                MouseEventKind::ScrollLeft => Some(Self::Left),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts an event while the search prompt is open: `enter` confirms the search,
+    /// `esc` cancels it and the other keys edit the query.
+    pub fn from_search_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Enter => Some(Self::ConfirmSearch),
+                KeyCode::Esc => Some(Self::CancelSearch),
+                _ => text_input::Message::from_event(event).map(Self::Search),
+            },
+            _ => text_input::Message::from_event(event).map(Self::Search),
+        }
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::new(Text::default())
+    }
+}
+
+impl Viewport {
+    /// Creates a viewport showing the start of `text`, sized for a 80×24 terminal.
+    pub fn new(text: impl Into<Text<'static>>) -> Self {
+        let viewport = Self {
+            lines: Arc::from([]),
+            plain: Arc::from([]),
+            max_width: 0,
+            top: 0,
+            left: 0,
+            width: 80,
+            height: 24,
+            scrollbar: true,
+            search: TextInput::new(),
+            searching: false,
+            query: String::new(),
+            matches: vec![],
+            current: None,
+            origin: (0, 0),
+            style: Style::new(),
+            match_style: Style::new().add_modifier(Modifier::REVERSED),
+            current_match_style: Style::new().fg(Color::Black).bg(Color::Yellow),
+            prompt_style: Style::new(),
+        };
+
+        viewport.with_text(text)
+    }
+
+    /// Replaces the text, keeping the scroll position and searching the new text
+    /// for the current query.
+    pub fn with_text(self, text: impl Into<Text<'static>>) -> Self {
+        let lines: Arc<[Line<'static>]> = text.into().lines.into_iter().map(expand_tabs).collect();
+        let plain: Arc<[String]> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|span| &*span.content).collect())
+            .collect();
+        let max_width = lines.iter().map(Line::width).max().unwrap_or(0);
+
+        let viewport = Self {
+            lines,
+            plain,
+            max_width,
+            ..self
+        };
+        let query = viewport.active_query().to_string();
+
+        viewport.find(&query).clamp()
+    }
+
+    /// Size of the area the viewport is drawn in, used by the page keys and to stop
+    /// scrolling past the end of the text.
+    ///
+    /// The viewport still fits a smaller area when drawn.
+    pub fn with_size(self, width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            ..self
+        }
+        .clamp()
+    }
+
+    /// Shows a scrollbar when the text is taller than the viewport, the default.
+    pub fn with_scrollbar(self, scrollbar: bool) -> Self {
+        Self { scrollbar, ..self }.clamp()
+    }
+
+    pub fn with_style(self, style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            ..self
+        }
+    }
+
+    /// Style patched on the matches of the search, reversed by default.
+    pub fn with_match_style(self, match_style: impl Into<Style>) -> Self {
+        Self {
+            match_style: match_style.into(),
+            ..self
+        }
+    }
+
+    /// Style patched on the match moved to by [`Message::NextMatch`]
+    /// and [`Message::PreviousMatch`].
+    pub fn with_current_match_style(self, current_match_style: impl Into<Style>) -> Self {
+        Self {
+            current_match_style: current_match_style.into(),
+            ..self
+        }
+    }
+
+    /// Style of the search prompt.
+    pub fn with_prompt_style(self, prompt_style: impl Into<Style>) -> Self {
+        let prompt_style = prompt_style.into();
+
+        Self {
+            prompt_style,
+            search: self.search.with_style(prompt_style),
+            ..self
+        }
+    }
+
+    pub fn lines(&self) -> &[Line<'static>] {
+        &self.lines
+    }
+
+    /// First line and first column shown.
+    pub fn offset(&self) -> (usize, usize) {
+        (self.top, self.left)
+    }
+
+    /// Whether the last line is shown.
+    pub fn is_at_bottom(&self) -> bool {
+        self.top >= self.max_top()
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.searching
+    }
+
+    /// Query of the search being typed, or of the last search.
+    pub fn query(&self) -> &str {
+        self.active_query()
+    }
+
+    /// Number of matches of the search.
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Position of the current match among all matches.
+    pub fn current_match(&self) -> Option<usize> {
+        self.current
+    }
+
+    fn active_query(&self) -> &str {
+        if self.searching {
+            self.search.value()
+        } else {
+            &self.query
+        }
+    }
+
+    /// Lines of text shown, without the search prompt.
+    fn page_height(&self) -> usize {
+        self.height.saturating_sub(self.searching as u16) as usize
+    }
+
+    /// Columns of text shown, without the scrollbar.
+    fn page_width(&self) -> usize {
+        let scrollbar = self.scrollbar && self.lines.len() > self.page_height();
+        self.width.saturating_sub(scrollbar as u16) as usize
+    }
+
+    fn max_top(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height())
+    }
+
+    fn max_left(&self) -> usize {
+        self.max_width.saturating_sub(self.page_width())
+    }
+
+    fn clamp(self) -> Self {
+        Self {
+            top: self.top.min(self.max_top()),
+            left: self.left.min(self.max_left()),
+            ..self
+        }
+    }
+
+    /// Matches of `query` in every line, ignoring case unless the query has uppercase characters.
+    fn find(self, query: &str) -> Self {
+        let pattern: Vec<char> = query.chars().collect();
+        let case_sensitive = pattern.iter().any(|c| c.is_uppercase());
+        let eq = |a: char, b: char| {
+            if case_sensitive {
+                a == b
+            } else {
+                a.to_lowercase().eq(b.to_lowercase())
+            }
+        };
+
+        let mut matches = vec![];
+
+        if !pattern.is_empty() {
+            for (row, line) in self.plain.iter().enumerate() {
+                let mut start = 0;
+
+                while start < line.len() {
+                    let mut chars = line[start..].char_indices();
+                    let found = pattern
+                        .iter()
+                        .all(|&p| chars.next().is_some_and(|(_, c)| eq(c, p)));
+
+                    if found {
+                        let end = chars.next().map_or(line.len(), |(index, _)| start + index);
+                        matches.push((row, start..end));
+                        start = end;
+                    } else {
+                        start += line[start..].chars().next().map_or(1, char::len_utf8);
+                    }
+                }
+            }
+        }
+
+        let current = self.current.filter(|&current| current < matches.len());
+
+        Self {
+            matches,
+            current,
+            ..self
+        }
+    }
+
+    /// Columns of a match in its line.
+    fn columns(&self, (row, range): &(usize, Range<usize>)) -> Range<usize> {
+        let line = &self.plain[*row];
+        line[..range.start].width()..line[..range.end].width()
+    }
+
+    /// Makes `current` the current match, scrolling to show it.
+    fn show_match(self, current: Option<usize>) -> Self {
+        let Some(found) = current.and_then(|current| self.matches.get(current)) else {
+            return Self { current, ..self };
+        };
+
+        let row = found.0;
+        let columns = self.columns(found);
+        let (height, width) = (self.page_height(), self.page_width());
+
+        let top = if row < self.top || row >= self.top + height {
+            row
+        } else {
+            self.top
+        };
+        let left = if columns.start < self.left || columns.end > self.left + width {
+            columns.start.saturating_sub(width / 2)
+        } else {
+            self.left
+        };
+
+        Self {
+            top,
+            left,
+            current,
+            ..self
+        }
+        .clamp()
+    }
+
+    /// First match at or after the top line.
+    fn first_visible_match(&self) -> Option<usize> {
+        if self.matches.is_empty() {
+            return None;
+        }
+
+        let position = self.matches.iter().position(|(row, _)| *row >= self.top);
+        Some(position.unwrap_or(0))
+    }
+
+    fn scroll(self, top: usize, left: usize) -> Self {
+        Self { top, left, ..self }.clamp()
+    }
+
+    pub(crate) fn apply(self, message: Message) -> Self {
+        let (top, left) = (self.top, self.left);
+        let page = self.page_height().max(1);
+
+        match message {
+            Message::Up => self.scroll(top.saturating_sub(1), left),
+            Message::Down => self.scroll(top + 1, left),
+            Message::Left => self.scroll(top, left.saturating_sub(HORIZONTAL_STEP)),
+            Message::Right => self.scroll(top, left + HORIZONTAL_STEP),
+            Message::PageUp => self.scroll(top.saturating_sub(page), left),
+            Message::PageDown => self.scroll(top + page, left),
+            Message::HalfPageUp => self.scroll(top.saturating_sub(page.div_ceil(2)), left),
+            Message::HalfPageDown => self.scroll(top + page.div_ceil(2), left),
+            Message::Top => self.scroll(0, left),
+            Message::Bottom => self.scroll(usize::MAX, left),
+            Message::LineStart => self.scroll(top, 0),
+            Message::GoTo(line) => self.scroll(line, left),
+            Message::StartSearch => Self {
+                searching: true,
+                search: self.search.with_value(""),
+                origin: (top, left),
+                ..self
+            }
+            .find("")
+            .clamp(),
+            Message::Search(msg) => {
+                let search = self.search.apply(msg);
+                let query = search.value().to_string();
+                let (top, left) = self.origin;
+
+                // Incremental search, from where the search started
+                let viewport = Self {
+                    search,
+                    top,
+                    left,
+                    current: None,
+                    ..self
+                }
+                .find(&query);
+                let current = viewport.first_visible_match();
+
+                viewport.show_match(current)
+            }
+            Message::ConfirmSearch => Self {
+                searching: false,
+                query: self.search.value().to_string(),
+                ..self
+            }
+            .clamp(),
+            Message::CancelSearch => {
+                let query = self.query.clone();
+                let (top, left) = self.origin;
+
+                Self {
+                    searching: false,
+                    top,
+                    left,
+                    current: None,
+                    ..self
+                }
+                .find(&query)
+                .clamp()
+            }
+            Message::NextMatch => {
+                let current = match self.current {
+                    Some(current) => Some((current + 1) % self.matches.len().max(1)),
+                    None => self.first_visible_match(),
+                };
+
+                self.show_match(current)
+            }
+            Message::PreviousMatch => {
+                let count = self.matches.len();
+                let current = match self.current {
+                    Some(current) => Some((current + count - 1) % count.max(1)),
+                    None => self.first_visible_match(),
+                };
+
+                self.show_match(current)
+            }
+        }
+    }
+}
+
+/// Replaces the tabs of a line with spaces, which the terminal would not draw.
+fn expand_tabs(line: Line<'static>) -> Line<'static> {
+    if !line.spans.iter().any(|span| span.content.contains('\t')) {
+        return line;
+    }
+
+    let spans: Vec<Span<'static>> = line
+        .spans
+        .into_iter()
+        .map(|span| {
+            if span.content.contains('\t') {
+                Span::styled(
+                    span.content.replace('\t', &" ".repeat(TAB_WIDTH)),
+                    span.style,
+                )
+            } else {
+                span
+            }
+        })
+        .collect();
+
+    Line { spans, ..line }
+}
+
+impl Component for Viewport {
+    type Message = Message;
+    type Effect = Infallible;
+
+    fn update(self, message: Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    fn view(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let mut text_area = area;
+
+        if self.searching {
+            text_area.height -= 1;
+
+            let prompt_area = Rect {
+                y: area.bottom() - 1,
+                height: 1,
+                ..area
+            };
+            buf.set_style(prompt_area, self.prompt_style);
+            buf.set_string(prompt_area.x, prompt_area.y, "/", self.prompt_style);
+
+            let input_area = Rect {
+                x: prompt_area.x + 1,
+                width: prompt_area.width - 1,
+                ..prompt_area
+            };
+            self.search.view(input_area, buf);
+        }
+
+        let scrollbar = self.scrollbar && self.lines.len() > text_area.height as usize;
+        let scrollbar_area = text_area;
+
+        if scrollbar {
+            text_area.width = text_area.width.saturating_sub(1);
+        }
+
+        // Fits an area smaller than the size given to the model
+        let height = text_area.height as usize;
+        let width = text_area.width as usize;
+        let top = self.top.min(self.lines.len().saturating_sub(height));
+        let left = self.left.min(self.max_width.saturating_sub(width));
+        let bottom = (top + height).min(self.lines.len());
+
+        Paragraph::new(self.lines[top..bottom].to_vec())
+            .style(self.style)
+            .scroll((0, left.min(u16::MAX as usize) as u16))
+            .render(text_area, buf);
+
+        for (position, found) in self.matches.iter().enumerate() {
+            let row = found.0;
+
+            if row < top || row >= bottom {
+                continue;
+            }
+
+            let columns = self.columns(found);
+            let start = columns.start.max(left);
+            let end = columns.end.min(left + width);
+
+            if start >= end {
+                continue;
+            }
+
+            let style = if self.current == Some(position) {
+                self.current_match_style
+            } else {
+                self.match_style
+            };
+            let highlighted = Rect::new(
+                text_area.x + (start - left) as u16,
+                text_area.y + (row - top) as u16,
+                (end - start) as u16,
+                1,
+            );
+            buf.set_style(highlighted, style);
+        }
+
+        if scrollbar {
+            let mut state = ScrollbarState::new(self.lines.len().saturating_sub(height))
+                .position(top)
+                .viewport_content_length(height);
+
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(
+                scrollbar_area,
+                buf,
+                &mut state,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(viewport: &Viewport) -> Vec<(usize, Range<usize>)> {
+        viewport.matches.clone()
+    }
+
+    #[test]
+    fn finds_every_match_in_every_line() {
+        let viewport = Viewport::new("one two\ntwo two\nthree").find("two");
+
+        assert_eq!(ranges(&viewport), [(0, 4..7), (1, 0..3), (1, 4..7)]);
+    }
+
+    #[test]
+    fn matches_do_not_overlap() {
+        let viewport = Viewport::new("aaaa").find("aa");
+
+        assert_eq!(ranges(&viewport), [(0, 0..2), (0, 2..4)]);
+    }
+
+    #[test]
+    fn ignores_case_unless_the_query_has_uppercase() {
+        let text = "Rust rust RUST";
+
+        assert_eq!(Viewport::new(text).find("rust").match_count(), 3);
+        assert_eq!(ranges(&Viewport::new(text).find("Rust")), [(0, 0..4)]);
+    }
+
+    #[test]
+    fn match_ranges_are_byte_offsets() {
+        let viewport = Viewport::new("café café").find("é c");
+
+        assert_eq!(ranges(&viewport), [(0, 3..7)]);
+        assert_eq!(viewport.columns(&viewport.matches[0]), 3..6);
+    }
+
+    #[test]
+    fn empty_query_matches_nothing() {
+        assert_eq!(Viewport::new("text").find("").match_count(), 0);
+    }
+
+    #[test]
+    fn search_shows_the_first_match_after_the_top() {
+        let text: Vec<String> = (0..100).map(|n| format!("line {n}")).collect();
+        let viewport = Viewport::new(text.join("\n"))
+            .with_size(80, 10)
+            .apply(Message::GoTo(50))
+            .apply(Message::StartSearch)
+            .apply(Message::Search(text_input::Message::Paste("line 7".into())));
+
+        // Found in "line 7" and "line 70" to "line 79", the first one after the top is shown
+        assert_eq!(viewport.match_count(), 11);
+        assert_eq!(viewport.current_match(), Some(1));
+        assert_eq!(viewport.offset(), (70, 0));
+
+        // Matches already shown don't scroll
+        let viewport = viewport
+            .apply(Message::ConfirmSearch)
+            .apply(Message::NextMatch);
+        assert_eq!(viewport.current_match(), Some(2));
+        assert_eq!(viewport.offset(), (70, 0));
+
+        let viewport = viewport
+            .apply(Message::PreviousMatch)
+            .apply(Message::PreviousMatch);
+        assert_eq!(viewport.current_match(), Some(0));
+        assert_eq!(viewport.offset(), (7, 0));
+
+        let viewport = viewport.apply(Message::PreviousMatch);
+        assert_eq!(viewport.current_match(), Some(10));
+    }
+
+    #[test]
+    fn cancelled_search_goes_back() {
+        let viewport = Viewport::new("a\nb\nc\nd\ne")
+            .with_size(80, 2)
+            .apply(Message::StartSearch)
+            .apply(Message::Search(text_input::Message::Insert('e')))
+            .apply(Message::CancelSearch);
+
+        assert_eq!(viewport.offset(), (0, 0));
+        assert_eq!(viewport.match_count(), 0);
+    }
+}
@@ -0,0 +1,188 @@
+//! Selectable list of rows kept outside of the component, for lists too long to be copied
+//! or built in full every frame.
+//!
+//! The list only knows how many rows there are, which one is selected and which one is
+//! shown first; the view asks the parent for the rows fitting in the area and nothing else:
+//!
+//! ```no_run
+//! # use ratatui::{buffer::Buffer, layout::Rect, widgets::{ListItem, StatefulWidget}};
+//! # use std::sync::Arc;
+//! # use teatui::components::virtual_list::VirtualList;
+//! # struct Model { results: VirtualList, lines: Arc<Vec<String>> }
+//! # fn view(model: &Model, area: Rect, buf: &mut Buffer) {
+//! let list = model.results.list(area.height, |index| ListItem::new(model.lines[index].as_str()));
+//! StatefulWidget::render(list, area, buf, &mut model.results.state(area.height));
+//! # }
+//! ```
+//!
+//! The rows are usually kept behind an `Arc` in the model, since the model is copied
+//! for every frame. Tables are drawn the same way, with ratatui's `Table` built from
+//! the rows of [`VirtualList::window`].
+//!
+//! The list moves with the messages of [`SelectList`](super::list::SelectList), given to
+//! [`VirtualList::update`], except [`list::Message::Filter`]: the parent filters its rows
+//! and gives their new number with [`VirtualList::with_len`].
+use super::list;
+use crate::update::Update;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{List, ListItem, ListState};
+use std::convert::Infallible;
+use std::ops::Range;
+
+/// Model of a list of rows stored by its parent.
+#[derive(Debug, Clone, Copy)]
+pub struct VirtualList {
+    len: usize,
+    selected: Option<usize>,
+    /// Index of the first row shown.
+    offset: usize,
+    page_size: usize,
+    wrap_around: bool,
+    highlight_style: Style,
+    highlight_symbol: &'static str,
+}
+
+impl Default for VirtualList {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl VirtualList {
+    /// Creates a list of `len` rows, the first one being selected.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            selected: (len > 0).then_some(0),
+            offset: 0,
+            page_size: 10,
+            wrap_around: false,
+            highlight_style: Style::new().add_modifier(Modifier::REVERSED),
+            highlight_symbol: "",
+        }
+    }
+
+    /// Changes the number of rows, keeping the selected index if it still exists.
+    pub fn with_len(self, len: usize) -> Self {
+        let last = len.checked_sub(1);
+        let selected = match self.selected {
+            Some(selected) => last.map(|last| selected.min(last)),
+            None if self.len == 0 => last.map(|_| 0),
+            None => None,
+        };
+
+        Self {
+            len,
+            selected,
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Rows moved by a page, the rows the list is expected to be shown in.
+    pub fn with_page_size(self, page_size: usize) -> Self {
+        Self {
+            page_size: page_size.max(1),
+            ..self
+        }
+        .scroll()
+    }
+
+    /// Selects the first row after the last one, and the other way around.
+    pub fn with_wrap_around(self, wrap_around: bool) -> Self {
+        Self {
+            wrap_around,
+            ..self
+        }
+    }
+
+    /// Style of the selected row, reversed by default.
+    pub fn with_highlight_style(self, highlight_style: impl Into<Style>) -> Self {
+        Self {
+            highlight_style: highlight_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_highlight_symbol(self, highlight_symbol: &'static str) -> Self {
+        Self {
+            highlight_symbol,
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Index of the selected row.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Index of the first row shown.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Indices of the rows shown in `height` rows: from the offset, scrolled further
+    /// if the list is drawn in fewer rows than the page size.
+    pub fn window(&self, height: u16) -> Range<usize> {
+        list::window(self.len, self.selected, self.offset, height)
+    }
+
+    /// State to render [`VirtualList::list`], or a table of the rows of
+    /// [`VirtualList::window`], with.
+    pub fn state(&self, height: u16) -> ListState {
+        let window = self.window(height);
+
+        ListState::default().with_selected(self.selected.map(|selected| selected - window.start))
+    }
+
+    /// Builds the ratatui list of the rows shown in `height` rows, `item` being called
+    /// with the index of each of them.
+    pub fn list<'a>(&self, height: u16, item: impl FnMut(usize) -> ListItem<'a>) -> List<'a> {
+        List::new(self.window(height).map(item))
+            .highlight_style(self.highlight_style)
+            .highlight_symbol(self.highlight_symbol)
+    }
+
+    /// Moves the selection like [`Component::update`](crate::component::Component::update) does for the other components.
+    ///
+    /// The list is not a component since it can't draw rows it doesn't have,
+    /// the parent draws them with [`VirtualList::list`] instead.
+    pub fn update(self, message: list::Message) -> Update<Self, Infallible> {
+        Update::Next(self.apply(message), None)
+    }
+
+    /// Moves the offset so the selected row is shown within the page.
+    fn scroll(self) -> Self {
+        let offset = list::scroll_offset(self.len, self.selected, self.offset, self.page_size);
+        Self { offset, ..self }
+    }
+
+    pub(crate) fn apply(self, message: list::Message) -> Self {
+        use list::Message;
+
+        let Some(last) = self.len.checked_sub(1) else {
+            return self;
+        };
+
+        let selected = match message {
+            Message::Select(index) => (index <= last).then_some(index).or(self.selected),
+            message => list::navigate(
+                &message,
+                self.selected,
+                last,
+                self.page_size,
+                self.wrap_around,
+            ),
+        };
+
+        Self { selected, ..self }.scroll()
+    }
+}
@@ -0,0 +1,200 @@
+//! Runtime diagnostics overlay drawn on top of the application view.
+//!
+//! When enabled through [`crate::options::ProgramOptions::with_diagnostics`],
+//! pressing the toggle key shows a small panel with the time spent rendering the last frame,
+//! the frames drawn per second, the messages and effects waiting to be processed
+//! and the last messages received by the update actor.
+use crate::queue::Depth;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often the view is redrawn while the overlay is visible,
+/// so the statistics keep updating even if the model doesn't change.
+pub(crate) const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+const HUD_WIDTH: u16 = 48;
+
+/// Settings of the diagnostics overlay.
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    toggle_key: KeyCode,
+    history: usize,
+    visible: bool,
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::F(12),
+            history: 5,
+            visible: false,
+        }
+    }
+}
+
+impl Diagnostics {
+    /// Key that shows and hides the overlay, `F12` by default.
+    ///
+    /// The key press is consumed by the runtime and never reaches the update function.
+    pub fn with_toggle_key(self, toggle_key: KeyCode) -> Self {
+        Self { toggle_key, ..self }
+    }
+
+    /// Number of recent messages listed in the overlay, 5 by default.
+    pub fn with_history(self, history: usize) -> Self {
+        Self { history, ..self }
+    }
+
+    /// Whether the overlay is shown when the program starts, hidden by default.
+    pub fn visible(self, visible: bool) -> Self {
+        Self { visible, ..self }
+    }
+}
+
+/// Statistics shared between the actors and rendered by the overlay.
+pub(crate) struct Monitor {
+    toggle_key: KeyCode,
+    history: usize,
+    visible: AtomicBool,
+    pending_messages: Depth,
+    pending_effects: Depth,
+    stats: Mutex<Stats>,
+}
+
+#[derive(Default)]
+struct Stats {
+    render_time: Duration,
+    frames: VecDeque<Instant>,
+    messages: VecDeque<String>,
+}
+
+impl Monitor {
+    pub(crate) fn new(
+        settings: Diagnostics,
+        pending_messages: Depth,
+        pending_effects: Depth,
+    ) -> Self {
+        Self {
+            toggle_key: settings.toggle_key,
+            history: settings.history,
+            visible: AtomicBool::new(settings.visible),
+            pending_messages,
+            pending_effects,
+            stats: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn is_visible(&self) -> bool {
+        self.visible.load(Ordering::Relaxed)
+    }
+
+    /// Flips the visibility of the overlay if the event is a press of the toggle key.
+    ///
+    /// Returns whether the event was consumed.
+    pub(crate) fn toggle(&self, event: &Event) -> bool {
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) if *code == self.toggle_key => {
+                self.visible.fetch_xor(true, Ordering::Relaxed);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn message_received(&self, message: String) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+
+        stats.messages.push_back(message);
+
+        while stats.messages.len() > self.history {
+            stats.messages.pop_front();
+        }
+    }
+
+    /// Records a drawn frame, `refresh` being set when the same model was drawn again
+    /// to update the overlay, which doesn't count towards the frame rate.
+    pub(crate) fn frame_rendered(&self, render_time: Duration, refresh: bool) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+
+        stats.render_time = render_time;
+
+        if !refresh {
+            stats.frames.push_back(now);
+        }
+
+        while stats
+            .frames
+            .front()
+            .is_some_and(|frame| now.duration_since(*frame) > Duration::from_secs(1))
+        {
+            stats.frames.pop_front();
+        }
+    }
+
+    pub(crate) fn hud(&self) -> Hud {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+
+        Hud {
+            render_time: stats.render_time,
+            fps: stats.frames.len(),
+            pending_messages: self.pending_messages.get(),
+            pending_effects: self.pending_effects.get(),
+            messages: stats.messages.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Snapshot of the statistics, rendered in the top right corner of the area.
+pub(crate) struct Hud {
+    render_time: Duration,
+    fps: usize,
+    pending_messages: usize,
+    pending_effects: usize,
+    messages: Vec<String>,
+}
+
+impl Widget for Hud {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![
+            Line::from(format!(
+                "render {:.2}ms | {} fps",
+                self.render_time.as_secs_f64() * 1000.0,
+                self.fps
+            )),
+            Line::from(format!(
+                "pending messages {} | effects {}",
+                self.pending_messages, self.pending_effects
+            )),
+        ];
+
+        if !self.messages.is_empty() {
+            lines.push(Line::from("last messages:".bold()));
+            lines.extend(self.messages.into_iter().rev().map(Line::from));
+        }
+
+        let width = HUD_WIDTH.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let area = Rect::new(area.right() - width, area.y, width, height);
+
+        Clear.render(area, buf);
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title(" diagnostics ".bold()).yellow())
+            .render(area, buf);
+    }
+}
@@ -1,9 +1,15 @@
 //! Actor responsible of processing side effects sent by the update actor.
-use std::sync::mpsc::{Receiver, SendError, Sender};
+use crate::queue::{Receiver, Sender};
+use crate::update::Input;
+use std::fmt::Debug;
+use std::sync::mpsc::SendError;
 
 #[cfg(feature = "tokio")]
 use std::future::Future;
 
+#[cfg(all(feature = "tokio", feature = "tracing"))]
+use tracing::Instrument;
+
 #[derive(thiserror::Error, Debug)]
 pub enum EffectsError<M> {
     #[error("Failed to send message to update process")]
@@ -14,10 +20,11 @@ pub enum EffectsError<M> {
 pub(crate) fn run<M, Msg, Eff, F>(
     effects_fn: F,
     rx: Receiver<(M, Eff)>,
-    tx: Sender<Msg>,
+    tx: Sender<Input<Msg>>,
 ) -> Result<(), EffectsError<Msg>>
 where
     Msg: Send + Sync + 'static,
+    Eff: Debug,
     F: Fn(M, Eff) -> Option<Msg>,
 {
     loop {
@@ -25,8 +32,11 @@ where
             return Ok(());
         };
 
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("effect", ?effect, pending = rx.depth()).entered();
+
         if let Some(msg) = effects_fn(model, effect) {
-            tx.send(msg)?;
+            tx.send_message(msg)?;
         }
     }
 }
@@ -35,12 +45,12 @@ where
 pub(crate) fn run_async<M, Msg, Eff, F, Fut>(
     effects_fn: F,
     rx: Receiver<(M, Eff)>,
-    tx: Sender<Msg>,
+    tx: Sender<Input<Msg>>,
 ) -> Result<(), EffectsError<Msg>>
 where
     M: Send + Sync + 'static,
     Msg: Send + Sync + 'static,
-    Eff: Send + Sync + 'static,
+    Eff: Debug + Send + Sync + 'static,
     Fut: Future<Output = Option<Msg>> + Send,
     F: Fn(M, Eff) -> Fut + Send + Sync + 'static,
 {
@@ -55,11 +65,17 @@ where
                 break;
             };
 
+            #[cfg(feature = "tracing")]
+            let span = tracing::debug_span!("effect", ?effect, pending = rx.depth());
+
             // We spawn the effect in the tokio reactor so they can run concurrently
             let fut = effects_fn(model, effect);
 
+            #[cfg(feature = "tracing")]
+            let fut = fut.instrument(span);
+
             if let Some(msg) = fut.await {
-                let _ = tx.send(msg);
+                let _ = tx.send_message(msg);
             }
         }
     });
@@ -1,7 +1,12 @@
 //! Actor responsible of reading terminal input events.
-use crossterm::event;
+use crate::queue::Sender;
+use crate::update::Input;
+use crossterm::event::{self, Event};
 use std::fmt::Debug;
-use std::sync::mpsc::{SendError, Sender};
+use std::sync::mpsc::SendError;
+
+#[cfg(feature = "serde")]
+use crate::session::{Session, SessionError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum EventLoopError<M> {
@@ -9,14 +14,50 @@ pub enum EventLoopError<M> {
     MessageSend(#[from] SendError<M>),
     #[error("Failed to read crossterm event")]
     EventRead(#[from] std::io::Error),
+    #[cfg(feature = "serde")]
+    #[error("Failed to record or replay the input session")]
+    Session(#[from] SessionError),
 }
 
-pub(crate) fn run<M>(tx: Sender<M>) -> Result<(), EventLoopError<M>>
+pub(crate) fn run<M>(
+    #[cfg(feature = "serde")] mut session: Session,
+    tx: Sender<Input<M>>,
+) -> Result<(), EventLoopError<M>>
 where
-    M: From<crossterm::event::Event> + Sync + Send + 'static,
+    M: From<Event> + Sync + Send + 'static,
 {
+    // Recorded events are sent first, then the terminal takes over
+    #[cfg(feature = "serde")]
+    if let Some(replay) = session.replay.take() {
+        for event in replay {
+            let event = event?;
+            session.record(&event)?;
+            send(&tx, event)?;
+        }
+    }
+
     loop {
-        let message = M::from(event::read()?);
-        tx.send(message)?;
+        let event = event::read()?;
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(?event, "terminal event read");
+
+        #[cfg(feature = "serde")]
+        session.record(&event)?;
+
+        send(&tx, event)?;
     }
 }
+
+/// Events are converted into messages by the update actor,
+/// but if it is gone we still report the message the event would have become.
+fn send<M>(tx: &Sender<Input<M>>, event: Event) -> Result<(), SendError<M>>
+where
+    M: From<Event>,
+{
+    tx.send(Input::Event(event))
+        .map_err(|SendError(input)| match input {
+            Input::Event(event) => SendError(M::from(event)),
+            Input::Message(message) => SendError(message),
+        })
+}
@@ -0,0 +1,137 @@
+//! Tracks which component, among the ones on screen, receives keyboard input.
+//!
+//! A [`Focus`] lives in the model next to the components it switches between.
+//! Once registered with [`crate::options::ProgramOptions::with_focus`], the runtime cycles it
+//! with `tab` and `shift+tab`, and [`crate::options::ProgramOptions::with_focus_keymap`]
+//! resolves key presses through the keymap of the focused component first.
+use crate::keymap::KeyCombo;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::style::{Color, Style};
+
+/// Ordered set of focusable ids, at most one of them being focused.
+#[derive(Debug, Clone)]
+pub struct Focus<Id> {
+    ids: Vec<Id>,
+    current: Option<usize>,
+    next_key: KeyCombo,
+    previous_key: KeyCombo,
+    focused_style: Style,
+    unfocused_style: Style,
+}
+
+impl<Id> Focus<Id>
+where
+    Id: PartialEq,
+{
+    /// Creates a focus cycling through `ids` in order, the first one being focused.
+    pub fn new(ids: impl IntoIterator<Item = Id>) -> Self {
+        let ids: Vec<_> = ids.into_iter().collect();
+        let current = (!ids.is_empty()).then_some(0);
+
+        Self {
+            ids,
+            current,
+            next_key: KeyCombo::from(KeyCode::Tab),
+            previous_key: KeyCombo::new(KeyCode::BackTab, KeyModifiers::NONE),
+            focused_style: Style::new().fg(Color::Cyan),
+            unfocused_style: Style::new().fg(Color::DarkGray),
+        }
+    }
+
+    /// Keys moving the focus forward and backward, `tab` and `shift+tab` by default.
+    pub fn with_keys(self, next: KeyCombo, previous: KeyCombo) -> Self {
+        Self {
+            next_key: next,
+            previous_key: previous,
+            ..self
+        }
+    }
+
+    /// Styles returned by [`Focus::style`], cyan and dark gray by default.
+    pub fn with_styles(self, focused: Style, unfocused: Style) -> Self {
+        Self {
+            focused_style: focused,
+            unfocused_style: unfocused,
+            ..self
+        }
+    }
+
+    /// Id currently focused, if any.
+    pub fn current(&self) -> Option<&Id> {
+        self.current.map(|index| &self.ids[index])
+    }
+
+    pub fn is_focused(&self, id: &Id) -> bool {
+        self.current() == Some(id)
+    }
+
+    pub fn ids(&self) -> &[Id] {
+        &self.ids
+    }
+
+    /// Focuses the given id, leaving the focus unchanged if it is unknown.
+    pub fn focus(self, id: &Id) -> Self {
+        match self.ids.iter().position(|candidate| candidate == id) {
+            Some(index) => Self {
+                current: Some(index),
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    /// Removes the focus, so no id is focused until the next focus request.
+    pub fn blur(self) -> Self {
+        Self {
+            current: None,
+            ..self
+        }
+    }
+
+    /// Focuses the next id, wrapping around after the last one.
+    pub fn next(self) -> Self {
+        let len = self.ids.len();
+        let current = match self.current {
+            _ if len == 0 => None,
+            Some(index) => Some((index + 1) % len),
+            None => Some(0),
+        };
+
+        Self { current, ..self }
+    }
+
+    /// Focuses the previous id, wrapping around before the first one.
+    pub fn previous(self) -> Self {
+        let len = self.ids.len();
+        let current = match self.current {
+            _ if len == 0 => None,
+            Some(index) => Some((index + len - 1) % len),
+            None => Some(len - 1),
+        };
+
+        Self { current, ..self }
+    }
+
+    /// Style hint for the view, usually applied to the border of the component.
+    pub fn style(&self, id: &Id) -> Style {
+        if self.is_focused(id) {
+            self.focused_style
+        } else {
+            self.unfocused_style
+        }
+    }
+
+    /// Moves the focus if `key` is one of the cycling keys.
+    pub(crate) fn cycle(&self, key: KeyCombo) -> Option<Self>
+    where
+        Id: Clone,
+    {
+        if key == self.next_key {
+            Some(self.clone().next())
+        } else if key == self.previous_key {
+            Some(self.clone().previous())
+        } else {
+            None
+        }
+    }
+}
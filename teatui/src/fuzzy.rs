@@ -0,0 +1,254 @@
+//! Fuzzy matching of a pattern against text, as in most pickers: the characters of the pattern
+//! must appear in the text in order, but not necessarily next to each other.
+//!
+//! Matches are scored so the best ones can be shown first: consecutive characters and
+//! characters at the start of words score higher, gaps lower. The pattern is matched
+//! regardless of case unless it has uppercase characters.
+//!
+//! [`crate::components::list::SelectList::with_fuzzy_filter`] ranks the items of a list
+//! with it, and [`highlight`] shows which characters matched.
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+/// Score of a matched character.
+const MATCH: i64 = 16;
+/// Bonus of a character following the previous matched one.
+const CONSECUTIVE: i64 = 8;
+/// Bonus of a character starting a word.
+const BOUNDARY: i64 = 8;
+/// Bonus of an uppercase character following a lowercase one, as in `camelCase`.
+const CAMEL_CASE: i64 = 6;
+/// Penalty of every character skipped between two matched ones.
+const GAP: i64 = 2;
+/// Penalty of every character before the first matched one, up to [`MAX_LEADING`].
+const LEADING: i64 = 1;
+const MAX_LEADING: i64 = 8;
+
+/// How well a pattern matches a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Higher is better, only meaningful compared to the score of other texts.
+    pub score: i64,
+    /// Byte offsets of the characters of the text matching the pattern, in order.
+    pub indices: Vec<usize>,
+}
+
+/// Matches `pattern` against `text`, returning `None` if some characters of the pattern are missing.
+///
+/// An empty pattern matches every text.
+pub fn matches(pattern: &str, text: &str) -> Option<Match> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let case_sensitive = pattern.iter().any(|c| c.is_uppercase());
+    let eq = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    if pattern.is_empty() {
+        return Some(Match {
+            score: 0,
+            indices: vec![],
+        });
+    }
+
+    // Earliest position at which the whole pattern has been seen
+    let mut matched = 0;
+    let mut end = None;
+
+    for (position, &(_, c)) in chars.iter().enumerate() {
+        if eq(c, pattern[matched]) {
+            matched += 1;
+
+            if matched == pattern.len() {
+                end = Some(position);
+                break;
+            }
+        }
+    }
+
+    let end = end?;
+
+    // Going backwards from there finds the shortest window holding the pattern
+    let mut start = end;
+    let mut remaining = pattern.len();
+
+    for position in (0..=end).rev() {
+        if eq(chars[position].1, pattern[remaining - 1]) {
+            remaining -= 1;
+
+            if remaining == 0 {
+                start = position;
+                break;
+            }
+        }
+    }
+
+    let mut score = -(start as i64 * LEADING).min(MAX_LEADING);
+    let mut indices = Vec::with_capacity(pattern.len());
+    let mut previous: Option<usize> = None;
+    let mut next = 0;
+
+    for (position, &(index, c)) in chars.iter().enumerate().take(end + 1).skip(start) {
+        if next == pattern.len() || !eq(c, pattern[next]) {
+            continue;
+        }
+
+        score += MATCH;
+
+        match previous {
+            Some(previous) if previous + 1 == position => score += CONSECUTIVE,
+            Some(previous) => score -= (position - previous - 1) as i64 * GAP,
+            None => {}
+        }
+
+        let before = position.checked_sub(1).map(|before| chars[before].1);
+
+        score += match before {
+            None => BOUNDARY,
+            Some(before) if !before.is_alphanumeric() => BOUNDARY,
+            Some(before) if before.is_lowercase() && c.is_uppercase() => CAMEL_CASE,
+            Some(_) => 0,
+        };
+
+        indices.push(index);
+        previous = Some(position);
+        next += 1;
+    }
+
+    Some(Match { score, indices })
+}
+
+/// Matches `pattern` against every text, returning the index and match of the ones matching,
+/// best first.
+///
+/// Texts with the same score keep their order.
+pub fn rank<'a>(pattern: &str, texts: impl IntoIterator<Item = &'a str>) -> Vec<(usize, Match)> {
+    let mut ranked: Vec<_> = texts
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, text)| matches(pattern, text).map(|found| (index, found)))
+        .collect();
+
+    ranked.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+    ranked
+}
+
+/// Line showing `text` with the characters at `indices` patched with `matched`.
+pub fn highlight<'a>(text: &'a str, indices: &[usize], style: Style, matched: Style) -> Line<'a> {
+    let mut spans = vec![];
+    let mut indices = indices.iter().peekable();
+    let mut start = 0;
+    let mut highlighted = false;
+
+    for (index, _) in text.char_indices() {
+        let is_match = indices.next_if_eq(&&index).is_some();
+
+        if is_match != highlighted && index > start {
+            let span_style = if highlighted {
+                style.patch(matched)
+            } else {
+                style
+            };
+            spans.push(Span::styled(&text[start..index], span_style));
+            start = index;
+        }
+
+        highlighted = is_match;
+    }
+
+    let span_style = if highlighted {
+        style.patch(matched)
+    } else {
+        style
+    };
+    spans.push(Span::styled(&text[start..], span_style));
+
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(pattern: &str, text: &str) -> Option<Vec<usize>> {
+        matches(pattern, text).map(|found| found.indices)
+    }
+
+    fn score(pattern: &str, text: &str) -> i64 {
+        matches(pattern, text).unwrap().score
+    }
+
+    #[test]
+    fn characters_must_appear_in_order() {
+        assert_eq!(indices("abc", "a_b_c"), Some(vec![0, 2, 4]));
+        assert_eq!(indices("cba", "a_b_c"), None);
+        assert_eq!(indices("abcd", "abc"), None);
+    }
+
+    #[test]
+    fn empty_pattern_matches_everything() {
+        assert_eq!(
+            matches("", "anything"),
+            Some(Match {
+                score: 0,
+                indices: vec![]
+            })
+        );
+    }
+
+    #[test]
+    fn case_is_ignored_unless_the_pattern_has_uppercase() {
+        assert_eq!(indices("fb", "FooBar"), Some(vec![0, 3]));
+        assert_eq!(indices("FB", "FooBar"), Some(vec![0, 3]));
+        assert_eq!(indices("Fb", "FooBar"), None);
+    }
+
+    #[test]
+    fn matches_the_shortest_window() {
+        assert_eq!(indices("ab", "a___ab"), Some(vec![4, 5]));
+    }
+
+    #[test]
+    fn indices_are_byte_offsets() {
+        assert_eq!(indices("éa", "éta"), Some(vec![0, 3]));
+    }
+
+    #[test]
+    fn consecutive_and_word_starts_score_higher() {
+        assert!(score("abc", "abc") > score("abc", "a_b_c"));
+        assert!(score("fb", "foo_bar") > score("fb", "xfxb"));
+        assert!(score("fb", "fooBar") > score("fb", "foobar"));
+        assert!(score("x", "x") > score("x", "__x"));
+    }
+
+    #[test]
+    fn ranks_best_first_keeping_ties_in_order() {
+        let ranked: Vec<usize> = rank("ab", ["xaxb", "ab", "nope", "ab"])
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+
+        assert_eq!(ranked, [1, 3, 0]);
+    }
+
+    #[test]
+    fn highlights_matched_characters() {
+        let matched = Style::new().bold();
+        let line = highlight("abcd", &[1, 2], Style::new(), matched);
+
+        let spans: Vec<(&str, Style)> = line
+            .spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style))
+            .collect();
+
+        assert_eq!(
+            spans,
+            [("a", Style::new()), ("bc", matched), ("d", Style::new())]
+        );
+    }
+}
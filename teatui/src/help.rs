@@ -0,0 +1,198 @@
+//! Help generated from the bindings of a [`Keymap`], so it never drifts from the real keys.
+//!
+//! Only the bindings active in the current mode are shown. Bindings sharing a description
+//! are shown together (`q/esc quit`), and bindings with an empty description are hidden.
+use crate::keymap::Keymap;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
+use std::collections::HashSet;
+
+/// Space between two columns of the full help.
+const COLUMN_GAP: u16 = 3;
+
+/// Widget rendering the active bindings of a keymap.
+///
+/// [`Help::short`] renders a single line, usually in a footer,
+/// while [`Help::full`] spreads the bindings in as many columns as needed.
+pub struct Help<'a, Msg> {
+    keymaps: Vec<&'a Keymap<Msg>>,
+    full: bool,
+    key_style: Style,
+    description_style: Style,
+    separator: &'a str,
+}
+
+impl<'a, Msg> Help<'a, Msg> {
+    /// One line help, truncated with `…` when it doesn't fit.
+    pub fn short(keymap: &'a Keymap<Msg>) -> Self {
+        Self {
+            keymaps: vec![keymap],
+            full: false,
+            key_style: Style::new().bold(),
+            description_style: Style::new().dark_gray(),
+            separator: " • ",
+        }
+    }
+
+    /// Multi-column help, filling every column from top to bottom.
+    pub fn full(keymap: &'a Keymap<Msg>) -> Self {
+        Self {
+            full: true,
+            ..Self::short(keymap)
+        }
+    }
+
+    pub fn with_key_style(self, key_style: impl Into<Style>) -> Self {
+        Self {
+            key_style: key_style.into(),
+            ..self
+        }
+    }
+
+    pub fn with_description_style(self, description_style: impl Into<Style>) -> Self {
+        Self {
+            description_style: description_style.into(),
+            ..self
+        }
+    }
+
+    /// Shows the bindings of another keymap after the ones of the keymaps already added,
+    /// hiding the keys they shadow.
+    ///
+    /// This is meant for the keymap registered with [`crate::options::ProgramOptions::with_keymap`],
+    /// added to the help built from the keymap of the focused component.
+    pub fn with_keymap(mut self, keymap: &'a Keymap<Msg>) -> Self {
+        self.keymaps.push(keymap);
+        self
+    }
+
+    /// Text between two bindings of the short help, ` • ` by default.
+    pub fn with_separator(self, separator: &'a str) -> Self {
+        Self { separator, ..self }
+    }
+
+    /// Keys and description of every binding to show, in the order of the keymap.
+    fn entries(&self) -> Vec<(String, &'a str)> {
+        let mut entries: Vec<(Vec<String>, &str)> = vec![];
+        // Bindings shadowed by the current mode, or by a previous keymap, are not active
+        let mut seen = HashSet::new();
+
+        let bindings = self
+            .keymaps
+            .iter()
+            .flat_map(|keymap| keymap.active_bindings());
+
+        for binding in bindings {
+            if !seen.insert(binding.keys()) || binding.description().is_empty() {
+                continue;
+            }
+
+            let keys = binding
+                .keys()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            match entries
+                .iter_mut()
+                .find(|(_, description)| *description == binding.description())
+            {
+                Some((all_keys, _)) => all_keys.push(keys),
+                None => entries.push((vec![keys], binding.description())),
+            }
+        }
+
+        entries
+            .into_iter()
+            .map(|(keys, description)| (keys.join("/"), description))
+            .collect()
+    }
+
+    fn render_short(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![];
+        let mut width = 0;
+        let entries = self.entries();
+        let count = entries.len();
+        // Room left after a binding for the ellipsis replacing the following ones
+        let ellipsis_width = Span::raw(format!("{}…", self.separator)).width();
+
+        for (index, (keys, description)) in entries.into_iter().enumerate() {
+            let separator = if index == 0 { "" } else { self.separator };
+            let entry = [
+                Span::styled(separator, self.description_style),
+                Span::styled(keys, self.key_style),
+                Span::styled(" ", self.description_style),
+                Span::styled(description, self.description_style),
+            ];
+            let entry_width: usize = entry.iter().map(Span::width).sum();
+            let reserved = if index + 1 < count { ellipsis_width } else { 0 };
+
+            if width + entry_width + reserved > area.width as usize {
+                spans.push(Span::styled(
+                    format!("{separator}…"),
+                    self.description_style,
+                ));
+                break;
+            }
+
+            width += entry_width;
+            spans.extend(entry);
+        }
+
+        Line::from(spans).render(area, buf);
+    }
+
+    fn render_full(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let entries = self.entries();
+        let mut x = area.x;
+
+        for column in entries.chunks(area.height as usize) {
+            let keys_width = column
+                .iter()
+                .map(|(keys, _)| Span::raw(keys.as_str()).width())
+                .max()
+                .unwrap_or_default() as u16;
+
+            let mut column_width = 0;
+
+            for ((keys, description), y) in column.iter().zip(area.y..) {
+                let keys = Span::styled(keys.as_str(), self.key_style);
+                let padding = " ".repeat(keys_width as usize - keys.width() + 1);
+                let line = Line::from(vec![
+                    keys,
+                    Span::raw(padding),
+                    Span::styled(*description, self.description_style),
+                ]);
+
+                column_width = column_width.max(line.width() as u16);
+
+                let row = Rect::new(x, y, area.right().saturating_sub(x), 1);
+                line.render(row, buf);
+            }
+
+            x = x.saturating_add(column_width + COLUMN_GAP);
+
+            if x >= area.right() {
+                break;
+            }
+        }
+    }
+}
+
+impl<Msg> Widget for Help<'_, Msg> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.full {
+            self.render_full(area, buf);
+        } else {
+            self.render_short(area, buf);
+        }
+    }
+}
@@ -0,0 +1,567 @@
+//! Declarative key bindings with modes and multi-key sequences.
+//!
+//! A [`Keymap`] maps key sequences like `"ctrl+c"`, `"g g"` or `"<leader>w"` to messages.
+//! It is meant to live in the model, so the update function can switch modes
+//! with [`Keymap::with_mode`] and the view can render the active bindings.
+//!
+//! Once registered with [`crate::options::ProgramOptions::with_keymap`], the runtime resolves
+//! every key press against the keymap of the current model before calling the update function.
+//! Keys that are not bound to anything are converted into messages with `Msg::from` as usual.
+#[cfg(feature = "config")]
+pub mod config;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Name of the mode a keymap starts in.
+pub const DEFAULT_MODE: &str = "normal";
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum KeymapError {
+    #[error("Invalid key '{0}'")]
+    InvalidKey(String),
+    #[error("Unknown modifier '{0}'")]
+    UnknownModifier(String),
+    #[error("Empty key sequence")]
+    EmptySequence,
+}
+
+/// A single key press: a key code together with the modifiers held.
+///
+/// It can be parsed from strings like `"q"`, `"G"`, `"ctrl+c"`, `"alt+enter"`, `"shift+tab"` or `"f5"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyCombo {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // The case of a character already tells whether shift was held,
+        // so shift is dropped to make "G" and "shift+g" the same key.
+        let (code, modifiers) = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => (
+                KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers - KeyModifiers::SHIFT,
+            ),
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT)
+            }
+            KeyCode::BackTab => (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT),
+            code => (code, modifiers),
+        };
+
+        Self { code, modifiers }
+    }
+}
+
+impl From<KeyEvent> for KeyCombo {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyCombo {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = KeymapError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+
+        // A trailing "+" is the plus key itself, as in "+" or "ctrl++"
+        let (modifiers, key) = match spec.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+                (rest.strip_suffix('+').unwrap_or(rest), "+")
+            }
+            _ => spec.rsplit_once('+').unwrap_or(("", spec)),
+        };
+
+        let modifiers = modifiers
+            .split('+')
+            .filter(|modifier| !modifier.is_empty())
+            .try_fold(KeyModifiers::NONE, |acc, modifier| {
+                parse_modifier(modifier).map(|modifier| acc | modifier)
+            })?;
+
+        Ok(Self::new(parse_code(key)?, modifiers))
+    }
+}
+
+fn parse_modifier(modifier: &str) -> Result<KeyModifiers, KeymapError> {
+    match modifier.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Ok(KeyModifiers::CONTROL),
+        "alt" | "meta" => Ok(KeyModifiers::ALT),
+        "shift" => Ok(KeyModifiers::SHIFT),
+        "super" | "cmd" => Ok(KeyModifiers::SUPER),
+        _ => Err(KeymapError::UnknownModifier(modifier.to_string())),
+    }
+}
+
+fn parse_code(key: &str) -> Result<KeyCode, KeymapError> {
+    let mut chars = key.chars();
+
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+
+    let code = match key.to_ascii_lowercase().as_str() {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        "plus" => KeyCode::Char('+'),
+        name => match name.strip_prefix('f').map(str::parse::<u8>) {
+            Some(Ok(n)) if (1..=24).contains(&n) => KeyCode::F(n),
+            _ => return Err(KeymapError::InvalidKey(key.to_string())),
+        },
+    };
+
+    Ok(code)
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SUPER, "super"),
+            (KeyModifiers::SHIFT, "shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "shift+tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            code => write!(f, "{code}"),
+        }
+    }
+}
+
+/// Parses a whitespace separated sequence of keys, replacing `<leader>` with the leader key.
+fn parse_sequence(spec: &str, leader: KeyCombo) -> Result<Vec<KeyCombo>, KeymapError> {
+    let mut keys = vec![];
+
+    for token in spec.split_whitespace() {
+        match token.strip_prefix("<leader>") {
+            Some(rest) => {
+                keys.push(leader);
+                if !rest.is_empty() {
+                    keys.push(rest.parse()?);
+                }
+            }
+            None => keys.push(token.parse()?),
+        }
+    }
+
+    if keys.is_empty() {
+        return Err(KeymapError::EmptySequence);
+    }
+
+    Ok(keys)
+}
+
+/// A key sequence bound to a message.
+#[derive(Debug, Clone)]
+pub struct Binding<Msg> {
+    mode: Option<String>,
+    keys: Vec<KeyCombo>,
+    message: Msg,
+    description: String,
+}
+
+impl<Msg> Binding<Msg> {
+    /// Mode the binding belongs to, or `None` if it is active in every mode.
+    pub fn mode(&self) -> Option<&str> {
+        self.mode.as_deref()
+    }
+
+    pub fn keys(&self) -> &[KeyCombo] {
+        &self.keys
+    }
+
+    pub fn message(&self) -> &Msg {
+        &self.message
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Result of looking up a sequence of pressed keys in a keymap.
+pub(crate) enum Lookup<Msg> {
+    /// The keys match a binding and no other binding starts with them.
+    Matched(Msg),
+    /// Longer bindings start with the keys, so we need to wait for the next key.
+    /// If it doesn't arrive in time, the binding matching the keys exactly (if any) is used.
+    Pending {
+        fallback: Option<Msg>,
+        timeout: Duration,
+    },
+    NotFound,
+}
+
+/// A set of key bindings, organized in modes.
+///
+/// Bindings added with [`Keymap::bind`] are active in every mode,
+/// while the ones added with [`Keymap::bind_in`] are only active in their mode
+/// and take precedence over the global ones.
+#[derive(Debug)]
+pub struct Keymap<Msg> {
+    mode: String,
+    leader: KeyCombo,
+    timeout: Duration,
+    bindings: Arc<Vec<Binding<Msg>>>,
+}
+
+impl<Msg> Clone for Keymap<Msg> {
+    fn clone(&self) -> Self {
+        Self {
+            mode: self.mode.clone(),
+            leader: self.leader,
+            timeout: self.timeout,
+            bindings: self.bindings.clone(),
+        }
+    }
+}
+
+impl<Msg> Default for Keymap<Msg> {
+    fn default() -> Self {
+        Self {
+            mode: DEFAULT_MODE.to_string(),
+            leader: KeyCombo::from(KeyCode::Char('\\')),
+            timeout: Duration::from_secs(1),
+            bindings: Arc::default(),
+        }
+    }
+}
+
+impl<Msg> Keymap<Msg> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current mode of the keymap.
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    /// Returns the same keymap in another mode.
+    ///
+    /// Switching modes is cheap, the bindings are shared between both keymaps.
+    pub fn with_mode(self, mode: impl Into<String>) -> Self {
+        Self {
+            mode: mode.into(),
+            ..self
+        }
+    }
+
+    /// Sets the key `<leader>` stands for in the sequences bound afterwards, `\` by default.
+    pub fn with_leader(self, leader: KeyCombo) -> Self {
+        Self { leader, ..self }
+    }
+
+    /// Time to wait for the next key of a sequence, one second by default.
+    ///
+    /// When it runs out, the keys pressed so far trigger their own binding, if they have one.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// All the bindings, in every mode.
+    pub fn bindings(&self) -> impl Iterator<Item = &Binding<Msg>> {
+        self.bindings.iter()
+    }
+
+    /// Bindings active in the current mode, the mode specific ones first.
+    pub fn active_bindings(&self) -> impl Iterator<Item = &Binding<Msg>> {
+        let in_mode = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.mode() == Some(self.mode()));
+
+        let global = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.mode().is_none());
+
+        in_mode.chain(global)
+    }
+}
+
+impl<Msg> Keymap<Msg>
+where
+    Msg: Clone,
+{
+    /// Binds a key sequence to a message in every mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is not a valid key sequence, use [`Keymap::try_bind`]
+    /// for sequences which are not known at compile time.
+    pub fn bind(self, keys: &str, message: Msg, description: &str) -> Self {
+        self.try_bind(keys, message, description)
+            .unwrap_or_else(|error| panic!("Invalid key sequence '{keys}': {error}"))
+    }
+
+    /// Binds a key sequence to a message, only active in the given mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is not a valid key sequence, use [`Keymap::try_bind_in`]
+    /// for sequences which are not known at compile time.
+    pub fn bind_in(self, mode: &str, keys: &str, message: Msg, description: &str) -> Self {
+        self.try_bind_in(mode, keys, message, description)
+            .unwrap_or_else(|error| panic!("Invalid key sequence '{keys}': {error}"))
+    }
+
+    /// Same as [`Keymap::bind`], returning an error if the sequence is not valid.
+    pub fn try_bind(
+        self,
+        keys: &str,
+        message: Msg,
+        description: &str,
+    ) -> Result<Self, KeymapError> {
+        self.add(None, keys, message, description)
+    }
+
+    /// Same as [`Keymap::bind_in`], returning an error if the sequence is not valid.
+    pub fn try_bind_in(
+        self,
+        mode: &str,
+        keys: &str,
+        message: Msg,
+        description: &str,
+    ) -> Result<Self, KeymapError> {
+        self.add(Some(mode.to_string()), keys, message, description)
+    }
+
+    fn add(
+        mut self,
+        mode: Option<String>,
+        keys: &str,
+        message: Msg,
+        description: &str,
+    ) -> Result<Self, KeymapError> {
+        self.push(mode, keys, message, description)?;
+        Ok(self)
+    }
+
+    fn push(
+        &mut self,
+        mode: Option<String>,
+        keys: &str,
+        message: Msg,
+        description: &str,
+    ) -> Result<(), KeymapError> {
+        let binding = Binding {
+            mode,
+            keys: parse_sequence(keys, self.leader)?,
+            message,
+            description: description.to_string(),
+        };
+
+        Arc::make_mut(&mut self.bindings).push(binding);
+
+        Ok(())
+    }
+
+    /// Looks up the keys pressed so far in the bindings of the current mode.
+    pub(crate) fn lookup(&self, keys: &[KeyCombo]) -> Lookup<Msg> {
+        let mut exact = None;
+        let mut longer = false;
+
+        for binding in self.active_bindings() {
+            if binding.keys == keys {
+                exact = exact.or(Some(binding));
+            } else if binding.keys.starts_with(keys) {
+                longer = true;
+            }
+        }
+
+        match (exact, longer) {
+            (Some(binding), false) => Lookup::Matched(binding.message.clone()),
+            (exact, true) => Lookup::Pending {
+                fallback: exact.map(|binding| binding.message.clone()),
+                timeout: self.timeout,
+            },
+            (None, false) => Lookup::NotFound,
+        }
+    }
+}
+
+/// Whether a key event should be resolved through the keymap.
+pub(crate) fn is_press(event: &KeyEvent) -> bool {
+    matches!(event.kind, KeyEventKind::Press | KeyEventKind::Repeat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(code: KeyCode, modifiers: KeyModifiers) -> KeyCombo {
+        KeyCombo::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_single_keys() {
+        assert_eq!("q".parse(), Ok(KeyCombo::from(KeyCode::Char('q'))));
+        assert_eq!("enter".parse(), Ok(KeyCombo::from(KeyCode::Enter)));
+        assert_eq!("Esc".parse(), Ok(KeyCombo::from(KeyCode::Esc)));
+        assert_eq!("space".parse(), Ok(KeyCombo::from(KeyCode::Char(' '))));
+        assert_eq!("f5".parse(), Ok(KeyCombo::from(KeyCode::F(5))));
+    }
+
+    #[test]
+    fn parses_modifiers() {
+        assert_eq!(
+            "ctrl+c".parse(),
+            Ok(combo(KeyCode::Char('c'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            "ctrl+alt+delete".parse(),
+            Ok(combo(
+                KeyCode::Delete,
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+    }
+
+    #[test]
+    fn shift_is_folded_into_the_key() {
+        assert_eq!("shift+g".parse::<KeyCombo>(), "G".parse());
+        assert_eq!("shift+tab".parse(), Ok(KeyCombo::from(KeyCode::BackTab)));
+        assert_eq!(
+            KeyCombo::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            KeyCombo::from(KeyCode::BackTab)
+        );
+    }
+
+    #[test]
+    fn parses_the_plus_key() {
+        assert_eq!("+".parse(), Ok(KeyCombo::from(KeyCode::Char('+'))));
+        assert_eq!(
+            "ctrl++".parse(),
+            Ok(combo(KeyCode::Char('+'), KeyModifiers::CONTROL))
+        );
+        assert_eq!("plus".parse(), Ok(KeyCombo::from(KeyCode::Char('+'))));
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert_eq!(
+            "hyper+a".parse::<KeyCombo>(),
+            Err(KeymapError::UnknownModifier("hyper".to_string()))
+        );
+        assert_eq!(
+            "f25".parse::<KeyCombo>(),
+            Err(KeymapError::InvalidKey("f25".to_string()))
+        );
+        assert_eq!(
+            "nope".parse::<KeyCombo>(),
+            Err(KeymapError::InvalidKey("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn displays_what_it_parses() {
+        for spec in ["q", "ctrl+c", "alt+enter", "shift+tab", "space", "f5"] {
+            let key: KeyCombo = spec.parse().unwrap();
+            assert_eq!(key.to_string(), spec);
+        }
+    }
+
+    #[test]
+    fn parses_sequences_with_the_leader() {
+        let leader = KeyCombo::from(KeyCode::Char(' '));
+        let keys = parse_sequence("<leader>w g g", leader).unwrap();
+
+        assert_eq!(
+            keys,
+            [
+                leader,
+                KeyCombo::from(KeyCode::Char('w')),
+                KeyCombo::from(KeyCode::Char('g')),
+                KeyCombo::from(KeyCode::Char('g')),
+            ]
+        );
+        assert_eq!(
+            parse_sequence("  ", leader),
+            Err(KeymapError::EmptySequence)
+        );
+    }
+
+    fn keys(spec: &str) -> Vec<KeyCombo> {
+        parse_sequence(spec, KeyCombo::from(KeyCode::Char('\\'))).unwrap()
+    }
+
+    #[test]
+    fn looks_up_sequences() {
+        let keymap = Keymap::new()
+            .bind("g", 1, "")
+            .bind("g g", 2, "")
+            .bind("q", 3, "");
+
+        assert!(matches!(keymap.lookup(&keys("q")), Lookup::Matched(3)));
+        assert!(matches!(keymap.lookup(&keys("g g")), Lookup::Matched(2)));
+        assert!(matches!(
+            keymap.lookup(&keys("g")),
+            Lookup::Pending {
+                fallback: Some(1),
+                ..
+            }
+        ));
+        assert!(matches!(keymap.lookup(&keys("x")), Lookup::NotFound));
+    }
+
+    #[test]
+    fn mode_bindings_come_first() {
+        let keymap = Keymap::new()
+            .bind("esc", 1, "")
+            .bind_in("insert", "esc", 2, "");
+
+        assert!(matches!(keymap.lookup(&keys("esc")), Lookup::Matched(1)));
+
+        let keymap = keymap.with_mode("insert");
+        assert!(matches!(keymap.lookup(&keys("esc")), Lookup::Matched(2)));
+    }
+}
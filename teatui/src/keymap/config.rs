@@ -0,0 +1,429 @@
+//! Key bindings loaded from a TOML file, so users can remap keys without recompiling.
+//!
+//! The file binds key sequences to action names, which are registered
+//! in [`Actions`] together with the message they stand for:
+//!
+//! ```toml
+//! leader = "space"
+//! timeout_ms = 500
+//!
+//! [bindings]
+//! "q" = "quit"
+//! "g g" = { action = "reset", description = "back to zero" }
+//!
+//! [modes.insert]
+//! "esc" = "normal_mode"
+//! ```
+//!
+//! Bindings under `[bindings]` are active in every mode, the ones under `[modes.<name>]`
+//! only in that mode. A file can be watched for changes with
+//! [`crate::options::ProgramOptions::with_keymap_reload`].
+use super::{KeyCombo, Keymap, KeymapError};
+use crate::queue::Sender;
+use crate::update::Input;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, io, thread};
+
+/// How often a watched keymap file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(thiserror::Error, Debug)]
+pub enum KeymapConfigError {
+    #[error("Failed to read the keymap file")]
+    Io(#[from] io::Error),
+    #[error("Failed to parse the keymap file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Invalid leader key: {0}")]
+    InvalidLeader(KeymapError),
+    #[error("Mode '{0}' should be a table of bindings")]
+    InvalidMode(String),
+    #[error("Invalid bindings in the keymap file:\n{}", list(.0))]
+    InvalidBindings(Vec<BindingError>),
+}
+
+/// A binding of the keymap file that can't be added to the keymap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingError {
+    pub mode: Option<String>,
+    pub keys: String,
+    pub kind: BindingErrorKind,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum BindingErrorKind {
+    #[error("expected an action name or a table with an `action` and a `description`")]
+    InvalidEntry,
+    #[error("unknown action '{0}'")]
+    UnknownAction(String),
+    #[error("{0}")]
+    InvalidKeys(#[from] KeymapError),
+}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.mode {
+            Some(mode) => write!(f, "'{}' in mode '{mode}': {}", self.keys, self.kind),
+            None => write!(f, "'{}': {}", self.keys, self.kind),
+        }
+    }
+}
+
+impl std::error::Error for BindingError {}
+
+fn list(errors: &[BindingError]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("  - {error}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Names the keymap file can refer to, each one standing for a message.
+pub struct Actions<Msg> {
+    actions: HashMap<String, (Msg, String)>,
+}
+
+impl<Msg> Default for Actions<Msg> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::new(),
+        }
+    }
+}
+
+impl<Msg> Actions<Msg> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an action, `description` is used for bindings that don't provide their own.
+    pub fn action(mut self, name: &str, message: Msg, description: &str) -> Self {
+        self.actions
+            .insert(name.to_string(), (message, description.to_string()));
+        self
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeymapFile {
+    leader: Option<String>,
+    timeout_ms: Option<u64>,
+    #[serde(default)]
+    bindings: toml::Table,
+    #[serde(default)]
+    modes: toml::Table,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Action(String),
+    Described {
+        action: String,
+        description: Option<String>,
+    },
+}
+
+impl Entry {
+    fn action(&self) -> &str {
+        match self {
+            Self::Action(action) | Self::Described { action, .. } => action,
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Self::Action(_) => None,
+            Self::Described { description, .. } => description.as_deref(),
+        }
+    }
+}
+
+impl<Msg> Keymap<Msg>
+where
+    Msg: Clone,
+{
+    /// Builds a keymap from the contents of a keymap file.
+    ///
+    /// Every invalid binding is reported at once, so they can all be fixed in one go.
+    pub fn from_toml(source: &str, actions: &Actions<Msg>) -> Result<Self, KeymapConfigError> {
+        let file: KeymapFile = toml::from_str(source)?;
+        let mut keymap = Self::new();
+
+        if let Some(leader) = file.leader {
+            let leader = leader
+                .parse::<KeyCombo>()
+                .map_err(KeymapConfigError::InvalidLeader)?;
+            keymap = keymap.with_leader(leader);
+        }
+
+        if let Some(timeout) = file.timeout_ms {
+            keymap = keymap.with_timeout(Duration::from_millis(timeout));
+        }
+
+        let mut entries: Vec<_> = file
+            .bindings
+            .into_iter()
+            .map(|(keys, entry)| (None, keys, entry))
+            .collect();
+
+        for (mode, bindings) in file.modes {
+            let toml::Value::Table(bindings) = bindings else {
+                return Err(KeymapConfigError::InvalidMode(mode));
+            };
+
+            entries.extend(
+                bindings
+                    .into_iter()
+                    .map(|(keys, entry)| (Some(mode.clone()), keys, entry)),
+            );
+        }
+
+        let mut errors = vec![];
+
+        for (mode, keys, entry) in entries {
+            let Ok(entry) = entry.try_into::<Entry>() else {
+                errors.push(BindingError {
+                    mode,
+                    keys,
+                    kind: BindingErrorKind::InvalidEntry,
+                });
+                continue;
+            };
+
+            let Some((message, description)) = actions.actions.get(entry.action()) else {
+                errors.push(BindingError {
+                    mode,
+                    keys,
+                    kind: BindingErrorKind::UnknownAction(entry.action().to_string()),
+                });
+                continue;
+            };
+
+            let description = entry.description().unwrap_or(description);
+
+            if let Err(error) = keymap.push(mode.clone(), &keys, message.clone(), description) {
+                errors.push(BindingError {
+                    mode,
+                    keys,
+                    kind: error.into(),
+                });
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(KeymapConfigError::InvalidBindings(errors));
+        }
+
+        Ok(keymap)
+    }
+
+    /// Reads a keymap file, see [`Keymap::from_toml`].
+    pub fn load(path: impl AsRef<Path>, actions: &Actions<Msg>) -> Result<Self, KeymapConfigError> {
+        let source = fs::read_to_string(path)?;
+        Self::from_toml(&source, actions)
+    }
+}
+
+/// A keymap file watched for changes.
+pub(crate) struct Watch<Msg> {
+    pub(crate) path: PathBuf,
+    /// Loads the file and maps the result into a message.
+    pub(crate) reload: Box<dyn Fn(&Path) -> Msg + Send>,
+}
+
+/// Reloads the keymap file every time it is modified, until the update actor is gone.
+pub(crate) fn watch<Msg>(watch: Watch<Msg>, tx: Sender<Input<Msg>>) {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified());
+    let mut last_modified = modified(&watch.path).ok();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        // Editors often replace the file on save, so it might be briefly missing
+        let Ok(current) = modified(&watch.path) else {
+            continue;
+        };
+
+        if last_modified == Some(current) {
+            continue;
+        }
+
+        last_modified = Some(current);
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(path = ?watch.path, "keymap file changed");
+
+        if tx.send_message((watch.reload)(&watch.path)).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Quit,
+        Reset,
+        Normal,
+    }
+
+    fn actions() -> Actions<Message> {
+        Actions::new()
+            .action("quit", Message::Quit, "quit")
+            .action("reset", Message::Reset, "reset the counter")
+            .action("normal_mode", Message::Normal, "normal mode")
+    }
+
+    fn binding_errors(source: &str) -> Vec<BindingError> {
+        match Keymap::from_toml(source, &actions()) {
+            Err(KeymapConfigError::InvalidBindings(errors)) => errors,
+            other => panic!("expected invalid bindings, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn loads_bindings_in_every_mode_and_in_one_mode() {
+        let source = r#"
+            leader = "space"
+            timeout_ms = 500
+
+            [bindings]
+            "q" = "quit"
+            "<leader> r" = { action = "reset", description = "back to zero" }
+
+            [modes.insert]
+            "esc" = "normal_mode"
+        "#;
+        let keymap = Keymap::from_toml(source, &actions()).unwrap();
+        let bindings: Vec<_> = keymap.bindings().collect();
+
+        assert_eq!(keymap.leader, KeyCombo::from(KeyCode::Char(' ')));
+        assert_eq!(keymap.timeout, Duration::from_millis(500));
+        assert_eq!(bindings.len(), 3);
+
+        assert_eq!(bindings[0].mode(), None);
+        assert_eq!(bindings[0].message(), &Message::Quit);
+        assert_eq!(bindings[0].description(), "quit");
+
+        assert_eq!(
+            bindings[1].keys(),
+            [
+                KeyCombo::from(KeyCode::Char(' ')),
+                KeyCombo::from(KeyCode::Char('r'))
+            ]
+        );
+        assert_eq!(bindings[1].description(), "back to zero");
+
+        assert_eq!(bindings[2].mode(), Some("insert"));
+        assert_eq!(bindings[2].keys(), [KeyCombo::from(KeyCode::Esc)]);
+        assert_eq!(bindings[2].message(), &Message::Normal);
+    }
+
+    #[test]
+    fn an_empty_file_is_an_empty_keymap() {
+        let keymap = Keymap::from_toml("", &actions()).unwrap();
+
+        assert_eq!(keymap.bindings().count(), 0);
+    }
+
+    #[test]
+    fn reports_files_that_are_not_keymaps() {
+        let errors = ["[bindings", "bindings = 3", "unknown = true"]
+            .map(|source| Keymap::from_toml(source, &actions()));
+
+        for error in errors {
+            assert!(matches!(error, Err(KeymapConfigError::Parse(_))));
+        }
+    }
+
+    #[test]
+    fn reports_an_invalid_leader() {
+        let error = Keymap::from_toml(r#"leader = "hyper+x""#, &actions());
+
+        assert!(matches!(
+            error,
+            Err(KeymapConfigError::InvalidLeader(KeymapError::UnknownModifier(modifier)))
+                if modifier == "hyper"
+        ));
+    }
+
+    #[test]
+    fn reports_a_mode_that_is_not_a_table() {
+        let error = Keymap::from_toml("[modes]\ninsert = \"quit\"", &actions());
+
+        assert!(matches!(
+            error,
+            Err(KeymapConfigError::InvalidMode(mode)) if mode == "insert"
+        ));
+    }
+
+    #[test]
+    fn reports_every_invalid_binding_at_once() {
+        let source = r#"
+            [bindings]
+            "q" = "quit"
+            "x" = 3
+            "y" = "explode"
+            "hyper+x" = "quit"
+
+            [modes.insert]
+            "" = "normal_mode"
+        "#;
+
+        assert_eq!(
+            binding_errors(source),
+            [
+                BindingError {
+                    mode: None,
+                    keys: "x".to_string(),
+                    kind: BindingErrorKind::InvalidEntry,
+                },
+                BindingError {
+                    mode: None,
+                    keys: "y".to_string(),
+                    kind: BindingErrorKind::UnknownAction("explode".to_string()),
+                },
+                BindingError {
+                    mode: None,
+                    keys: "hyper+x".to_string(),
+                    kind: KeymapError::UnknownModifier("hyper".to_string()).into(),
+                },
+                BindingError {
+                    mode: Some("insert".to_string()),
+                    keys: String::new(),
+                    kind: KeymapError::EmptySequence.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_invalid_bindings_in_the_error_message() {
+        let source = "[bindings]\n\"y\" = \"explode\"\n[modes.insert]\n\"z\" = \"explode\"";
+        let error = KeymapConfigError::InvalidBindings(binding_errors(source));
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid bindings in the keymap file:\n  \
+             - 'y': unknown action 'explode'\n  \
+             - 'z' in mode 'insert': unknown action 'explode'"
+        );
+    }
+
+    #[test]
+    fn reports_a_missing_file() {
+        let error = Keymap::load("/nonexistent/keymap.toml", &actions());
+
+        assert!(matches!(error, Err(KeymapConfigError::Io(_))));
+    }
+}
@@ -0,0 +1,264 @@
+//! Layers drawn above the view: modals, popups and toasts.
+//!
+//! A [`Layered`] widget renders the view of the program, then every layer from the bottom
+//! to the top, each one in the area given by its [`Placement`] and cleared beforehand:
+//!
+//! ```no_run
+//! # use ratatui::{buffer::Buffer, layout::Rect, widgets::{Paragraph, Widget}};
+//! # use teatui::component::Component;
+//! # use teatui::components::dialog::Modals;
+//! # use teatui::layers::{Corner, Layered, Placement};
+//! # #[derive(Clone)]
+//! # enum Message {}
+//! # struct Model { show_info: bool, modals: Modals<Message> }
+//! # struct AppView { model: Model }
+//! # struct MainView;
+//! # impl MainView { fn new(_model: &Model) -> Paragraph<'static> { Paragraph::new("main") } }
+//! # struct Info;
+//! # impl Info { fn new(_model: &Model) -> Paragraph<'static> { Paragraph::new("info") } }
+//! impl Widget for AppView {
+//!     fn render(self, area: Rect, buf: &mut Buffer) {
+//!         let mut layered = Layered::new(MainView::new(&self.model));
+//!
+//!         if self.model.show_info {
+//!             let info = Info::new(&self.model);
+//!             layered = layered.layer(Placement::corner(Corner::TopRight, 30, 5), info);
+//!         }
+//!
+//!         layered
+//!             .layer(Placement::Full, self.model.modals.widget())
+//!             .render(area, buf);
+//!     }
+//! }
+//! ```
+//!
+//! Only the top layer placing the cursor shows it, a modal hides the cursor of the input below.
+//! Input is routed to the topmost modal by [`ProgramOptions::with_modals`](crate::options::ProgramOptions::with_modals).
+use crate::view;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::{Clear, Widget};
+use unicode_width::UnicodeWidthStr;
+
+/// Corner of the screen a layer is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Area of the screen a layer is drawn in, always clipped to the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// The whole screen, for layers placing their own content such as modals.
+    /// Neither the screen nor the cursor are cleared, the layer clears what it covers.
+    Full,
+    Center {
+        width: u16,
+        height: u16,
+    },
+    Corner {
+        corner: Corner,
+        width: u16,
+        height: u16,
+    },
+    /// Anywhere, such as next to the widget a popup belongs to.
+    Area(Rect),
+}
+
+impl Placement {
+    pub fn center(width: u16, height: u16) -> Self {
+        Self::Center { width, height }
+    }
+
+    pub fn corner(corner: Corner, width: u16, height: u16) -> Self {
+        Self::Corner {
+            corner,
+            width,
+            height,
+        }
+    }
+
+    /// Area of the layer on the given screen.
+    pub fn area(self, screen: Rect) -> Rect {
+        match self {
+            Self::Full => screen,
+            Self::Center { width, height } => {
+                let width = width.min(screen.width);
+                let height = height.min(screen.height);
+
+                Rect {
+                    x: screen.x + (screen.width - width) / 2,
+                    y: screen.y + (screen.height - height) / 2,
+                    width,
+                    height,
+                }
+            }
+            Self::Corner {
+                corner,
+                width,
+                height,
+            } => {
+                let width = width.min(screen.width);
+                let height = height.min(screen.height);
+                let x = match corner {
+                    Corner::TopLeft | Corner::BottomLeft => screen.x,
+                    Corner::TopRight | Corner::BottomRight => screen.right() - width,
+                };
+                let y = match corner {
+                    Corner::TopLeft | Corner::TopRight => screen.y,
+                    Corner::BottomLeft | Corner::BottomRight => screen.bottom() - height,
+                };
+
+                Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                }
+            }
+            Self::Area(area) => area.intersection(screen),
+        }
+    }
+}
+
+/// Draws a layer in its area.
+type Render<'a> = Box<dyn FnOnce(Rect, &mut Buffer) + 'a>;
+
+/// Layer of a [`Layered`] widget.
+struct Layer<'a> {
+    placement: Placement,
+    render: Render<'a>,
+}
+
+/// Widget drawing a base widget with layers above it.
+pub struct Layered<'a, W> {
+    base: W,
+    /// From the bottom to the top.
+    layers: Vec<Layer<'a>>,
+}
+
+impl<'a, W> Layered<'a, W> {
+    pub fn new(base: W) -> Self {
+        Self {
+            base,
+            layers: vec![],
+        }
+    }
+
+    /// Adds a layer above the others.
+    pub fn layer(mut self, placement: Placement, widget: impl Widget + 'a) -> Self {
+        self.layers.push(Layer {
+            placement,
+            render: Box::new(|area, buf| widget.render(area, buf)),
+        });
+        self
+    }
+}
+
+impl<W: Widget> Widget for Layered<'_, W> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.base.render(area, buf);
+
+        for layer in self.layers {
+            let layer_area = layer.placement.area(area);
+
+            if layer_area.is_empty() {
+                continue;
+            }
+
+            if layer.placement != Placement::Full {
+                Clear.render(layer_area, buf);
+                // The cursor of a widget below would show through the layer
+                view::hide_cursor_in(layer_area);
+            }
+
+            (layer.render)(layer_area, buf);
+        }
+    }
+}
+
+/// Rows taken by `text` wrapped at word boundaries in `width` columns,
+/// for layers sized after their content.
+pub(crate) fn wrapped_height(text: &str, width: u16) -> u16 {
+    let width = width.max(1) as usize;
+
+    text.lines()
+        .map(|line| {
+            let mut rows = 1;
+            let mut column = 0;
+
+            // Words of zero width, such as a lone combining mark, take no room
+            let words = line
+                .split_whitespace()
+                .map(UnicodeWidthStr::width)
+                .filter(|&word| word > 0);
+
+            for word in words {
+                if column > 0 && column + 1 + word <= width {
+                    column += 1 + word;
+                    continue;
+                }
+
+                if column > 0 {
+                    rows += 1;
+                }
+
+                // Words longer than a row are broken
+                rows += (word - 1) / width;
+                column = (word - 1) % width + 1;
+            }
+
+            rows
+        })
+        .sum::<usize>() as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wrapped_height;
+
+    #[test]
+    fn words_fitting_a_row_take_one_row() {
+        assert_eq!(wrapped_height("hello world", 20), 1);
+        assert_eq!(wrapped_height("abc def", 7), 1);
+    }
+
+    #[test]
+    fn words_wrap_at_the_end_of_a_row() {
+        assert_eq!(wrapped_height("hello world", 5), 2);
+        assert_eq!(wrapped_height("abc def", 6), 2);
+    }
+
+    #[test]
+    fn words_longer_than_a_row_are_broken() {
+        assert_eq!(wrapped_height("abcdefghij", 4), 3);
+        assert_eq!(wrapped_height("abcdefgh", 4), 2);
+        assert_eq!(wrapped_height("ab abcdefgh", 4), 3);
+    }
+
+    #[test]
+    fn every_line_takes_at_least_one_row() {
+        assert_eq!(wrapped_height("", 10), 0);
+        assert_eq!(wrapped_height("a\nb", 10), 2);
+        assert_eq!(wrapped_height("a\n\nb", 10), 3);
+    }
+
+    #[test]
+    fn wide_characters_count_their_width() {
+        assert_eq!(wrapped_height("日本語", 4), 2);
+    }
+
+    #[test]
+    fn zero_width_words_take_no_room() {
+        assert_eq!(wrapped_height("\u{200B}", 10), 1);
+        assert_eq!(wrapped_height("a \u{301} b", 3), 1);
+    }
+
+    #[test]
+    fn zero_width_is_one_column() {
+        assert_eq!(wrapped_height("ab", 0), 2);
+    }
+}
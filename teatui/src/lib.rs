@@ -28,19 +28,57 @@
 //! - An effects function that given a reference to the model and an effect,
 //!   might perform any side effects and optionally return a message to update the state of the application
 //!
+//! ### Features
+//!
+//! - `tokio`: side effects are asynchronous and run on a Tokio reactor.
+//!
+//! - `serde`: terminal input sessions can be recorded into a file and replayed later
+//!   (see [`options::ProgramOptions`]).
+//!
+//! - `config`: keymaps can be loaded from TOML files and reloaded when they change
+//!   (see [`keymap::config`]).
+//!
+//! - `tracing`: the runtime emits [tracing](https://docs.rs/tracing) spans for every update, effect,
+//!   view and draw, together with the number of pending messages in each actor queue.
+//!   Update spans record the message as formatted for the diagnostics overlay
+//!   (see [`options::ProgramOptions::with_diagnostics`]), or its type name otherwise.
+//!   Since the terminal is owned by the program, subscribers should write to a file.
+//!
 //! ### Examples
 //!
 //! You can find a folder with example projects in the [examples](https://github.com/JasterV/teatui/tree/main/examples) folder.
+use diagnostics::Monitor;
 use effects::EffectsError;
 use events::EventLoopError;
+use mouse::GestureDetector;
+use options::ProgramOptions;
+use pipeline::{Chord, Pipeline};
 use ratatui::widgets::Widget;
+use regions::Regions;
 use std::fmt::Debug;
+use std::sync::Arc;
 use std::{sync::mpsc::channel, thread};
-use update::{Update, UpdateError};
+use update::{Input, Update, UpdateError};
 use view::ViewError;
 
+pub mod component;
+pub mod components;
+pub mod diagnostics;
 pub mod effects;
 pub mod events;
+pub mod focus;
+pub mod fuzzy;
+pub mod help;
+pub mod keymap;
+pub mod layers;
+pub mod mouse;
+pub mod options;
+mod pipeline;
+mod queue;
+pub mod regions;
+#[cfg(feature = "serde")]
+pub mod session;
+mod terminal;
 pub mod update;
 pub mod view;
 
@@ -90,9 +128,41 @@ where
     VF: Fn(M) -> W + Send + Sync + 'static,
     EF: Fn(M, Eff) -> Option<Msg> + Send + Sync + 'static,
 {
-    run_program(init_fn, update_fn, view_fn, move |effects_rx, update_tx| {
-        effects::run(effects_fn, effects_rx, update_tx)
-    })
+    start_with(
+        ProgramOptions::default(),
+        init_fn,
+        update_fn,
+        view_fn,
+        effects_fn,
+    )
+}
+
+/// Same as [`start`], but the runtime is customized with the given [`ProgramOptions`].
+#[cfg(not(feature = "tokio"))]
+pub fn start_with<M, Msg, Eff, W, IF, UF, VF, EF>(
+    options: ProgramOptions<M, Msg>,
+    init_fn: IF,
+    update_fn: UF,
+    view_fn: VF,
+    effects_fn: EF,
+) -> Result<(), ProgramError<M, Msg, Eff>>
+where
+    M: Clone + Send + Sync + 'static,
+    Eff: Debug + Send + Sync + 'static,
+    Msg: From<crossterm::event::Event> + Sync + Send + 'static,
+    W: Widget,
+    IF: Fn() -> (M, Option<Eff>) + Send + Sync + 'static,
+    UF: Fn(M, Msg) -> Update<M, Eff> + Send + Sync + 'static,
+    VF: Fn(M) -> W + Send + Sync + 'static,
+    EF: Fn(M, Eff) -> Option<Msg> + Send + Sync + 'static,
+{
+    run_program(
+        options,
+        init_fn,
+        update_fn,
+        view_fn,
+        move |effects_rx, update_tx| effects::run(effects_fn, effects_rx, update_tx),
+    )
 }
 
 /// Starts the runtime with asynchronous (Tokio) side effects.
@@ -114,13 +184,47 @@ where
     EF: Fn(M, Eff) -> Fut + Send + Sync + 'static,
     Fut: std::future::Future<Output = Option<Msg>> + Send,
 {
-    run_program(init_fn, update_fn, view_fn, move |effects_rx, update_tx| {
-        effects::run_async(effects_fn, effects_rx, update_tx)
-    })
+    start_with(
+        ProgramOptions::default(),
+        init_fn,
+        update_fn,
+        view_fn,
+        effects_fn,
+    )
+}
+
+/// Same as [`start`], but the runtime is customized with the given [`ProgramOptions`].
+#[cfg(feature = "tokio")]
+pub fn start_with<M, Msg, Eff, W, IF, UF, VF, EF, Fut>(
+    options: ProgramOptions<M, Msg>,
+    init_fn: IF,
+    update_fn: UF,
+    view_fn: VF,
+    effects_fn: EF,
+) -> Result<(), ProgramError<M, Msg, Eff>>
+where
+    M: Clone + Send + Sync + 'static,
+    Eff: Debug + Send + Sync + 'static,
+    Msg: From<crossterm::event::Event> + Sync + Send + 'static,
+    W: Widget,
+    IF: Fn() -> (M, Option<Eff>) + Send + Sync + 'static,
+    UF: Fn(M, Msg) -> Update<M, Eff> + Send + Sync + 'static,
+    VF: Fn(M) -> W + Send + Sync + 'static,
+    EF: Fn(M, Eff) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<Msg>> + Send,
+{
+    run_program(
+        options,
+        init_fn,
+        update_fn,
+        view_fn,
+        move |effects_rx, update_tx| effects::run_async(effects_fn, effects_rx, update_tx),
+    )
 }
 
 /// Internal helper to abstract the common actor-spawning logic.
 fn run_program<M, Msg, Eff, W, IF, UF, VF, SF>(
+    options: ProgramOptions<M, Msg>,
     init_fn: IF,
     update_fn: UF,
     view_fn: VF,
@@ -135,27 +239,41 @@ where
     UF: Fn(M, Msg) -> Update<M, Eff> + Send + Sync + 'static,
     VF: Fn(M) -> W + Send + Sync + 'static,
     SF: FnOnce(
-            std::sync::mpsc::Receiver<(M, Eff)>,
-            std::sync::mpsc::Sender<Msg>,
+            queue::Receiver<(M, Eff)>,
+            queue::Sender<Input<Msg>>,
         ) -> Result<(), EffectsError<Msg>>
         + Send
         + Sync
         + 'static,
 {
-    let terminal = ratatui::init();
+    let (terminal, terminal_modes) = terminal::init(options.terminal);
 
     let (shutdown_tx, shutdown_rx) = channel::<Result<(), ProgramError<M, Msg, Eff>>>();
-    let (update_tx, update_rx) = channel::<Msg>();
-    let (view_tx, view_rx) = channel::<M>();
-    let (effects_tx, effects_rx) = channel::<(M, Eff)>();
+    let (update_tx, update_rx) = queue::channel::<Input<Msg>>();
+    let (view_tx, view_rx) = queue::channel::<M>();
+    let (effects_tx, effects_rx) = queue::channel::<(M, Eff)>();
+
+    let (monitor, describe) = options
+        .diagnostics
+        .map(|(diagnostics, describe)| {
+            let monitor = Monitor::new(diagnostics, update_tx.depth(), effects_tx.depth());
+            (Arc::new(monitor), describe)
+        })
+        .unzip();
+
+    let regions = Arc::new(Regions::default());
 
     // Spawn View Actor
     thread::spawn({
         let (model, _) = init_fn();
         let shutdown_tx = shutdown_tx.clone();
+        let monitor = monitor.clone();
+        let regions = regions.clone();
         move || {
-            let result =
-                view::run(model, terminal, view_fn, view_rx).map_err(ProgramError::ViewError);
+            let result = run_actor("view", || {
+                view::run(model, terminal, view_fn, view_rx, monitor, regions)
+            })
+            .map_err(ProgramError::ViewError);
             let _ = shutdown_tx.send(result);
         }
     });
@@ -164,9 +282,31 @@ where
     thread::spawn({
         let shutdown_tx = shutdown_tx.clone();
         let (model, effect) = init_fn();
+        let pipeline = Pipeline {
+            monitor,
+            describe,
+            gestures: terminal_modes.mouse_capture.then(GestureDetector::default),
+            gesture_handler: options.gestures,
+            regions,
+            keymaps: options
+                .focus_keymap
+                .into_iter()
+                .chain(options.keymap)
+                .collect(),
+            focus: options.focus,
+            back: options.back,
+            modals: options.modals,
+            palette: options.palette,
+            ticks: options.ticks.into_iter().chain(options.toasts).collect(),
+            chord: Chord::default(),
+        };
         move || {
-            let result = update::run(model, effect, update_fn, update_rx, view_tx, effects_tx)
-                .map_err(ProgramError::UpdateError);
+            let result = run_actor("update", || {
+                update::run(
+                    model, effect, update_fn, pipeline, update_rx, view_tx, effects_tx,
+                )
+            })
+            .map_err(ProgramError::UpdateError);
             let _ = shutdown_tx.send(result);
         }
     });
@@ -177,26 +317,75 @@ where
         let update_tx = update_tx.clone();
 
         move || {
-            let result = effects_fn(effects_rx, update_tx).map_err(ProgramError::EffectsError);
+            let result = run_actor("effects", || effects_fn(effects_rx, update_tx))
+                .map_err(ProgramError::EffectsError);
 
             let _ = shutdown_tx.send(result);
         }
     });
 
+    // Spawn the keymap watcher, which stops once the update actor is gone
+    #[cfg(feature = "config")]
+    if let Some(watch) = options.keymap_watch {
+        let update_tx = update_tx.clone();
+        thread::spawn(move || {
+            run_actor("keymap watcher", || {
+                keymap::config::watch(watch, update_tx);
+                Ok::<(), ()>(())
+            })
+        });
+    }
+
     // Spawn Events Actor
     thread::spawn({
         let shutdown_tx = shutdown_tx.clone();
+        #[cfg(feature = "serde")]
+        let (record, replay) = (options.record, options.replay);
+
         move || {
-            let result = events::run(update_tx).map_err(ProgramError::EventLoopError);
-            let _ = shutdown_tx.send(result);
+            let result = run_actor("events", || {
+                #[cfg(feature = "serde")]
+                return session::Session::open(
+                    record.as_deref(),
+                    replay
+                        .as_ref()
+                        .map(|(path, speed)| (path.as_path(), *speed)),
+                )
+                .map_err(EventLoopError::from)
+                .and_then(|session| events::run(session, update_tx));
+
+                #[cfg(not(feature = "serde"))]
+                {
+                    events::run(update_tx)
+                }
+            });
+
+            let _ = shutdown_tx.send(result.map_err(ProgramError::EventLoopError));
         }
     });
 
     let result = shutdown_rx.recv().ok();
-    ratatui::restore();
+    terminal::restore(terminal_modes);
 
     match result {
         Some(result) => result,
         None => Err(ProgramError::GracefulShutdownError),
     }
 }
+
+/// Runs the body of an actor, tracing when it starts and stops.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn run_actor<E>(name: &'static str, actor: impl FnOnce() -> Result<(), E>) -> Result<(), E> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("actor", name).entered();
+
+    #[cfg(feature = "tracing")]
+    tracing::info!("actor started");
+
+    let result = actor();
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(failed = result.is_err(), "actor stopped");
+
+    result
+}
@@ -0,0 +1,368 @@
+//! Higher-level mouse gestures synthesized from raw crossterm mouse events.
+//!
+//! Mouse events are only reported by the terminal when mouse capture is enabled,
+//! see [`crate::options::ProgramOptions::with_mouse_capture`].
+use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use std::time::{Duration, Instant};
+
+/// Maximum time between two clicks for them to be considered a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A mouse interaction recognized from a sequence of raw mouse events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseGesture {
+    /// A button was pressed and released without moving the mouse in between.
+    Click {
+        button: MouseButton,
+        position: Position,
+        modifiers: KeyModifiers,
+    },
+    /// A second click of the same button on the same cell, shortly after the first one.
+    ///
+    /// It is always preceded by a [`MouseGesture::Click`] for the same press.
+    DoubleClick {
+        button: MouseButton,
+        position: Position,
+        modifiers: KeyModifiers,
+    },
+    /// The mouse moved while a button was held down.
+    DragStart {
+        button: MouseButton,
+        origin: Position,
+        modifiers: KeyModifiers,
+    },
+    /// The mouse keeps moving during a drag.
+    DragMove {
+        button: MouseButton,
+        origin: Position,
+        position: Position,
+        modifiers: KeyModifiers,
+    },
+    /// The button held during a drag was released.
+    DragEnd {
+        button: MouseButton,
+        origin: Position,
+        position: Position,
+        modifiers: KeyModifiers,
+    },
+    /// The wheel was scrolled.
+    Scroll {
+        direction: ScrollDirection,
+        position: Position,
+        modifiers: KeyModifiers,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl MouseGesture {
+    /// Cell the gesture ended at.
+    pub fn position(&self) -> Position {
+        match *self {
+            Self::Click { position, .. }
+            | Self::DoubleClick { position, .. }
+            | Self::DragMove { position, .. }
+            | Self::DragEnd { position, .. }
+            | Self::Scroll { position, .. } => position,
+            Self::DragStart { origin, .. } => origin,
+        }
+    }
+}
+
+/// Keeps track of the buttons pressed and the last click
+/// in order to turn raw mouse events into gestures.
+#[derive(Debug, Default)]
+pub(crate) struct GestureDetector {
+    pressed: Option<Press>,
+    last_click: Option<(MouseButton, Position, Instant)>,
+}
+
+#[derive(Debug)]
+struct Press {
+    button: MouseButton,
+    origin: Position,
+    dragging: bool,
+}
+
+impl GestureDetector {
+    /// Feeds a terminal event into the detector and returns the gestures it completes.
+    pub(crate) fn detect(&mut self, event: &Event) -> Vec<MouseGesture> {
+        let Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers,
+        }) = *event
+        else {
+            return vec![];
+        };
+
+        let position = Position::new(column, row);
+
+        match kind {
+            MouseEventKind::Down(button) => {
+                self.pressed = Some(Press {
+                    button,
+                    origin: position,
+                    dragging: false,
+                });
+                vec![]
+            }
+            MouseEventKind::Drag(button) => match self.pressed.as_mut() {
+                Some(press) if press.button == button && !press.dragging => {
+                    press.dragging = true;
+                    vec![
+                        MouseGesture::DragStart {
+                            button,
+                            origin: press.origin,
+                            modifiers,
+                        },
+                        MouseGesture::DragMove {
+                            button,
+                            origin: press.origin,
+                            position,
+                            modifiers,
+                        },
+                    ]
+                }
+                Some(press) if press.button == button => vec![MouseGesture::DragMove {
+                    button,
+                    origin: press.origin,
+                    position,
+                    modifiers,
+                }],
+                _ => vec![],
+            },
+            MouseEventKind::Up(button) => match self.pressed.take() {
+                Some(press) if press.button == button && press.dragging => {
+                    vec![MouseGesture::DragEnd {
+                        button,
+                        origin: press.origin,
+                        position,
+                        modifiers,
+                    }]
+                }
+                Some(press) if press.button == button => self.click(button, position, modifiers),
+                _ => vec![],
+            },
+            MouseEventKind::ScrollUp => vec![scroll(ScrollDirection::Up, position, modifiers)],
+            MouseEventKind::ScrollDown => vec![scroll(ScrollDirection::Down, position, modifiers)],
+            MouseEventKind::ScrollLeft => vec![scroll(ScrollDirection::Left, position, modifiers)],
+            MouseEventKind::ScrollRight => {
+                vec![scroll(ScrollDirection::Right, position, modifiers)]
+            }
+            MouseEventKind::Moved => vec![],
+        }
+    }
+
+    fn click(
+        &mut self,
+        button: MouseButton,
+        position: Position,
+        modifiers: KeyModifiers,
+    ) -> Vec<MouseGesture> {
+        let now = Instant::now();
+        let click = MouseGesture::Click {
+            button,
+            position,
+            modifiers,
+        };
+
+        let is_double_click =
+            self.last_click
+                .take()
+                .is_some_and(|(last_button, last_position, at)| {
+                    last_button == button
+                        && last_position == position
+                        && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+                });
+
+        if is_double_click {
+            return vec![
+                click,
+                MouseGesture::DoubleClick {
+                    button,
+                    position,
+                    modifiers,
+                },
+            ];
+        }
+
+        self.last_click = Some((button, position, now));
+        vec![click]
+    }
+}
+
+fn scroll(direction: ScrollDirection, position: Position, modifiers: KeyModifiers) -> MouseGesture {
+    MouseGesture::Scroll {
+        direction,
+        position,
+        modifiers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: MouseButton = MouseButton::Left;
+    const NONE: KeyModifiers = KeyModifiers::NONE;
+
+    fn event(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: NONE,
+        })
+    }
+
+    fn feed(
+        detector: &mut GestureDetector,
+        events: &[(MouseEventKind, u16, u16)],
+    ) -> Vec<MouseGesture> {
+        events
+            .iter()
+            .flat_map(|&(kind, column, row)| detector.detect(&event(kind, column, row)))
+            .collect()
+    }
+
+    fn click(column: u16, row: u16) -> [(MouseEventKind, u16, u16); 2] {
+        [
+            (MouseEventKind::Down(LEFT), column, row),
+            (MouseEventKind::Up(LEFT), column, row),
+        ]
+    }
+
+    #[test]
+    fn press_and_release_is_a_click() {
+        let mut detector = GestureDetector::default();
+
+        assert_eq!(
+            feed(&mut detector, &click(3, 4)),
+            [MouseGesture::Click {
+                button: LEFT,
+                position: Position::new(3, 4),
+                modifiers: NONE,
+            }]
+        );
+    }
+
+    #[test]
+    fn second_click_on_the_same_cell_is_a_double_click() {
+        let mut detector = GestureDetector::default();
+        feed(&mut detector, &click(3, 4));
+
+        let gestures = feed(&mut detector, &click(3, 4));
+        assert_eq!(gestures.len(), 2);
+        assert!(matches!(gestures[1], MouseGesture::DoubleClick { .. }));
+
+        // A third click starts over
+        assert_eq!(feed(&mut detector, &click(3, 4)).len(), 1);
+    }
+
+    #[test]
+    fn clicks_on_other_cells_or_buttons_are_single() {
+        let mut detector = GestureDetector::default();
+        feed(&mut detector, &click(3, 4));
+
+        assert_eq!(feed(&mut detector, &click(5, 4)).len(), 1);
+        assert_eq!(
+            feed(
+                &mut detector,
+                &[
+                    (MouseEventKind::Down(MouseButton::Right), 5, 4),
+                    (MouseEventKind::Up(MouseButton::Right), 5, 4),
+                ]
+            )
+            .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn slow_clicks_are_single() {
+        let mut detector = GestureDetector::default();
+        feed(&mut detector, &click(3, 4));
+        detector.last_click = detector
+            .last_click
+            .map(|(button, position, at)| (button, position, at - DOUBLE_CLICK_INTERVAL * 2));
+
+        assert_eq!(feed(&mut detector, &click(3, 4)).len(), 1);
+    }
+
+    #[test]
+    fn moving_while_pressed_is_a_drag() {
+        let mut detector = GestureDetector::default();
+        let origin = Position::new(1, 1);
+
+        let gestures = feed(
+            &mut detector,
+            &[
+                (MouseEventKind::Down(LEFT), 1, 1),
+                (MouseEventKind::Drag(LEFT), 2, 1),
+                (MouseEventKind::Drag(LEFT), 3, 2),
+                (MouseEventKind::Up(LEFT), 3, 2),
+            ],
+        );
+
+        assert_eq!(
+            gestures,
+            [
+                MouseGesture::DragStart {
+                    button: LEFT,
+                    origin,
+                    modifiers: NONE,
+                },
+                MouseGesture::DragMove {
+                    button: LEFT,
+                    origin,
+                    position: Position::new(2, 1),
+                    modifiers: NONE,
+                },
+                MouseGesture::DragMove {
+                    button: LEFT,
+                    origin,
+                    position: Position::new(3, 2),
+                    modifiers: NONE,
+                },
+                MouseGesture::DragEnd {
+                    button: LEFT,
+                    origin,
+                    position: Position::new(3, 2),
+                    modifiers: NONE,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn releases_without_a_press_are_ignored() {
+        let mut detector = GestureDetector::default();
+
+        assert!(feed(&mut detector, &[(MouseEventKind::Up(LEFT), 1, 1)]).is_empty());
+        assert!(feed(&mut detector, &[(MouseEventKind::Drag(LEFT), 1, 1)]).is_empty());
+        assert!(feed(&mut detector, &[(MouseEventKind::Moved, 1, 1)]).is_empty());
+    }
+
+    #[test]
+    fn scrolling_is_reported_where_it_happens() {
+        let mut detector = GestureDetector::default();
+
+        assert_eq!(
+            feed(&mut detector, &[(MouseEventKind::ScrollDown, 7, 8)]),
+            [MouseGesture::Scroll {
+                direction: ScrollDirection::Down,
+                position: Position::new(7, 8),
+                modifiers: NONE,
+            }]
+        );
+    }
+}
@@ -0,0 +1,372 @@
+//! Settings that change how the runtime started by [`crate::start_with`] behaves.
+use crate::components::dialog::{ModalMessage, Modals};
+use crate::components::palette::{self, CommandPalette};
+use crate::components::router::{self, Router, Screen};
+use crate::components::toast::{self, Toasts};
+use crate::diagnostics::Diagnostics;
+use crate::focus::Focus;
+#[cfg(feature = "config")]
+use crate::keymap::config::{Actions, KeymapConfigError, Watch};
+use crate::keymap::{KeyCombo, Keymap, Lookup};
+use crate::mouse::MouseGesture;
+use crate::terminal::TerminalModes;
+use crossterm::event::{Event, KeyboardEnhancementFlags};
+use std::fmt::Debug;
+#[cfg(any(feature = "serde", feature = "config"))]
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Formats a message for the diagnostics overlay.
+pub(crate) type Describe<Msg> = fn(&Msg) -> String;
+
+/// Maps a mouse gesture into a message for the update function.
+pub(crate) type GestureHandler<Msg> = Box<dyn Fn(MouseGesture) -> Option<Msg> + Send>;
+
+/// Looks up the keys pressed so far in the keymap of the model.
+pub(crate) type KeymapResolver<M, Msg> = Box<dyn Fn(&M, &[KeyCombo]) -> Lookup<Msg> + Send>;
+
+/// Moves the focus stored in the model when a cycling key is pressed.
+pub(crate) type FocusHandler<M, Msg> = Box<dyn Fn(&M, KeyCombo) -> Option<Msg> + Send>;
+
+/// Goes back to the previous screen of the router stored in the model when its key is pressed.
+pub(crate) type BackHandler<M, Msg> = Box<dyn Fn(&M, KeyCombo) -> Option<Msg> + Send>;
+
+/// Gives the events to the command palette stored in the model, if it wants them.
+pub(crate) type PaletteRouter<M, Msg> = Box<dyn Fn(&M, &Event) -> Option<Vec<Msg>> + Send>;
+
+/// Gives the keys to the topmost dialog stored in the model, if one is open.
+pub(crate) type ModalRouter<M, Msg> = Box<dyn Fn(&M, &Event) -> Option<Vec<Msg>> + Send>;
+
+/// Instant at which the model wants a tick, if any.
+pub(crate) type Deadline<M> = Box<dyn Fn(&M) -> Option<Instant> + Send>;
+
+/// Tells when the model wants a tick, and the message carrying it.
+pub(crate) struct Ticks<M, Msg> {
+    pub(crate) deadline: Deadline<M>,
+    pub(crate) message: Box<dyn Fn(Instant) -> Msg + Send>,
+    /// Deadline a tick was last sent for, which is not sent again
+    /// while the model keeps returning it.
+    pub(crate) fired: Option<Instant>,
+}
+
+/// Options used to customize the program runtime.
+///
+/// Use [`ProgramOptions::default`] and chain the `with_*` methods
+/// to enable the desired behaviour.
+pub struct ProgramOptions<M, Msg> {
+    pub(crate) diagnostics: Option<(Diagnostics, Describe<Msg>)>,
+    pub(crate) terminal: TerminalModes,
+    pub(crate) gestures: Option<GestureHandler<Msg>>,
+    pub(crate) keymap: Option<KeymapResolver<M, Msg>>,
+    pub(crate) focus_keymap: Option<KeymapResolver<M, Msg>>,
+    pub(crate) focus: Option<FocusHandler<M, Msg>>,
+    pub(crate) back: Option<BackHandler<M, Msg>>,
+    pub(crate) palette: Option<PaletteRouter<M, Msg>>,
+    pub(crate) modals: Option<ModalRouter<M, Msg>>,
+    pub(crate) ticks: Option<Ticks<M, Msg>>,
+    pub(crate) toasts: Option<Ticks<M, Msg>>,
+    #[cfg(feature = "config")]
+    pub(crate) keymap_watch: Option<Watch<Msg>>,
+    #[cfg(feature = "serde")]
+    pub(crate) record: Option<PathBuf>,
+    #[cfg(feature = "serde")]
+    pub(crate) replay: Option<(PathBuf, f64)>,
+}
+
+impl<M, Msg> Default for ProgramOptions<M, Msg> {
+    fn default() -> Self {
+        Self {
+            diagnostics: None,
+            terminal: TerminalModes::default(),
+            gestures: None,
+            keymap: None,
+            focus_keymap: None,
+            focus: None,
+            back: None,
+            palette: None,
+            modals: None,
+            ticks: None,
+            toasts: None,
+            #[cfg(feature = "config")]
+            keymap_watch: None,
+            #[cfg(feature = "serde")]
+            record: None,
+            #[cfg(feature = "serde")]
+            replay: None,
+        }
+    }
+}
+
+impl<M, Msg> ProgramOptions<M, Msg> {
+    /// Enables the diagnostics overlay, which can be shown on top of the view with a key press.
+    ///
+    /// The overlay lists the last messages received, formatted with [`Debug`].
+    pub fn with_diagnostics(mut self, diagnostics: Diagnostics) -> Self
+    where
+        Msg: Debug,
+    {
+        self.diagnostics = Some((diagnostics, |msg| format!("{msg:?}")));
+        self
+    }
+
+    /// Asks the terminal to report mouse events, which are disabled by default.
+    ///
+    /// Raw `Event::Mouse` events are then converted into messages like any other event.
+    pub fn with_mouse_capture(mut self) -> Self {
+        self.terminal.mouse_capture = true;
+        self
+    }
+
+    /// Enables mouse capture and recognizes clicks, double clicks, drags and scrolls.
+    ///
+    /// Every recognized [`MouseGesture`] is given to `handler`, and the message it returns
+    /// is sent to the update function right before the raw event that completed the gesture.
+    pub fn with_mouse_gestures(
+        mut self,
+        handler: impl Fn(MouseGesture) -> Option<Msg> + Send + 'static,
+    ) -> Self {
+        self.gestures = Some(Box::new(handler));
+        self.with_mouse_capture()
+    }
+
+    /// Enables bracketed paste, so pasted text arrives as a single `Event::Paste`
+    /// instead of one key event per character.
+    pub fn with_bracketed_paste(mut self) -> Self {
+        self.terminal.bracketed_paste = true;
+        self
+    }
+
+    /// Asks the terminal to report `Event::FocusGained` and `Event::FocusLost`
+    /// when its window gains or loses focus.
+    pub fn with_focus_change(mut self) -> Self {
+        self.terminal.focus_change = true;
+        self
+    }
+
+    /// Pushes the given kitty keyboard protocol flags when the program starts
+    /// and pops them when it exits.
+    ///
+    /// With [`KeyboardEnhancementFlags::REPORT_EVENT_TYPES`], key repeat and release events
+    /// are reported too, so the update function should check the `kind` of every `KeyEvent`.
+    ///
+    /// The flags are ignored in terminals which don't support the protocol.
+    pub fn with_keyboard_enhancement(mut self, flags: KeyboardEnhancementFlags) -> Self {
+        self.terminal.keyboard_enhancement = Some(flags);
+        self
+    }
+
+    /// Resolves key presses through the [`Keymap`] stored in the model.
+    ///
+    /// `keymap` returns the keymap of a given model, so the bindings and the active mode
+    /// always follow the latest state of the application.
+    pub fn with_keymap(mut self, keymap: fn(&M) -> &Keymap<Msg>) -> Self
+    where
+        M: 'static,
+        Msg: Clone + 'static,
+    {
+        self.keymap = Some(Box::new(move |model, keys| keymap(model).lookup(keys)));
+        self
+    }
+
+    /// Moves the [`Focus`] stored in the model when its cycling keys are pressed,
+    /// `tab` and `shift+tab` by default.
+    ///
+    /// `on_change` maps the moved focus into a message, so the update function can store it.
+    /// Cycling keys bound in a keymap trigger their binding instead.
+    pub fn with_focus<Id>(
+        mut self,
+        focus: fn(&M) -> &Focus<Id>,
+        on_change: impl Fn(Focus<Id>) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        M: 'static,
+        Id: Clone + PartialEq + 'static,
+    {
+        self.focus = Some(Box::new(move |model, key| {
+            focus(model).cycle(key).map(&on_change)
+        }));
+        self
+    }
+
+    /// Goes back to the previous screen of the [`Router`] stored in the model
+    /// when its back key is pressed, `esc` by default.
+    ///
+    /// Router messages are wrapped with `wrap`. The back key bound in a keymap
+    /// or cycling the focus triggers that instead.
+    pub fn with_router<S>(
+        mut self,
+        router: fn(&M) -> &Router<S>,
+        wrap: impl Fn(router::Message<S>) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        M: 'static,
+        S: Screen + 'static,
+    {
+        self.back = Some(Box::new(move |model, key| {
+            router(model)
+                .is_back(key)
+                .then(|| wrap(router::Message::Pop))
+        }));
+        self
+    }
+
+    /// Resolves key presses through the keymap of the focused component
+    /// before the one registered with [`ProgramOptions::with_keymap`].
+    ///
+    /// `keymap` usually matches on the current [`Focus`] of the model,
+    /// returning `None` when the focused component has no bindings of its own.
+    pub fn with_focus_keymap(mut self, keymap: fn(&M) -> Option<&Keymap<Msg>>) -> Self
+    where
+        M: 'static,
+        Msg: Clone + 'static,
+    {
+        self.focus_keymap = Some(Box::new(move |model, keys| {
+            keymap(model).map_or(Lookup::NotFound, |keymap| keymap.lookup(keys))
+        }));
+        self
+    }
+
+    /// Opens the command palette stored in the model with its key, `ctrl+p` by default,
+    /// and gives it every key while it is open, before any keymap.
+    ///
+    /// Palette messages are wrapped with `wrap`, and `enter` sends the message
+    /// of the selected command right after closing the palette.
+    pub fn with_command_palette(
+        mut self,
+        palette: fn(&M) -> &CommandPalette<Msg>,
+        wrap: impl Fn(palette::Message) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        M: 'static,
+        Msg: Clone + 'static,
+    {
+        self.palette = Some(Box::new(move |model, event| {
+            palette(model).route(event, &wrap)
+        }));
+        self
+    }
+
+    /// Gives every key to the topmost dialog of the modals stored in the model,
+    /// before the command palette and any keymap.
+    ///
+    /// Dialog messages are wrapped with `wrap`: `enter` closes the dialog and sends
+    /// the message it resolves to, `esc` closes it and sends its cancel message.
+    pub fn with_modals(
+        mut self,
+        modals: fn(&M) -> &Modals<Msg>,
+        wrap: impl Fn(ModalMessage<Msg>) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        M: 'static,
+        Msg: Clone + 'static,
+    {
+        self.modals = Some(Box::new(move |model, event| {
+            modals(model).route(event, &wrap)
+        }));
+        self
+    }
+
+    /// Sends a tick to the update function at the instant returned by `deadline`,
+    /// for animations and timers.
+    ///
+    /// `deadline` is asked again after every update, and returns `None` while nothing
+    /// needs a tick, so an idle program never wakes up. Components such as
+    /// [`Spinner`](crate::components::spinner::Spinner) tell when they want their next tick,
+    /// the model returning the earliest one:
+    ///
+    /// ```no_run
+    /// # use std::time::Instant;
+    /// # use teatui::components::{progress::ProgressBar, spinner::Spinner};
+    /// # use teatui::options::ProgramOptions;
+    /// # struct Model { spinner: Spinner, progress: ProgressBar }
+    /// # enum Message { Tick(Instant) }
+    /// # let options = ProgramOptions::<Model, Message>::default();
+    /// # let _ =
+    /// options.with_ticks(
+    ///     |model: &Model| [model.spinner.deadline(), model.progress.deadline()].into_iter().flatten().min(),
+    ///     Message::Tick,
+    /// )
+    /// # ;
+    /// ```
+    pub fn with_ticks(
+        mut self,
+        deadline: fn(&M) -> Option<Instant>,
+        on_tick: impl Fn(Instant) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        M: 'static,
+    {
+        self.ticks = Some(Ticks {
+            deadline: Box::new(deadline),
+            message: Box::new(on_tick),
+            fired: None,
+        });
+        self
+    }
+
+    /// Removes the toasts stored in the model once they expire, sending
+    /// [`toast::Message::Tick`] wrapped with `wrap`, apart from the ticks of [`ProgramOptions::with_ticks`].
+    pub fn with_toasts(
+        mut self,
+        toasts: fn(&M) -> &Toasts,
+        wrap: impl Fn(toast::Message) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        M: 'static,
+    {
+        self.toasts = Some(Ticks {
+            deadline: Box::new(move |model| toasts(model).deadline()),
+            message: Box::new(move |now| wrap(toast::Message::Tick(now))),
+            fired: None,
+        });
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<M, Msg> ProgramOptions<M, Msg> {
+    /// Records every terminal event read by the program into a JSON-lines session file,
+    /// together with the time it was read at and the size of the terminal.
+    pub fn with_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// Replays the events of a session file before reading from the terminal.
+    ///
+    /// The original delay between events is divided by `speed`,
+    /// so `1.0` reproduces the recorded timing and `f64::INFINITY` sends the events without waiting.
+    /// Any other speed than a positive number makes the program fail to start.
+    ///
+    /// The terminal is not resized to match the recorded size, so sessions have to be
+    /// replayed in a terminal of the same dimensions: the program fails at the first event
+    /// recorded in a terminal of another size.
+    pub fn with_replay(mut self, path: impl Into<PathBuf>, speed: f64) -> Self {
+        self.replay = Some((path.into(), speed));
+        self
+    }
+}
+
+#[cfg(feature = "config")]
+impl<M, Msg> ProgramOptions<M, Msg> {
+    /// Watches a keymap file and reloads it every time it changes.
+    ///
+    /// `on_reload` maps the new keymap, or the reason it couldn't be loaded, into a message,
+    /// so the update function can swap the keymap stored in the model.
+    /// The new keymap starts in the default mode, use [`Keymap::with_mode`] to keep the current one.
+    pub fn with_keymap_reload(
+        mut self,
+        path: impl Into<PathBuf>,
+        actions: Actions<Msg>,
+        on_reload: impl Fn(Result<Keymap<Msg>, KeymapConfigError>) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        Msg: Clone + Send + 'static,
+    {
+        self.keymap_watch = Some(Watch {
+            path: path.into(),
+            reload: Box::new(move |path| on_reload(Keymap::load(path, &actions))),
+        });
+        self
+    }
+}
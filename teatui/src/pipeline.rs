@@ -0,0 +1,354 @@
+//! Conversion of terminal events into messages, run by the update actor before calling update.
+//!
+//! Every event goes through the following stages, the first one consuming the event wins:
+//!
+//! 1. The diagnostics overlay toggle key.
+//! 2. Mouse gestures, dispatched to clickable regions or the gesture handler.
+//!    The raw mouse event is still converted with `Msg::from` afterwards.
+//! 3. The topmost modal dialog, which takes every key while one is open.
+//! 4. The command palette, which takes every key while it is open.
+//! 5. The keymap of the focused component, then the keymap of the current model.
+//! 6. The focus cycling keys.
+//! 7. The back key of the screen router.
+//! 8. `Msg::from`.
+//!
+//! Besides events, the pipeline tells the update actor when to wake up on its own:
+//! when a pending key sequence times out, and when the model wants a tick.
+use crate::diagnostics::Monitor;
+use crate::keymap::{self, KeyCombo, Lookup};
+use crate::mouse::GestureDetector;
+use crate::options::{
+    BackHandler, Describe, FocusHandler, GestureHandler, KeymapResolver, ModalRouter,
+    PaletteRouter, Ticks,
+};
+use crate::regions::Regions;
+use crossterm::event::Event;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub(crate) struct Pipeline<M, Msg> {
+    pub(crate) monitor: Option<Arc<Monitor>>,
+    /// Formats the messages listed by the monitor.
+    pub(crate) describe: Option<Describe<Msg>>,
+    pub(crate) gestures: Option<GestureDetector>,
+    pub(crate) gesture_handler: Option<GestureHandler<Msg>>,
+    pub(crate) regions: Arc<Regions>,
+    /// Keymaps tried in order, the first one knowing the keys wins.
+    pub(crate) keymaps: Vec<KeymapResolver<M, Msg>>,
+    pub(crate) focus: Option<FocusHandler<M, Msg>>,
+    pub(crate) back: Option<BackHandler<M, Msg>>,
+    /// Tried before the palette, a dialog may be opened above it.
+    pub(crate) modals: Option<ModalRouter<M, Msg>>,
+    pub(crate) palette: Option<PaletteRouter<M, Msg>>,
+    /// Ticks of the model, and expiry of its toasts.
+    pub(crate) ticks: Vec<Ticks<M, Msg>>,
+    pub(crate) chord: Chord<Msg>,
+}
+
+/// Keys of a sequence pressed so far, waiting for the next key or the timeout.
+pub(crate) struct Chord<Msg> {
+    keys: Vec<KeyCombo>,
+    fallback: Option<Msg>,
+    deadline: Option<Instant>,
+}
+
+impl<Msg> Default for Chord<Msg> {
+    fn default() -> Self {
+        Self {
+            keys: vec![],
+            fallback: None,
+            deadline: None,
+        }
+    }
+}
+
+impl<M, Msg> Pipeline<M, Msg>
+where
+    Msg: From<Event> + 'static,
+{
+    /// Instant at which [`Pipeline::expire`] has to be called, if any.
+    pub(crate) fn deadline(&self, model: &M) -> Option<Instant> {
+        let ticks = self.ticks.iter().filter_map(|ticks| {
+            (ticks.deadline)(model).filter(|&deadline| Some(deadline) != ticks.fired)
+        });
+        self.chord.deadline.into_iter().chain(ticks).min()
+    }
+
+    /// Returns the messages due at `now`: the keys of a timed out sequence trigger
+    /// their own binding, and the model gets the ticks it asked for.
+    pub(crate) fn expire(&mut self, model: &M, now: Instant) -> Vec<Msg> {
+        let mut messages = vec![];
+
+        if self.chord.deadline.is_some_and(|deadline| deadline <= now) {
+            messages.extend(std::mem::take(&mut self.chord).fallback);
+        }
+
+        for ticks in &mut self.ticks {
+            if let Some(deadline) = (ticks.deadline)(model)
+                && deadline <= now
+                && ticks.fired != Some(deadline)
+            {
+                ticks.fired = Some(deadline);
+                messages.push((ticks.message)(now));
+            }
+        }
+
+        messages
+    }
+
+    /// Returns the messages an event translates into, in order.
+    pub(crate) fn process(&mut self, model: &M, event: Event) -> Vec<Msg> {
+        // Toggling the diagnostics overlay is handled by the runtime itself
+        if self
+            .monitor
+            .as_ref()
+            .is_some_and(|monitor| monitor.toggle(&event))
+        {
+            return vec![];
+        }
+
+        let mut messages = vec![];
+
+        let routed = [&self.modals, &self.palette]
+            .into_iter()
+            .flatten()
+            .find_map(|route| route(model, &event));
+
+        // Gestures are still detected while a dialog is open, to keep track of the buttons held
+        let gestures = self
+            .gestures
+            .as_mut()
+            .map(|detector| detector.detect(&event))
+            .unwrap_or_default();
+
+        // Nothing below a dialog or the palette is clicked
+        if routed.is_none() {
+            for gesture in gestures {
+                // Clickable regions take precedence over the gesture handler
+                let message = self.regions.hit(&gesture).or_else(|| {
+                    self.gesture_handler
+                        .as_ref()
+                        .and_then(|handler| handler(gesture))
+                });
+
+                messages.extend(message);
+            }
+        }
+
+        if let Some(routed) = routed {
+            // Dialogs and the palette interrupt any pending key sequence
+            self.chord = Chord::default();
+            messages.extend(routed);
+            return messages;
+        }
+
+        if let Event::Key(key) = event
+            && keymap::is_press(&key)
+        {
+            if !self.keymaps.is_empty() {
+                let lookup = |keys: &[KeyCombo]| {
+                    self.keymaps
+                        .iter()
+                        .map(|resolve| resolve(model, keys))
+                        .find(|lookup| !matches!(lookup, Lookup::NotFound))
+                        .unwrap_or(Lookup::NotFound)
+                };
+
+                let (bound, handled) = press(&mut self.chord, lookup, key.into());
+                messages.extend(bound);
+
+                if handled {
+                    return messages;
+                }
+            }
+
+            if let Some(message) = [&self.focus, &self.back]
+                .into_iter()
+                .flatten()
+                .find_map(|handle| handle(model, key.into()))
+            {
+                messages.push(message);
+                return messages;
+            }
+        }
+
+        messages.push(Msg::from(event));
+        messages
+    }
+}
+
+/// Feeds a key into the pending sequence.
+///
+/// Returns the messages triggered and whether the key was consumed by the keymap.
+fn press<Msg>(
+    chord: &mut Chord<Msg>,
+    lookup: impl Fn(&[KeyCombo]) -> Lookup<Msg>,
+    key: KeyCombo,
+) -> (Vec<Msg>, bool) {
+    let mut keys = std::mem::take(&mut chord.keys);
+    keys.push(key);
+
+    match lookup(&keys) {
+        Lookup::Matched(message) => {
+            *chord = Chord::default();
+            (vec![message], true)
+        }
+        Lookup::Pending { fallback, timeout } => {
+            *chord = Chord {
+                keys,
+                fallback,
+                deadline: Some(Instant::now() + timeout),
+            };
+            (vec![], true)
+        }
+        Lookup::NotFound if keys.len() > 1 => {
+            // The sequence was broken by this key: the keys pressed before trigger
+            // their own binding and this key is looked up on its own
+            let fallback = std::mem::take(chord).fallback;
+            let (messages, handled) = press(chord, lookup, key);
+            (fallback.into_iter().chain(messages).collect(), handled)
+        }
+        Lookup::NotFound => {
+            *chord = Chord::default();
+            (vec![], false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::components::palette::{self, CommandPalette};
+    use crate::keymap::Keymap;
+    use crate::mouse::GestureDetector;
+    use crate::update::Update;
+    use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    fn key(c: char) -> KeyCombo {
+        KeyCombo::from(KeyCode::Char(c))
+    }
+
+    fn keymap() -> Keymap<u8> {
+        Keymap::new()
+            .bind("g", 1, "")
+            .bind("g g", 2, "")
+            .bind("d d", 3, "")
+            .bind("x", 4, "")
+    }
+
+    #[test]
+    fn completes_a_sequence() {
+        let keymap = keymap();
+        let mut chord = Chord::default();
+
+        assert_eq!(
+            press(&mut chord, |keys| keymap.lookup(keys), key('d')),
+            (vec![], true)
+        );
+        assert!(chord.deadline.is_some());
+        assert_eq!(
+            press(&mut chord, |keys| keymap.lookup(keys), key('d')),
+            (vec![3], true)
+        );
+        assert!(chord.deadline.is_none());
+    }
+
+    #[test]
+    fn a_broken_sequence_falls_back_and_looks_up_the_key_alone() {
+        let keymap = keymap();
+        let mut chord = Chord::default();
+
+        press(&mut chord, |keys| keymap.lookup(keys), key('g'));
+        assert_eq!(
+            press(&mut chord, |keys| keymap.lookup(keys), key('x')),
+            (vec![1, 4], true)
+        );
+
+        press(&mut chord, |keys| keymap.lookup(keys), key('d'));
+        assert_eq!(
+            press(&mut chord, |keys| keymap.lookup(keys), key('z')),
+            (vec![], false)
+        );
+        assert!(chord.keys.is_empty());
+    }
+
+    #[test]
+    fn unbound_keys_are_not_handled() {
+        let keymap = keymap();
+        let mut chord = Chord::default();
+
+        assert_eq!(
+            press(&mut chord, |keys| keymap.lookup(keys), key('z')),
+            (vec![], false)
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Event(Event),
+        Palette(palette::Message),
+        Clicked,
+    }
+
+    impl From<Event> for Message {
+        fn from(event: Event) -> Self {
+            Self::Event(event)
+        }
+    }
+
+    /// Pipeline routing the events to the palette of the model, every click being handled.
+    fn palette_pipeline() -> Pipeline<CommandPalette<Message>, Message> {
+        Pipeline {
+            monitor: None,
+            describe: None,
+            gestures: Some(GestureDetector::default()),
+            gesture_handler: Some(Box::new(|_| Some(Message::Clicked))),
+            regions: Arc::default(),
+            keymaps: vec![],
+            focus: None,
+            back: None,
+            modals: None,
+            palette: Some(Box::new(|palette: &CommandPalette<Message>, event| {
+                palette.route(event, &Message::Palette)
+            })),
+            ticks: vec![],
+            chord: Chord::default(),
+        }
+    }
+
+    fn mouse(kind: MouseEventKind) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column: 1,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn click(
+        pipeline: &mut Pipeline<CommandPalette<Message>, Message>,
+        model: &CommandPalette<Message>,
+    ) -> Vec<Message> {
+        let mut messages = pipeline.process(model, mouse(MouseEventKind::Down(MouseButton::Left)));
+        messages.extend(pipeline.process(model, mouse(MouseEventKind::Up(MouseButton::Left))));
+        messages
+    }
+
+    #[test]
+    fn nothing_below_an_open_palette_is_clicked() {
+        let mut pipeline = palette_pipeline();
+        let closed = CommandPalette::new([]);
+        let open = match closed.clone().update(palette::Message::Open) {
+            Update::Next(palette, _) => palette,
+            Update::Exit => unreachable!(),
+        };
+
+        assert!(click(&mut pipeline, &closed).contains(&Message::Clicked));
+        assert_eq!(click(&mut pipeline, &open), []);
+        assert_eq!(
+            pipeline.process(&open, mouse(MouseEventKind::ScrollDown)),
+            []
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Channels used between actors which keep track of how many values are waiting to be received.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvError, RecvTimeoutError, SendError, TryRecvError};
+use std::time::Duration;
+
+pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    let depth = Depth::default();
+
+    (
+        Sender {
+            inner: tx,
+            depth: depth.clone(),
+        },
+        Receiver { inner: rx, depth },
+    )
+}
+
+/// Number of values sent through a channel but not received yet.
+#[derive(Clone, Default)]
+pub(crate) struct Depth(Arc<AtomicUsize>);
+
+impl Depth {
+    pub(crate) fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct Sender<T> {
+    inner: mpsc::Sender<T>,
+    depth: Depth,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            depth: self.depth.clone(),
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.depth.0.fetch_add(1, Ordering::Relaxed);
+        self.inner.send(value).inspect_err(|_| {
+            self.depth.0.fetch_sub(1, Ordering::Relaxed);
+        })
+    }
+
+    pub(crate) fn depth(&self) -> Depth {
+        self.depth.clone()
+    }
+}
+
+pub(crate) struct Receiver<T> {
+    inner: mpsc::Receiver<T>,
+    depth: Depth,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn recv(&self) -> Result<T, RecvError> {
+        let value = self.inner.recv()?;
+        self.depth.0.fetch_sub(1, Ordering::Relaxed);
+        Ok(value)
+    }
+
+    pub(crate) fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let value = self.inner.recv_timeout(timeout)?;
+        self.depth.0.fetch_sub(1, Ordering::Relaxed);
+        Ok(value)
+    }
+
+    pub(crate) fn try_recv(&self) -> Result<T, TryRecvError> {
+        let value = self.inner.try_recv()?;
+        self.depth.0.fetch_sub(1, Ordering::Relaxed);
+        Ok(value)
+    }
+
+    /// Number of values sent but not received yet.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    pub(crate) fn depth(&self) -> usize {
+        self.depth.get()
+    }
+}
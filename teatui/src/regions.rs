@@ -0,0 +1,226 @@
+//! Interactive areas registered by the view and hit-tested against mouse gestures.
+//!
+//! While rendering, widgets can associate a message with the area they are drawn into:
+//! clicking inside that area sends the message to the update function,
+//! without the update function having to know anything about the layout.
+//!
+//! Regions only receive clicks when mouse capture is enabled,
+//! see [`crate::options::ProgramOptions::with_mouse_capture`].
+use crate::mouse::MouseGesture;
+use crossterm::event::MouseButton;
+use ratatui::layout::Rect;
+use std::any::{Any, type_name};
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+thread_local! {
+    /// Regions registered while the current frame is being rendered.
+    static REGISTERED: RefCell<Vec<Region>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Registers messages to be sent when an area of the screen is clicked.
+pub trait Clickable {
+    /// Sends `message` when this area is clicked with the left mouse button.
+    ///
+    /// The message must be of the same type the program's update function receives,
+    /// debug builds panic when a region registered with another type is clicked.
+    /// If several registered areas overlap, the one registered last wins.
+    fn on_click<Msg>(self, message: Msg)
+    where
+        Msg: Clone + Send + 'static;
+
+    /// Sends `message` when this area is double clicked with the left mouse button.
+    fn on_double_click<Msg>(self, message: Msg)
+    where
+        Msg: Clone + Send + 'static;
+}
+
+impl Clickable for Rect {
+    fn on_click<Msg>(self, message: Msg)
+    where
+        Msg: Clone + Send + 'static,
+    {
+        register(self, Trigger::Click, message);
+    }
+
+    fn on_double_click<Msg>(self, message: Msg)
+    where
+        Msg: Clone + Send + 'static,
+    {
+        register(self, Trigger::DoubleClick, message);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trigger {
+    Click,
+    DoubleClick,
+}
+
+struct Region {
+    area: Rect,
+    trigger: Trigger,
+    message: Box<dyn Fn() -> Box<dyn Any + Send> + Send>,
+    /// Name of the type of the message, for the mismatch assertion.
+    type_name: &'static str,
+}
+
+fn register<Msg>(area: Rect, trigger: Trigger, message: Msg)
+where
+    Msg: Clone + Send + 'static,
+{
+    let region = Region {
+        area,
+        trigger,
+        message: Box::new(move || Box::new(message.clone())),
+        type_name: type_name::<Msg>(),
+    };
+
+    REGISTERED.with_borrow_mut(|registered| registered.push(region));
+}
+
+/// Regions of the last rendered frame, shared between the view and events actors.
+#[derive(Default)]
+pub(crate) struct Regions(Mutex<Vec<Region>>);
+
+impl Regions {
+    /// Discards anything registered outside of a frame.
+    pub(crate) fn begin_frame(&self) {
+        REGISTERED.with_borrow_mut(Vec::clear);
+    }
+
+    /// Replaces the regions of the previous frame with the ones registered while rendering.
+    pub(crate) fn end_frame(&self) {
+        let registered = REGISTERED.take();
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = registered;
+    }
+
+    /// Returns the message of the topmost region hit by the gesture, if any.
+    pub(crate) fn hit<Msg: 'static>(&self, gesture: &MouseGesture) -> Option<Msg> {
+        let (trigger, position) = match *gesture {
+            MouseGesture::Click {
+                button: MouseButton::Left,
+                position,
+                ..
+            } => (Trigger::Click, position),
+            MouseGesture::DoubleClick {
+                button: MouseButton::Left,
+                position,
+                ..
+            } => (Trigger::DoubleClick, position),
+            _ => return None,
+        };
+
+        let regions = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+        let region = regions
+            .iter()
+            .rev()
+            .find(|region| region.trigger == trigger && region.area.contains(position))?;
+
+        let message = (region.message)().downcast::<Msg>();
+
+        debug_assert!(
+            message.is_ok(),
+            "a region was registered with a {} message, but the program receives {}",
+            region.type_name,
+            type_name::<Msg>(),
+        );
+
+        message.ok().map(|msg| *msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::layout::Position;
+
+    fn click(x: u16, y: u16) -> MouseGesture {
+        MouseGesture::Click {
+            button: MouseButton::Left,
+            position: Position::new(x, y),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn double_click(x: u16, y: u16) -> MouseGesture {
+        MouseGesture::DoubleClick {
+            button: MouseButton::Left,
+            position: Position::new(x, y),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Regions registered while rendering a frame with `render`.
+    fn frame(regions: &Regions, render: impl FnOnce()) {
+        regions.begin_frame();
+        render();
+        regions.end_frame();
+    }
+
+    #[test]
+    fn the_region_registered_last_wins() {
+        let regions = Regions::default();
+        frame(&regions, || {
+            Rect::new(0, 0, 20, 10).on_click("background");
+            Rect::new(5, 5, 5, 2).on_click("button");
+        });
+
+        assert_eq!(regions.hit(&click(6, 6)), Some("button"));
+        assert_eq!(regions.hit(&click(1, 1)), Some("background"));
+        assert_eq!(regions.hit::<&str>(&click(30, 1)), None);
+    }
+
+    #[test]
+    fn regions_only_get_their_own_gesture() {
+        let regions = Regions::default();
+        frame(&regions, || {
+            Rect::new(0, 0, 20, 10).on_click("select");
+            Rect::new(0, 0, 20, 10).on_double_click("open");
+        });
+
+        assert_eq!(regions.hit(&click(1, 1)), Some("select"));
+        assert_eq!(regions.hit(&double_click(1, 1)), Some("open"));
+
+        let right_click = MouseGesture::Click {
+            button: MouseButton::Right,
+            position: Position::new(1, 1),
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(regions.hit::<&str>(&right_click), None);
+    }
+
+    #[test]
+    fn every_frame_replaces_the_regions_of_the_previous_one() {
+        let regions = Regions::default();
+        frame(&regions, || Rect::new(0, 0, 10, 10).on_click("first"));
+        frame(&regions, || Rect::new(20, 0, 10, 10).on_click("second"));
+
+        assert_eq!(regions.hit::<&str>(&click(1, 1)), None);
+        assert_eq!(regions.hit(&click(21, 1)), Some("second"));
+
+        frame(&regions, || {});
+        assert_eq!(regions.hit::<&str>(&click(21, 1)), None);
+    }
+
+    #[test]
+    fn regions_registered_outside_of_a_frame_are_discarded() {
+        let regions = Regions::default();
+        Rect::new(0, 0, 10, 10).on_click("stray");
+        frame(&regions, || {});
+
+        assert_eq!(regions.hit::<&str>(&click(1, 1)), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "but the program receives")]
+    fn clicking_a_region_of_another_message_type_panics() {
+        let regions = Regions::default();
+        frame(&regions, || Rect::new(0, 0, 10, 10).on_click(1_u8));
+
+        regions.hit::<&str>(&click(1, 1));
+    }
+}
@@ -0,0 +1,335 @@
+//! Recording and replaying of terminal input sessions.
+//!
+//! A session file is a JSON-lines file where every line is a [`RecordedEvent`]:
+//! the terminal event read by the events actor, the time elapsed since the
+//! recording started and the size of the terminal at that moment.
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(thiserror::Error, Debug)]
+pub enum SessionError {
+    #[error("Failed to access the session file")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to encode a session entry")]
+    Encode(#[source] serde_json::Error),
+    #[error("Invalid replay speed {0}, it must be a positive number")]
+    Speed(f64),
+    #[error(
+        "Session entry at line {line} was recorded in a {}x{} terminal, not {}x{}",
+        .recorded.0, .recorded.1, .current.0, .current.1
+    )]
+    Size {
+        line: usize,
+        recorded: (u16, u16),
+        current: (u16, u16),
+    },
+    #[error("Malformed session entry at line {line}")]
+    Format {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A single terminal event captured during a recording.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    /// Milliseconds elapsed since the recording started.
+    pub elapsed_ms: u64,
+    /// Terminal size (columns, rows) when the event was read.
+    pub size: (u16, u16),
+    pub event: Event,
+}
+
+/// Appends every event it is given to a session file.
+pub(crate) struct Recorder {
+    writer: BufWriter<File>,
+    started_at: Instant,
+    /// Size of the terminal read last, kept when it can't be read.
+    size: (u16, u16),
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path) -> Result<Self, SessionError> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+            started_at: Instant::now(),
+            size: (0, 0),
+        })
+    }
+
+    pub(crate) fn record(&mut self, event: &Event) -> Result<(), SessionError> {
+        // Failing to read the size must not stop the events from being read
+        match crossterm::terminal::size() {
+            Ok(size) => self.size = size,
+            #[cfg(feature = "tracing")]
+            Err(error) => tracing::warn!(%error, "failed to read the terminal size"),
+            #[cfg(not(feature = "tracing"))]
+            Err(_) => {}
+        }
+
+        let entry = RecordedEvent {
+            elapsed_ms: self.started_at.elapsed().as_millis() as u64,
+            size: self.size,
+            event: event.clone(),
+        };
+
+        let line = serde_json::to_string(&entry).map_err(SessionError::Encode)?;
+
+        // Flush on every event so the file is usable even if the program crashes
+        writeln!(self.writer, "{line}")?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Reads a session file and yields its events,
+/// waiting between them to reproduce the original timing.
+pub(crate) struct Replay {
+    lines: Lines<BufReader<File>>,
+    line: usize,
+    speed: f64,
+    last_elapsed_ms: u64,
+    /// Size of the terminal the session is replayed in, if it could be read.
+    size: Option<(u16, u16)>,
+}
+
+impl Replay {
+    /// Opens a session to replay in a terminal of the given size.
+    ///
+    /// Events recorded in a terminal of another size fail the replay, as the program
+    /// would not see the same screen they were meant for.
+    pub(crate) fn open(
+        path: &Path,
+        speed: f64,
+        size: Option<(u16, u16)>,
+    ) -> Result<Self, SessionError> {
+        if speed.is_nan() || speed <= 0.0 {
+            return Err(SessionError::Speed(speed));
+        }
+
+        Ok(Self {
+            lines: BufReader::new(File::open(path)?).lines(),
+            line: 0,
+            speed,
+            last_elapsed_ms: 0,
+            size,
+        })
+    }
+}
+
+impl Iterator for Replay {
+    type Item = Result<Event, SessionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = loop {
+            self.line += 1;
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => break line,
+                Err(error) => return Some(Err(error.into())),
+            }
+        };
+
+        let entry: RecordedEvent = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            Err(source) => {
+                return Some(Err(SessionError::Format {
+                    line: self.line,
+                    source,
+                }));
+            }
+        };
+
+        // The size is (0, 0) when the recorder could not read it
+        if let Some(current) = self.size
+            && entry.size != (0, 0)
+            && entry.size != current
+        {
+            return Some(Err(SessionError::Size {
+                line: self.line,
+                recorded: entry.size,
+                current,
+            }));
+        }
+
+        let delay = entry.elapsed_ms.saturating_sub(self.last_elapsed_ms) as f64 / self.speed;
+        thread::sleep(Duration::try_from_secs_f64(delay / 1000.0).unwrap_or_default());
+        self.last_elapsed_ms = entry.elapsed_ms;
+
+        Some(Ok(entry.event))
+    }
+}
+
+/// Recording and replay settings handed over to the events actor.
+pub(crate) struct Session {
+    pub(crate) recorder: Option<Recorder>,
+    pub(crate) replay: Option<Replay>,
+}
+
+impl Session {
+    pub(crate) fn open(
+        record: Option<&Path>,
+        replay: Option<(&Path, f64)>,
+    ) -> Result<Self, SessionError> {
+        Ok(Self {
+            recorder: record.map(Recorder::create).transpose()?,
+            replay: replay
+                .map(|(path, speed)| Replay::open(path, speed, crossterm::terminal::size().ok()))
+                .transpose()?,
+        })
+    }
+
+    pub(crate) fn record(&mut self, event: &Event) -> Result<(), SessionError> {
+        match self.recorder.as_mut() {
+            Some(recorder) => recorder.record(event),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Session file of a test, removed when dropped.
+    struct SessionFile(PathBuf);
+
+    impl SessionFile {
+        fn new(name: &str) -> Self {
+            let file = format!("teatui-session-{}-{name}.jsonl", std::process::id());
+            Self(std::env::temp_dir().join(file))
+        }
+
+        fn write(name: &str, entries: &[RecordedEvent]) -> Self {
+            let file = Self::new(name);
+            let lines: Vec<String> = entries
+                .iter()
+                .map(|entry| serde_json::to_string(entry).unwrap())
+                .collect();
+            fs::write(&file.0, lines.join("\n")).unwrap();
+            file
+        }
+
+        fn replay(&self, size: Option<(u16, u16)>) -> Replay {
+            Replay::open(&self.0, f64::INFINITY, size).unwrap()
+        }
+    }
+
+    impl Drop for SessionFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    fn entry(size: (u16, u16), event: Event) -> RecordedEvent {
+        RecordedEvent {
+            elapsed_ms: 0,
+            size,
+            event,
+        }
+    }
+
+    #[test]
+    fn recorded_events_are_replayed_in_order() {
+        let file = SessionFile::new("round-trip");
+        let events = [
+            key('a'),
+            Event::Paste("pasted".into()),
+            Event::Resize(100, 30),
+        ];
+
+        let mut recorder = Recorder::create(&file.0).unwrap();
+        for event in &events {
+            recorder.record(event).unwrap();
+        }
+        drop(recorder);
+
+        let replayed: Vec<Event> = file.replay(None).map(Result::unwrap).collect();
+        assert_eq!(replayed, events);
+    }
+
+    #[test]
+    fn recordings_keep_the_time_of_every_event() {
+        let file = SessionFile::new("elapsed");
+        let mut recorder = Recorder::create(&file.0).unwrap();
+        recorder.record(&key('a')).unwrap();
+        thread::sleep(Duration::from_millis(20));
+        recorder.record(&key('b')).unwrap();
+        drop(recorder);
+
+        let entries: Vec<RecordedEvent> = fs::read_to_string(&file.0)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(entries[1].elapsed_ms >= entries[0].elapsed_ms + 20);
+    }
+
+    #[test]
+    fn replaying_in_a_terminal_of_another_size_fails() {
+        let file = SessionFile::write(
+            "size",
+            &[entry((80, 24), key('a')), entry((100, 30), key('b'))],
+        );
+        let mut replay = file.replay(Some((80, 24)));
+
+        assert_eq!(replay.next().unwrap().unwrap(), key('a'));
+        assert!(matches!(
+            replay.next(),
+            Some(Err(SessionError::Size {
+                line: 2,
+                recorded: (100, 30),
+                current: (80, 24),
+            }))
+        ));
+    }
+
+    #[test]
+    fn events_of_an_unknown_size_are_replayed_in_any_terminal() {
+        let file = SessionFile::write("unknown-size", &[entry((0, 0), key('a'))]);
+        let replayed: Vec<Event> = file.replay(Some((80, 24))).map(Result::unwrap).collect();
+
+        assert_eq!(replayed, [key('a')]);
+    }
+
+    #[test]
+    fn reports_the_line_of_a_malformed_entry() {
+        let file = SessionFile::write("malformed", &[entry((80, 24), key('a'))]);
+        let mut source = fs::read_to_string(&file.0).unwrap();
+        source.push_str("\n\n{\"elapsed_ms\": 1}\n");
+        fs::write(&file.0, source).unwrap();
+
+        let mut replay = file.replay(None);
+        assert!(replay.next().unwrap().is_ok());
+        assert!(matches!(
+            replay.next(),
+            Some(Err(SessionError::Format { line: 3, .. }))
+        ));
+    }
+
+    #[test]
+    fn replay_speeds_must_be_positive() {
+        let file = SessionFile::write("speed", &[]);
+
+        for speed in [0.0, -1.0, f64::NAN] {
+            assert!(matches!(
+                Replay::open(&file.0, speed, None),
+                Err(SessionError::Speed(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Setup and teardown of the terminal used by the program.
+use crossterm::{
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
+};
+use ratatui::DefaultTerminal;
+use std::io::stdout;
+
+/// Terminal modes enabled on top of the ones set by [`ratatui::init`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TerminalModes {
+    pub(crate) mouse_capture: bool,
+    pub(crate) bracketed_paste: bool,
+    pub(crate) focus_change: bool,
+    pub(crate) keyboard_enhancement: Option<KeyboardEnhancementFlags>,
+}
+
+/// Initializes the terminal and enables the requested modes.
+///
+/// Returns the modes that were actually enabled, which must be given back to [`restore`].
+/// The modes are also disabled if the program panics.
+pub(crate) fn init(mut modes: TerminalModes) -> (DefaultTerminal, TerminalModes) {
+    let terminal = ratatui::init();
+
+    // Pushing keyboard flags into a terminal that doesn't understand them
+    // would print garbage, so we only do it when the terminal supports them.
+    if modes.keyboard_enhancement.is_some()
+        && !crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false)
+    {
+        modes.keyboard_enhancement = None;
+    }
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        disable(modes);
+        hook(info);
+    }));
+
+    let mut stdout = stdout();
+
+    if modes.mouse_capture {
+        let _ = execute!(stdout, EnableMouseCapture);
+    }
+
+    if modes.bracketed_paste {
+        let _ = execute!(stdout, EnableBracketedPaste);
+    }
+
+    if modes.focus_change {
+        let _ = execute!(stdout, EnableFocusChange);
+    }
+
+    if let Some(flags) = modes.keyboard_enhancement {
+        let _ = execute!(stdout, PushKeyboardEnhancementFlags(flags));
+    }
+
+    (terminal, modes)
+}
+
+/// Disables the requested modes and restores the terminal to its original state.
+pub(crate) fn restore(modes: TerminalModes) {
+    disable(modes);
+    ratatui::restore();
+}
+
+fn disable(modes: TerminalModes) {
+    let mut stdout = stdout();
+
+    if modes.keyboard_enhancement.is_some() {
+        let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+    }
+
+    if modes.focus_change {
+        let _ = execute!(stdout, DisableFocusChange);
+    }
+
+    if modes.bracketed_paste {
+        let _ = execute!(stdout, DisableBracketedPaste);
+    }
+
+    if modes.mouse_capture {
+        let _ = execute!(stdout, DisableMouseCapture);
+    }
+}
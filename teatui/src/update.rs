@@ -1,5 +1,11 @@
 //! Actor responsible of maintaining the state of the application.
-use std::sync::mpsc::{Receiver, SendError, Sender};
+use crate::pipeline::Pipeline;
+use crate::queue::{Receiver, Sender};
+use crossterm::event::Event;
+use std::convert::Infallible;
+use std::fmt::Debug;
+use std::sync::mpsc::{RecvTimeoutError, SendError, TryRecvError};
+use std::time::Instant;
 
 /// Tells the runtime what to do with the previous message.
 ///
@@ -11,6 +17,65 @@ pub enum Update<M, E> {
     Next(M, Option<E>),
 }
 
+impl<M, E> Update<M, E> {
+    /// Transforms the new model, if any.
+    pub fn map_model<N>(self, f: impl FnOnce(M) -> N) -> Update<N, E> {
+        match self {
+            Self::Exit => Update::Exit,
+            Self::Next(model, effect) => Update::Next(f(model), effect),
+        }
+    }
+
+    /// Transforms the side effect, if any.
+    pub fn map_effect<F>(self, f: impl FnOnce(E) -> F) -> Update<M, F> {
+        match self {
+            Self::Exit => Update::Exit,
+            Self::Next(model, effect) => Update::Next(model, effect.map(f)),
+        }
+    }
+
+    /// Embeds the update of a child component into the update of its parent,
+    /// putting the new child model back into the parent model and lifting its effect:
+    ///
+    /// ```no_run
+    /// # use teatui::update::Update;
+    /// # struct Todo;
+    /// # impl Todo {
+    /// #     fn update(self, _msg: TodoMessage) -> Update<Todo, TodoEffect> { Update::Next(self, None) }
+    /// # }
+    /// # struct TodoMessage;
+    /// # struct TodoEffect;
+    /// # struct Model { todo: Todo }
+    /// # enum Message { Todo(TodoMessage) }
+    /// # enum Effect { Todo(TodoEffect) }
+    /// # fn update(model: Model, msg: Message) -> Update<Model, Effect> {
+    /// # match msg {
+    /// Message::Todo(msg) => model
+    ///     .todo
+    ///     .update(msg)
+    ///     .lift(|todo| Model { todo, ..model }, Effect::Todo),
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// A child returning [`Update::Exit`] exits the program,
+    /// parents willing to intercept it should match on the child update instead.
+    pub fn lift<N, F>(
+        self,
+        model: impl FnOnce(M) -> N,
+        effect: impl FnOnce(E) -> F,
+    ) -> Update<N, F> {
+        self.map_model(model).map_effect(effect)
+    }
+}
+
+impl<M> Update<M, Infallible> {
+    /// Same as [`Update::lift`], for components which never return an effect.
+    pub fn lift_model<N, F>(self, model: impl FnOnce(M) -> N) -> Update<N, F> {
+        self.lift(model, |never| match never {})
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum UpdateError<M, Eff>
 where
@@ -22,16 +87,36 @@ where
     ViewSend(#[from] SendError<M>),
 }
 
+/// Values received by the update actor.
+pub(crate) enum Input<Msg> {
+    /// A terminal event, converted into messages by the pipeline.
+    Event(Event),
+    /// A message ready to be given to the update function.
+    Message(Msg),
+}
+
+impl<Msg> Sender<Input<Msg>> {
+    pub(crate) fn send_message(&self, message: Msg) -> Result<(), SendError<Msg>> {
+        self.send(Input::Message(message))
+            .map_err(|SendError(input)| match input {
+                Input::Message(message) => SendError(message),
+                Input::Event(_) => unreachable!("a message was sent"),
+            })
+    }
+}
+
 pub(crate) fn run<M, Msg, Eff, F>(
     mut model: M,
     initial_effect: Option<Eff>,
     update_fn: F,
-    rx: Receiver<Msg>,
+    mut pipeline: Pipeline<M, Msg>,
+    rx: Receiver<Input<Msg>>,
     view_tx: Sender<M>,
     effects_tx: Sender<(M, Eff)>,
 ) -> Result<(), UpdateError<M, Eff>>
 where
     F: Fn(M, Msg) -> Update<M, Eff>,
+    Msg: From<Event> + 'static,
     Eff: Sync + Send + 'static,
     M: Clone + Sync + Send + 'static,
 {
@@ -40,26 +125,74 @@ where
     }
 
     loop {
-        let Ok(msg) = rx.recv() else {
-            return Ok(());
+        // Wake up when a pending key sequence times out or the model wants a tick,
+        // even if events keep coming
+        let now = Instant::now();
+        let input = match pipeline.deadline(&model) {
+            // Inputs already waiting go first, so they are never starved by ticks
+            Some(deadline) if deadline <= now => match rx.try_recv() {
+                Ok(input) => Some(input),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            },
+            Some(deadline) => match rx.recv_timeout(deadline - now) {
+                Ok(input) => Some(input),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            },
+            None => match rx.recv() {
+                Ok(input) => Some(input),
+                Err(_) => return Ok(()),
+            },
         };
 
-        let update = update_fn(model, msg);
-
-        let (new_model, effect) = match update {
-            Update::Exit => return Ok(()),
-            Update::Next(new_model, effect) => (new_model, effect),
+        let messages = match input {
+            Some(Input::Message(msg)) => vec![msg],
+            Some(Input::Event(event)) => pipeline.process(&model, event),
+            None => pipeline.expire(&model, Instant::now()),
         };
 
-        // Send the new model to the view
-        view_tx.send(new_model.clone())?;
+        for msg in messages {
+            // Formatting every message is only worth it while the overlay lists them
+            if let (Some(monitor), Some(describe)) = (&pipeline.monitor, pipeline.describe)
+                && monitor.is_visible()
+            {
+                monitor.message_received(describe(&msg));
+            }
 
-        // After the view is notified of the new model,
-        // execute side effects if any
-        if let Some(effect) = effect {
-            effects_tx.send((new_model.clone(), effect))?;
-        }
+            // Messages are only formatted when the program describes them for the overlay,
+            // so tracing doesn't require them to implement Debug
+            #[cfg(feature = "tracing")]
+            let span = tracing::debug_span!(
+                "update",
+                message = pipeline.describe.map_or_else(
+                    || std::any::type_name::<Msg>().to_string(),
+                    |describe| describe(&msg)
+                ),
+                pending = rx.depth()
+            )
+            .entered();
 
-        model = new_model;
+            let update = update_fn(model, msg);
+
+            #[cfg(feature = "tracing")]
+            span.exit();
+
+            let (new_model, effect) = match update {
+                Update::Exit => return Ok(()),
+                Update::Next(new_model, effect) => (new_model, effect),
+            };
+
+            // Send the new model to the view
+            view_tx.send(new_model.clone())?;
+
+            // After the view is notified of the new model,
+            // execute side effects if any
+            if let Some(effect) = effect {
+                effects_tx.send((new_model.clone(), effect))?;
+            }
+
+            model = new_model;
+        }
     }
 }
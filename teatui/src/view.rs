@@ -1,7 +1,42 @@
 //! Actor responsible of rendering the model into the terminal.
+use crate::diagnostics::{Monitor, REFRESH_INTERVAL};
+use crate::queue::Receiver;
+use crate::regions::Regions;
 use ratatui::DefaultTerminal;
+use ratatui::layout::{Position, Rect};
 use ratatui::widgets::Widget;
-use std::sync::mpsc::Receiver;
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Instant;
+
+thread_local! {
+    /// Cursor position requested while the current frame is being rendered.
+    static CURSOR: Cell<Option<Position>> = const { Cell::new(None) };
+}
+
+/// Shows the terminal cursor at `position` once the frame being rendered is drawn.
+///
+/// Widgets call this while rendering, usually to show where text will be inserted.
+/// If several widgets place the cursor, the last one wins,
+/// and the cursor is hidden in frames where no widget places it.
+pub fn set_cursor(position: Position) {
+    CURSOR.set(Some(position));
+}
+
+/// Hides the cursor placed so far in the current frame, for widgets drawn above the one
+/// which placed it.
+pub(crate) fn hide_cursor() {
+    CURSOR.set(None);
+}
+
+/// Hides the cursor placed so far if it is inside `area`, for widgets drawn above
+/// only part of the frame.
+pub(crate) fn hide_cursor_in(area: Rect) {
+    if CURSOR.get().is_some_and(|position| area.contains(position)) {
+        CURSOR.set(None);
+    }
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum ViewError {
@@ -14,20 +49,78 @@ pub(crate) fn run<M, F, W>(
     mut terminal: DefaultTerminal,
     view_fn: F,
     rx: Receiver<M>,
+    monitor: Option<Arc<Monitor>>,
+    regions: Arc<Regions>,
 ) -> Result<(), ViewError>
 where
+    M: Clone,
     W: Widget,
     F: Fn(M) -> W,
 {
+    // Whether the model being drawn was already drawn, to refresh the diagnostics overlay
+    let mut refresh = false;
+
     loop {
+        let started_at = Instant::now();
+
+        // The diagnostics overlay refreshes periodically,
+        // so we need to keep the model around to draw it again
+        let retained = monitor.as_ref().map(|_| model.clone());
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("view", pending = rx.depth()).entered();
+
+        // Regions can be registered by the view function as well as while rendering
+        regions.begin_frame();
+
         let widget = view_fn(model);
 
-        terminal.draw(|frame| frame.render_widget(widget, frame.area()))?;
+        #[cfg(feature = "tracing")]
+        span.exit();
 
-        let Ok(new_model) = rx.recv() else {
-            return Ok(());
-        };
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("draw").entered();
+
+        terminal.draw(|frame| {
+            frame.render_widget(widget, frame.area());
+
+            if let Some(position) = CURSOR.take() {
+                frame.set_cursor_position(position);
+            }
 
-        model = new_model;
+            if let Some(monitor) = monitor.as_ref().filter(|monitor| monitor.is_visible()) {
+                frame.render_widget(monitor.hud(), frame.area());
+            }
+        })?;
+
+        regions.end_frame();
+
+        #[cfg(feature = "tracing")]
+        span.exit();
+
+        (model, refresh) = match (&monitor, retained) {
+            (Some(monitor), Some(retained)) => {
+                monitor.frame_rendered(started_at.elapsed(), refresh);
+                let was_visible = monitor.is_visible();
+
+                // Redraw the same model while the overlay is shown or has just been toggled
+                loop {
+                    match rx.recv_timeout(REFRESH_INTERVAL) {
+                        Ok(new_model) => break (new_model, false),
+                        Err(RecvTimeoutError::Timeout) if was_visible || monitor.is_visible() => {
+                            break (retained, true);
+                        }
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                    }
+                }
+            }
+            _ => {
+                let Ok(new_model) = rx.recv() else {
+                    return Ok(());
+                };
+                (new_model, false)
+            }
+        };
     }
 }